@@ -1,18 +1,22 @@
 use crate::api::file_cache::{CacheHit, FileCache};
 use crate::api::hackerweb_response::decode_json as decode_hackerweb_json;
+use crate::api::search::{AlgoliaResponse, SearchPage};
 use crate::api::types::{
-    ApiBackend, Comment, CommentNode, FeedKind, HnItem, Story, StoryThread, WebItem, WebStory,
+    AlgoliaItem, ApiBackend, Comment, CommentNode, FeedKind, HnItem, HnItemKind, PollOption, Story,
+    StoryThread, WebItem, WebStory,
 };
 use crate::logging;
 use anyhow::{anyhow, Context, Result};
+use futures::future::BoxFuture;
 use futures::stream::{self, StreamExt, TryStreamExt};
 use lru::LruCache;
 use reqwest::Client;
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, Mutex, Semaphore};
 
 #[derive(Debug, Clone)]
@@ -31,6 +35,34 @@ pub struct HnClient {
     concurrency: usize,
     revalidate_semaphore: Arc<Semaphore>,
     in_flight: InFlightItems,
+    /// Serve everything from the disk cache. Shared by every clone, so
+    /// flipping it reaches clients already handed to running tasks.
+    offline: Arc<AtomicBool>,
+    failover: Option<Arc<Failover>>,
+    /// The next Algolia listing page per feed. Untitled hits are dropped, so
+    /// a page can come back short and the page cannot be derived from how
+    /// many stories are loaded.
+    algolia_pages: Arc<std::sync::Mutex<HashMap<FeedKind, usize>>>,
+}
+
+/// Where HackerWeb requests go while the mirror is failing.
+struct Failover {
+    firebase: HnClient,
+    cooldown: Duration,
+    /// Until when requests skip HackerWeb and go straight to Firebase.
+    until: std::sync::Mutex<Option<Instant>>,
+}
+
+impl Failover {
+    fn is_active(&self) -> bool {
+        let until = self.until.lock().expect("failover lock poisoned");
+        until.is_some_and(|until| Instant::now() < until)
+    }
+
+    fn start(&self) {
+        let mut until = self.until.lock().expect("failover lock poisoned");
+        *until = Some(Instant::now() + self.cooldown);
+    }
 }
 
 type InFlightItems = Arc<Mutex<HashMap<u64, ItemFlightSender>>>;
@@ -40,6 +72,19 @@ const COMMENT_PREFETCH_EXTRA_DEPTH: usize = 2;
 const COMMENT_PREFETCH_CHILD_CONCURRENCY: usize = 8;
 const REVALIDATE_CONCURRENCY: usize = 4;
 const STALE_ITEM_MAX_AGE: Duration = Duration::from_secs(60 * 60 * 24);
+/// Hits per Algolia listing page, matching a HackerWeb page.
+const ALGOLIA_PAGE_SIZE: usize = 30;
+/// How far back the Algolia stand-in for the Best feed looks.
+const ALGOLIA_BEST_WINDOW: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+
+/// Whether an item read may be answered from the memory and disk caches.
+/// Watch polls read `Fresh`: a cached item never lists the replies posted
+/// since it was stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemRead {
+    Cached,
+    Fresh,
+}
 
 impl HnClient {
     pub fn new(
@@ -68,9 +113,82 @@ impl HnClient {
             concurrency,
             revalidate_semaphore: Arc::new(Semaphore::new(REVALIDATE_CONCURRENCY)),
             in_flight: Arc::new(Mutex::new(HashMap::new())),
+            offline: Arc::new(AtomicBool::new(false)),
+            failover: None,
+            algolia_pages: Arc::new(std::sync::Mutex::new(HashMap::new())),
         })
     }
 
+    /// Retry failed HackerWeb requests against Firebase at `firebase_base_url`,
+    /// then keep using Firebase for `cooldown` before trying HackerWeb again.
+    /// Both share this client's caches.
+    pub fn with_firebase_failover(mut self, firebase_base_url: String, cooldown: Duration) -> Self {
+        assert_eq!(
+            self.backend,
+            ApiBackend::HackerWeb,
+            "only HackerWeb fails over to Firebase"
+        );
+        let firebase = Self {
+            base_url: firebase_base_url.trim_end_matches('/').to_string(),
+            backend: ApiBackend::Firebase,
+            ..self.clone()
+        };
+        self.failover = Some(Arc::new(Failover {
+            firebase,
+            cooldown,
+            until: std::sync::Mutex::new(None),
+        }));
+        self
+    }
+
+    /// The backend answering requests right now.
+    pub fn active_backend(&self) -> ApiBackend {
+        self.active_client().backend
+    }
+
+    pub fn is_failed_over(&self) -> bool {
+        self.failover
+            .as_ref()
+            .is_some_and(|failover| failover.is_active())
+    }
+
+    fn active_client(&self) -> &HnClient {
+        match &self.failover {
+            Some(failover) if failover.is_active() => &failover.firebase,
+            _ => self,
+        }
+    }
+
+    /// Run `request` on the active backend. A HackerWeb failure is retried on
+    /// Firebase, which then stays active for the cooldown.
+    async fn with_failover<'a, T>(
+        &'a self,
+        request: impl Fn(&'a HnClient) -> BoxFuture<'a, Result<T>>,
+    ) -> Result<T> {
+        let client = self.active_client();
+        let error = match request(client).await {
+            Err(error) if client.backend == ApiBackend::HackerWeb => error,
+            result => return result,
+        };
+        let Some(failover) = &self.failover else {
+            return Err(error);
+        };
+        logging::log_error(format!(
+            "hackerweb failed, using firebase for {}s: {error:#}",
+            failover.cooldown.as_secs()
+        ));
+        failover.start();
+        request(&failover.firebase).await
+    }
+
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
     async fn fetch_story_ids_network(&self, feed: FeedKind) -> Result<Vec<u64>> {
         let url = format!("{}{}", self.base_url, feed.firebase_path());
         let label = feed.as_str();
@@ -110,12 +228,28 @@ impl HnClient {
     ///
     /// - **HackerWeb**: fetches pre-assembled pages (page 1 + page 2 if count > 30).
     /// - **Firebase**: fetches story IDs then batch-fetches items.
+    /// - **Algolia**: fetches tagged listing pages, like HackerWeb.
     pub async fn fetch_initial_stories(
         &self,
         feed: FeedKind,
         count: usize,
     ) -> Result<(Vec<u64>, Vec<Story>)> {
-        match self.backend {
+        if self.is_offline() {
+            return self.cached_initial_stories(feed, count).await;
+        }
+        let (story_ids, stories) = self
+            .with_failover(|client| Box::pin(client.fetch_initial_stories_online(feed, count)))
+            .await?;
+        self.cache_feed_background(feed, story_ids.clone());
+        Ok((story_ids, stories))
+    }
+
+    async fn fetch_initial_stories_online(
+        &self,
+        feed: FeedKind,
+        count: usize,
+    ) -> Result<(Vec<u64>, Vec<Story>)> {
+        Ok(match self.backend {
             ApiBackend::HackerWeb => {
                 let mut stories = self.fetch_hackerweb_feed(feed, 1).await?;
                 if count > 30 && stories.len() == 30 {
@@ -124,27 +258,85 @@ impl HnClient {
                 }
                 stories.truncate(count);
                 let ids: Vec<u64> = stories.iter().map(|s| s.id).collect();
-                Ok((ids, stories))
+                (ids, stories)
             }
             ApiBackend::Firebase => {
                 let story_ids = self.fetch_story_ids_network(feed).await?;
                 let ids: Vec<u64> = story_ids.iter().copied().take(count).collect();
                 let stories = self.fetch_stories_batch(&ids).await?;
-                Ok((story_ids, stories))
+                (story_ids, stories)
             }
-        }
+            ApiBackend::Algolia => {
+                let first = self.fetch_algolia_feed(feed, 0).await?;
+                let mut stories = first.hits;
+                let mut next_page = 1;
+                if count > ALGOLIA_PAGE_SIZE && first.has_more {
+                    stories.extend(self.fetch_algolia_feed(feed, 1).await?.hits);
+                    next_page = 2;
+                }
+                // Whole pages stay loaded, so paging picks up where they end.
+                self.set_algolia_page(feed, next_page);
+                let ids: Vec<u64> = stories.iter().map(|s| s.id).collect();
+                (ids, stories)
+            }
+        })
     }
 
     /// Fetch more stories beyond what's already loaded.
     ///
     /// - **HackerWeb**: computes next page number, fetches it.
     /// - **Firebase**: uses stored story_ids to batch-fetch the next slice.
+    /// - **Algolia**: fetches the page after the last one loaded.
     pub async fn fetch_more_stories(
         &self,
         feed: FeedKind,
         story_ids: &[u64],
         loaded_count: usize,
         page_size: usize,
+    ) -> Result<Vec<Story>> {
+        if self.is_offline() {
+            let start = loaded_count.min(story_ids.len());
+            let end = (loaded_count + page_size).min(story_ids.len());
+            return self.cached_stories(&story_ids[start..end]).await;
+        }
+        let stories = self
+            .with_failover(|client| {
+                if client.backend == ApiBackend::Firebase && self.backend == ApiBackend::HackerWeb {
+                    Box::pin(client.fetch_more_after_listed(
+                        feed,
+                        story_ids,
+                        loaded_count,
+                        page_size,
+                    ))
+                } else {
+                    Box::pin(client.fetch_more_stories_online(
+                        feed,
+                        story_ids,
+                        loaded_count,
+                        page_size,
+                    ))
+                }
+            })
+            .await?;
+        // HackerWeb and Algolia list a page at a time, so the cached listing
+        // grows with each page; offline paging reads it back.
+        let appended: Vec<u64> = stories
+            .iter()
+            .map(|story| story.id)
+            .filter(|id| !story_ids.contains(id))
+            .collect();
+        if !appended.is_empty() {
+            self.cache_feed_background(feed, [story_ids, &appended].concat());
+        }
+        Ok(stories)
+    }
+
+    async fn fetch_more_stories_online(
+        &self,
+        feed: FeedKind,
+        story_ids: &[u64],
+        loaded_count: usize,
+        page_size: usize,
     ) -> Result<Vec<Story>> {
         match self.backend {
             ApiBackend::HackerWeb => {
@@ -159,58 +351,183 @@ impl HnClient {
                 let ids = &story_ids[loaded_count..end];
                 self.fetch_stories_batch(ids).await
             }
+            ApiBackend::Algolia => {
+                // A list restored from disk was never paged by this client.
+                let mut page = self
+                    .algolia_pages
+                    .lock()
+                    .expect("algolia page lock poisoned")
+                    .get(&feed)
+                    .copied()
+                    .unwrap_or_else(|| loaded_count.div_ceil(ALGOLIA_PAGE_SIZE));
+                // Skip pages of nothing but untitled hits: an empty page ends
+                // paging for good.
+                loop {
+                    let listing = self.fetch_algolia_feed(feed, page).await?;
+                    page += 1;
+                    if !listing.hits.is_empty() || !listing.has_more {
+                        self.set_algolia_page(feed, page);
+                        return Ok(listing.hits);
+                    }
+                }
+            }
         }
     }
 
+    /// Firebase standing in for HackerWeb. `story_ids` holds what HackerWeb
+    /// listed, or all of Firebase's listing when the first page already came
+    /// from Firebase, so continue down Firebase's own listing past the first
+    /// `loaded_count` of them.
+    async fn fetch_more_after_listed(
+        &self,
+        feed: FeedKind,
+        story_ids: &[u64],
+        loaded_count: usize,
+        page_size: usize,
+    ) -> Result<Vec<Story>> {
+        let loaded = &story_ids[..loaded_count.min(story_ids.len())];
+        let ids: Vec<u64> = self
+            .fetch_story_ids_network(feed)
+            .await?
+            .into_iter()
+            .filter(|id| !loaded.contains(id))
+            .take(page_size)
+            .collect();
+        self.fetch_stories_batch(&ids).await
+    }
+
     /// Fetch a story's discussion — the self-post body when the backend
     /// reports one, plus the root comments.
     ///
     /// - **HackerWeb**: single request to `/item/:id`, returns body + nested tree.
     /// - **Firebase**: recursive item fetches with prefetch depth. The body
     ///   already rode along with the Story, so the thread carries no text.
+    /// - **Algolia**: single request to `/items/:id`, returns body + nested tree.
     pub async fn fetch_comment_roots(&self, story: &Story) -> Result<StoryThread> {
+        if self.is_offline() {
+            return self.cached_comment_roots(story).await;
+        }
+        self.with_failover(|client| {
+            Box::pin(client.fetch_comment_roots_online(story, ItemRead::Cached))
+        })
+        .await
+    }
+
+    /// Re-read a thread past the item caches, for watch mode. HackerWeb and
+    /// Algolia answer with the whole tree per request anyway; Firebase
+    /// refetches the story and every prefetched level.
+    pub async fn fetch_comment_roots_fresh(&self, story: &Story) -> Result<StoryThread> {
+        if self.is_offline() {
+            anyhow::bail!("watching a thread needs the network");
+        }
+        self.with_failover(|client| {
+            Box::pin(client.fetch_comment_roots_online(story, ItemRead::Fresh))
+        })
+        .await
+    }
+
+    async fn fetch_comment_roots_online(
+        &self,
+        story: &Story,
+        read: ItemRead,
+    ) -> Result<StoryThread> {
         match self.backend {
-            ApiBackend::HackerWeb => self.fetch_hackerweb_thread(story.id).await,
+            ApiBackend::HackerWeb => {
+                let thread = self.fetch_hackerweb_thread(story.id).await?;
+                self.cache_thread_background(story, &thread);
+                Ok(thread)
+            }
+            ApiBackend::Algolia => {
+                let thread = self.fetch_algolia_thread(story.id).await?;
+                self.cache_thread_background(story, &thread);
+                Ok(thread)
+            }
             ApiBackend::Firebase => {
-                let kids = if story.kids.is_empty() && story.comment_count > 0 {
-                    // Search results don't include kids — fetch the item to get them.
-                    let item = self.fetch_item(story.id).await?;
+                // Listed stories are in the item cache already; search
+                // results need the fetch for their kids, and a poll for its
+                // parts. A fresh read trusts only the refetched kids.
+                let item = self.fetch_item_with(story.id, read).await?;
+                let kids = if story.kids.is_empty() || read == ItemRead::Fresh {
                     item.kids.unwrap_or_default()
                 } else {
                     story.kids.clone()
                 };
-                if kids.is_empty() {
-                    return Ok(StoryThread::default());
-                }
-                let comments = self
-                    .fetch_comment_nodes_prefetch(&kids, 0, COMMENT_PREFETCH_EXTRA_DEPTH)
-                    .await?;
-                Ok(StoryThread::from_comments(comments))
+                let poll = self
+                    .fetch_items_batch_with(&item.parts.unwrap_or_default(), read)
+                    .await?
+                    .into_iter()
+                    .map(PollOption::from_item)
+                    .collect();
+                let comments = if kids.is_empty() {
+                    vec![]
+                } else {
+                    self.fetch_comment_nodes_prefetch(&kids, 0, COMMENT_PREFETCH_EXTRA_DEPTH, read)
+                        .await?
+                };
+                Ok(StoryThread {
+                    poll,
+                    ..StoryThread::from_comments(comments)
+                })
             }
         }
     }
 
     /// Fetch children of a comment for lazy expand.
     ///
-    /// - **HackerWeb**: all children are pre-loaded; returns empty vec as safety fallback.
+    /// - **HackerWeb**, **Algolia**: all children are pre-loaded; returns empty
+    ///   vec as safety fallback. With failover, HackerWeb asks Firebase: only
+    ///   a thread Firebase served can have unloaded children.
     /// - **Firebase**: recursive item fetches.
     pub async fn fetch_comment_children(
         &self,
         ids: &[u64],
         depth: usize,
     ) -> Result<Vec<CommentNode>> {
-        match self.backend {
-            ApiBackend::HackerWeb => Ok(vec![]),
+        if self.is_offline() {
+            return Ok(self.cached_comment_nodes(ids, depth).await?.nodes);
+        }
+        let client = self
+            .failover
+            .as_ref()
+            .map_or(self, |failover| &failover.firebase);
+        match client.backend {
+            ApiBackend::HackerWeb | ApiBackend::Algolia => Ok(vec![]),
             ApiBackend::Firebase => {
                 if ids.is_empty() {
                     return Ok(vec![]);
                 }
-                self.fetch_comment_nodes_prefetch(ids, depth, COMMENT_PREFETCH_EXTRA_DEPTH)
+                client
+                    .fetch_comment_nodes_prefetch(
+                        ids,
+                        depth,
+                        COMMENT_PREFETCH_EXTRA_DEPTH,
+                        ItemRead::Cached,
+                    )
                     .await
             }
         }
     }
 
+    /// The id a comment replies to, for walking up from a search hit that
+    /// sits below the loaded part of its thread. Backends that serve whole
+    /// threads have no missing levels and answer `None`.
+    pub async fn fetch_comment_parent(&self, id: u64) -> Result<Option<u64>> {
+        if self.is_offline() {
+            return Ok(self
+                .cached_item(id)
+                .await?
+                .and_then(|(item, _)| item.parent));
+        }
+        let client = self
+            .failover
+            .as_ref()
+            .map_or(self, |failover| &failover.firebase);
+        match client.backend {
+            ApiBackend::HackerWeb | ApiBackend::Algolia => Ok(None),
+            ApiBackend::Firebase => Ok(client.fetch_item(id).await?.parent),
+        }
+    }
+
     // ── HackerWeb private methods ──
 
     async fn fetch_hackerweb_feed(&self, feed: FeedKind, page: usize) -> Result<Vec<Story>> {
@@ -230,7 +547,9 @@ impl HnClient {
             .with_context(|| format!("HackerWeb returned an error for {label} page={page}"))?;
         let web_stories: Vec<WebStory> =
             decode_hackerweb_json(response, format!("{label} page={page}")).await?;
-        Ok(web_stories.into_iter().map(Story::from).collect())
+        let stories: Vec<Story> = web_stories.into_iter().map(Story::from).collect();
+        self.cache_listed_stories_background(&stories);
+        Ok(stories)
     }
 
     async fn fetch_hackerweb_thread(&self, story_id: u64) -> Result<StoryThread> {
@@ -254,6 +573,275 @@ impl HnClient {
                 .into_iter()
                 .filter_map(|c| c.into_comment_node(0))
                 .collect(),
+            stale_secs: None,
+            poll: web_item.poll.into_iter().map(PollOption::from).collect(),
+        })
+    }
+
+    // ── Algolia private methods ──
+
+    fn set_algolia_page(&self, feed: FeedKind, page: usize) {
+        self.algolia_pages
+            .lock()
+            .expect("algolia page lock poisoned")
+            .insert(feed, page);
+    }
+
+    async fn fetch_algolia_feed(&self, feed: FeedKind, page: usize) -> Result<SearchPage<Story>> {
+        let (path, tags) = feed.algolia_query();
+        let label = feed.as_str();
+        logging::log_info(format!("algolia: fetching {label} page={page}"));
+        let mut query = vec![
+            ("tags", tags.to_string()),
+            ("hitsPerPage", ALGOLIA_PAGE_SIZE.to_string()),
+            ("page", page.to_string()),
+        ];
+        if feed == FeedKind::Best {
+            let since = std::time::SystemTime::now()
+                .checked_sub(ALGOLIA_BEST_WINDOW)
+                .and_then(|since| since.duration_since(std::time::UNIX_EPOCH).ok())
+                .unwrap_or_default()
+                .as_secs();
+            query.push(("numericFilters", format!("created_at_i>{since}")));
+        }
+        let response: AlgoliaResponse = self
+            .http
+            .get(format!("{}{path}", self.base_url))
+            .query(&query)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Algolia request failed for {label} page={page}"))?
+            .error_for_status()
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Algolia returned an error for {label} page={page}"))?
+            .json()
+            .await
+            .with_context(|| format!("decode Algolia {label} page={page}"))?;
+        let has_more = page + 1 < response.nb_pages;
+        let stories = response.into_stories()?;
+        self.cache_listed_stories_background(&stories);
+        Ok(SearchPage {
+            hits: stories,
+            has_more,
+        })
+    }
+
+    async fn fetch_algolia_thread(&self, story_id: u64) -> Result<StoryThread> {
+        let url = format!("{}/items/{story_id}", self.base_url);
+        logging::log_info(format!("algolia: fetching item id={story_id}"));
+        let item: AlgoliaItem = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Algolia request failed for item id={story_id}"))?
+            .error_for_status()
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Algolia returned an error for item id={story_id}"))?
+            .json()
+            .await
+            .with_context(|| format!("decode Algolia item id={story_id}"))?;
+        Ok(StoryThread {
+            text: item.text.filter(|text| !text.trim().is_empty()),
+            comments: item
+                .children
+                .into_iter()
+                .filter_map(|c| c.into_comment_node(0))
+                .collect(),
+            stale_secs: None,
+            poll: item
+                .options
+                .into_iter()
+                .map(AlgoliaItem::into_poll_option)
+                .collect(),
+        })
+    }
+
+    // ── Disk-cache mirroring (what offline mode reads back) ──
+
+    fn cache_feed_background(&self, feed: FeedKind, story_ids: Vec<u64>) {
+        let Some(file_cache) = self.file_cache.clone() else {
+            return;
+        };
+        crate::tasks::spawn_detached(async move {
+            if let Err(err) = file_cache.put_feed(feed.as_str(), story_ids).await {
+                logging::log_error(format!(
+                    "failed to cache {} listing: {err:#}",
+                    feed.as_str()
+                ));
+            }
+        });
+    }
+
+    /// HackerWeb and Algolia listings never pass through `fetch_item`, so write
+    /// them into the item cache in Firebase's shape. A listing knows nothing of `kids`
+    /// or a poll's `parts`; keep the ones already on disk.
+    fn cache_listed_stories_background(&self, stories: &[Story]) {
+        let Some(file_cache) = self.file_cache.clone() else {
+            return;
+        };
+        let items: Vec<HnItem> = stories
+            .iter()
+            .map(|story| story_item(story, None))
+            .collect();
+        crate::tasks::spawn_detached(async move {
+            for mut item in items {
+                keep_cached_story_fields(&file_cache, &mut item, true).await;
+                if let Err(err) = file_cache.put_item(item.id, item).await {
+                    logging::log_error(format!("failed to cache story: {err:#}"));
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Whole-tree threads likewise skip `fetch_item`. Their poll options come
+    /// without item ids, so a poll keeps the `parts` already on disk.
+    fn cache_thread_background(&self, story: &Story, thread: &StoryThread) {
+        let Some(file_cache) = self.file_cache.clone() else {
+            return;
+        };
+        let mut story = story.clone();
+        story.absorb_text(thread.text.clone());
+        let root_ids = thread.comments.iter().map(|node| node.comment.id).collect();
+        let mut items = vec![story_item(&story, Some(root_ids))];
+        collect_comment_items(&thread.comments, &mut items);
+        crate::tasks::spawn_detached(async move {
+            keep_cached_story_fields(&file_cache, &mut items[0], false).await;
+            for item in items {
+                if let Err(err) = file_cache.put_item(item.id, item).await {
+                    logging::log_error(format!("failed to cache thread item: {err:#}"));
+                    return;
+                }
+            }
+        });
+    }
+
+    // ── Offline reads (disk cache only, never the network) ──
+
+    fn offline_cache(&self) -> Result<&FileCache> {
+        self.file_cache
+            .as_deref()
+            .context("offline mode needs the file cache (drop --no-file-cache)")
+    }
+
+    /// A cached item plus its age when it is past the TTL. Any age is served:
+    /// offline, stale beats nothing.
+    async fn cached_item(&self, id: u64) -> Result<Option<(HnItem, Option<u64>)>> {
+        let hit = self.offline_cache()?.get_item_with_staleness(id).await?;
+        Ok(hit.map(|hit| match hit {
+            CacheHit::Fresh(item) => (item, None),
+            CacheHit::Stale { item, stale_secs } => (item, Some(stale_secs)),
+        }))
+    }
+
+    async fn cached_initial_stories(
+        &self,
+        feed: FeedKind,
+        count: usize,
+    ) -> Result<(Vec<u64>, Vec<Story>)> {
+        let label = feed.as_str();
+        let story_ids = self
+            .offline_cache()?
+            .get_feed(label)
+            .await?
+            .with_context(|| format!("offline: no cached {label} listing"))?;
+        let ids: Vec<u64> = story_ids.iter().copied().take(count).collect();
+        let stories = self.cached_stories(&ids).await?;
+        Ok((story_ids, stories))
+    }
+
+    /// Stories in listing order, stopping at the first one not on disk: paging
+    /// indexes the listing by how many stories are loaded, so the result must
+    /// stay a prefix.
+    async fn cached_stories(&self, ids: &[u64]) -> Result<Vec<Story>> {
+        let mut stories = Vec::with_capacity(ids.len());
+        for &id in ids {
+            let Some((item, stale_secs)) = self.cached_item(id).await? else {
+                break;
+            };
+            let mut story = Story::try_from(item)?;
+            story.stale_secs = stale_secs;
+            stories.push(story);
+        }
+        Ok(stories)
+    }
+
+    async fn cached_comment_roots(&self, story: &Story) -> Result<StoryThread> {
+        let (kids, text, parts, story_stale) = match self.cached_item(story.id).await? {
+            Some((item, stale_secs)) if item.kids.is_some() || story.comment_count == 0 => (
+                item.kids.unwrap_or_default(),
+                item.text,
+                item.parts.unwrap_or_default(),
+                stale_secs,
+            ),
+            _ if !story.kids.is_empty() => (story.kids.clone(), None, vec![], None),
+            _ if story.comment_count == 0 => (vec![], None, vec![], None),
+            _ => anyhow::bail!(
+                "offline: discussion for story id={} is not cached",
+                story.id
+            ),
+        };
+        let cached = self.cached_comment_nodes(&kids, 0).await?;
+        // Options Firebase served are items on disk; a partial poll would
+        // misstate the vote, so it shows only when every option is there.
+        let mut poll = Vec::with_capacity(parts.len());
+        for &id in &parts {
+            match self.cached_item(id).await? {
+                Some((item, _)) => poll.push(PollOption::from_item(item)),
+                None => {
+                    poll.clear();
+                    break;
+                }
+            }
+        }
+        Ok(StoryThread {
+            text: text.filter(|text| !text.trim().is_empty()),
+            comments: cached.nodes,
+            stale_secs: story_stale.max(cached.stale_secs),
+            poll,
+        })
+    }
+
+    /// Rebuild as much of a comment tree as the disk holds. A comment whose
+    /// replies are all cached comes back with them attached; otherwise its
+    /// replies stay unloaded, and expanding it asks the cache again.
+    fn cached_comment_nodes<'a>(
+        &'a self,
+        ids: &'a [u64],
+        depth: usize,
+    ) -> BoxFuture<'a, Result<CachedNodes>> {
+        Box::pin(async move {
+            let mut cached = CachedNodes {
+                nodes: Vec::with_capacity(ids.len()),
+                stale_secs: None,
+                complete: true,
+            };
+            for &id in ids {
+                let Some((item, stale_secs)) = self.cached_item(id).await? else {
+                    cached.complete = false;
+                    continue;
+                };
+                if item.dead.unwrap_or(false) || item.deleted.unwrap_or(false) {
+                    continue;
+                }
+                cached.stale_secs = cached.stale_secs.max(stale_secs);
+                let mut comment = Comment::from_item(item, depth);
+                let kids = comment.kids.clone();
+                let mut children = vec![];
+                if !kids.is_empty() {
+                    let replies = self.cached_comment_nodes(&kids, depth + 1).await?;
+                    if replies.complete {
+                        cached.stale_secs = cached.stale_secs.max(replies.stale_secs);
+                        children = replies.nodes;
+                        comment.children_loaded = true;
+                    }
+                }
+                cached.nodes.push(CommentNode { comment, children });
+            }
+            Ok(cached)
         })
     }
 
@@ -290,17 +878,27 @@ impl HnClient {
         self.fetch_item_network_deduped(id).await
     }
 
+    async fn fetch_item_with(&self, id: u64, read: ItemRead) -> Result<HnItem> {
+        match read {
+            ItemRead::Cached => self.fetch_item(id).await,
+            ItemRead::Fresh => self.fetch_item_network_deduped(id).await,
+        }
+    }
+
     pub async fn fetch_items_batch(&self, ids: &[u64]) -> Result<Vec<HnItem>> {
+        self.fetch_items_batch_with(ids, ItemRead::Cached).await
+    }
+
+    async fn fetch_items_batch_with(&self, ids: &[u64], read: ItemRead) -> Result<Vec<HnItem>> {
         let concurrency = self.concurrency;
 
-        let mut out =
-            stream::iter(ids.iter().copied().enumerate())
-                .map(|(idx, id)| async move {
-                    Ok::<_, anyhow::Error>((idx, self.fetch_item(id).await?))
-                })
-                .buffer_unordered(concurrency)
-                .try_collect::<Vec<_>>()
-                .await?;
+        let mut out = stream::iter(ids.iter().copied().enumerate())
+            .map(|(idx, id)| async move {
+                Ok::<_, anyhow::Error>((idx, self.fetch_item_with(id, read).await?))
+            })
+            .buffer_unordered(concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
 
         out.sort_by_key(|(idx, _)| *idx);
         Ok(out.into_iter().map(|(_, item)| item).collect())
@@ -319,8 +917,9 @@ impl HnClient {
         ids: &[u64],
         depth: usize,
         prefetch_extra_depth: usize,
+        read: ItemRead,
     ) -> Result<Vec<CommentNode>> {
-        let items = self.fetch_items_batch(ids).await?;
+        let items = self.fetch_items_batch_with(ids, read).await?;
 
         let mut nodes = Vec::with_capacity(items.len());
         for item in items {
@@ -355,7 +954,7 @@ impl HnClient {
         let child_results = stream::iter(child_batches)
             .map(|(idx, kids)| async move {
                 let children = self
-                    .fetch_comment_nodes_prefetch(&kids, depth + 1, prefetch_extra_depth - 1)
+                    .fetch_comment_nodes_prefetch(&kids, depth + 1, prefetch_extra_depth - 1, read)
                     .await?;
                 Ok::<_, anyhow::Error>((idx, children))
             })
@@ -438,3 +1037,76 @@ impl HnClient {
         });
     }
 }
+
+struct CachedNodes {
+    nodes: Vec<CommentNode>,
+    stale_secs: Option<u64>,
+    /// Every requested ID was on disk.
+    complete: bool,
+}
+
+/// Fill in what a rewrite of a cached story item cannot know from `item`
+/// itself: a poll's `parts`, the type when the backend did not report one,
+/// and with `keep_kids` the ids of its replies.
+async fn keep_cached_story_fields(file_cache: &FileCache, item: &mut HnItem, keep_kids: bool) {
+    let cached = match file_cache.get_item_with_staleness(item.id).await {
+        Ok(Some(CacheHit::Fresh(cached) | CacheHit::Stale { item: cached, .. })) => Some(cached),
+        Ok(None) => None,
+        Err(err) => {
+            logging::log_error(format!("failed to read cached story: {err:#}"));
+            None
+        }
+    };
+    if let Some(cached) = cached {
+        if keep_kids {
+            item.kids = cached.kids;
+        }
+        item.parts = cached.parts;
+        item.kind = item.kind.or(cached.kind);
+    }
+    item.kind = item.kind.or(Some(HnItemKind::Story));
+}
+
+/// `kind` is left unset when the story's backend did not report one, for
+/// [`keep_cached_story_fields`] to settle.
+fn story_item(story: &Story, kids: Option<Vec<u64>>) -> HnItem {
+    HnItem {
+        id: story.id,
+        kind: story.kind,
+        by: Some(story.by.clone()),
+        time: Some(story.time),
+        title: Some(story.title.clone()),
+        url: story.url.clone(),
+        text: story.text.clone(),
+        score: Some(story.score),
+        descendants: Some(story.comment_count),
+        kids,
+        parent: None,
+        parts: None,
+        dead: None,
+        deleted: None,
+    }
+}
+
+fn collect_comment_items(nodes: &[CommentNode], items: &mut Vec<HnItem>) {
+    for node in nodes {
+        let comment = &node.comment;
+        items.push(HnItem {
+            id: comment.id,
+            kind: Some(HnItemKind::Comment),
+            by: comment.by.clone(),
+            time: comment.time,
+            title: None,
+            url: None,
+            text: Some(comment.text.clone()),
+            score: None,
+            descendants: None,
+            kids: Some(comment.kids.clone()),
+            parent: None,
+            parts: None,
+            dead: None,
+            deleted: None,
+        });
+        collect_comment_items(&node.children, items);
+    }
+}
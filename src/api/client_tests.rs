@@ -1,4 +1,6 @@
-use super::{ApiBackend, HnClient, Story};
+use super::file_cache::{CacheHit, FileCache};
+use super::types::{HnItem, HnItemKind};
+use super::{ApiBackend, DiskCacheConfig, FeedKind, HnClient, SearchClient, Story, UserClient};
 use std::io::{Read, Write};
 use std::net::{TcpListener, TcpStream};
 use std::thread;
@@ -69,6 +71,37 @@ fn serve_status_once(status: &str) -> (String, thread::JoinHandle<()>) {
     })
 }
 
+/// Answer one connection per body, in order, and hand back each request line
+/// so a test can check which pages were asked for.
+fn serve_json_sequence(bodies: Vec<&'static str>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind test server");
+    let address = listener.local_addr().expect("test server address");
+    let handle = thread::spawn(move || {
+        let mut request_lines = Vec::new();
+        for body in bodies {
+            let (mut stream, _) = listener.accept().expect("accept request");
+            let mut request = [0_u8; 4096];
+            let read = stream.read(&mut request).expect("read request");
+            let request = String::from_utf8_lossy(&request[..read]);
+            request_lines.push(request.lines().next().unwrap_or_default().to_string());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\n\
+                 Content-Type: application/json\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\
+                 \r\n\
+                 {body}",
+                body.len()
+            );
+            stream
+                .write_all(response.as_bytes())
+                .expect("write response");
+        }
+        request_lines
+    });
+    (format!("http://{address}"), handle)
+}
+
 fn hackerweb_client(base_url: String) -> HnClient {
     let http = reqwest::Client::builder()
         .pool_max_idle_per_host(10)
@@ -89,6 +122,8 @@ fn story(id: u64) -> Story {
         time: 1,
         comment_count: 1,
         kids: Vec::new(),
+        kind: None,
+        stale_secs: None,
     }
 }
 
@@ -237,3 +272,556 @@ async fn hackerweb_request_failure_does_not_expose_the_base_url() {
     );
     assert!(!message.contains(secret), "{message}");
 }
+
+#[tokio::test]
+async fn a_failing_hackerweb_request_is_retried_on_firebase_which_stays_active() {
+    let (hackerweb_url, hackerweb) = serve_status_once("502 Bad Gateway");
+    let (firebase_url, firebase) =
+        serve_chunked_once(br#"{"id": 1, "type": "story", "kids": []}"#.to_vec());
+    let client = hackerweb_client(hackerweb_url)
+        .with_firebase_failover(firebase_url, Duration::from_secs(60));
+    assert_eq!(client.active_backend(), ApiBackend::HackerWeb);
+
+    let thread = client
+        .fetch_comment_roots(&story(1))
+        .await
+        .expect("firebase answers for the failed mirror");
+
+    hackerweb.join().expect("hackerweb server thread");
+    firebase.join().expect("firebase server thread");
+    assert!(thread.comments.is_empty());
+    assert_eq!(client.active_backend(), ApiBackend::Firebase);
+    assert!(client.is_failed_over());
+}
+
+#[tokio::test]
+async fn algolia_item_decodes_the_whole_tree_and_drops_deleted_comments() {
+    let body = br#"{
+        "id": 1, "author": "pg", "created_at_i": 100, "text": "<p>body",
+        "children": [
+            {"id": 11, "author": "alice", "created_at_i": 101, "text": "root",
+             "children": [
+                {"id": 21, "author": "bob", "created_at_i": 102, "text": "reply", "children": []}
+             ]},
+            {"id": 12, "author": null, "created_at_i": 103, "text": null, "children": []}
+        ]
+    }"#
+    .to_vec();
+    let (base_url, server) = serve_chunked_once(body);
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        base_url,
+        ApiBackend::Algolia,
+        1,
+        1,
+        None,
+    )
+    .expect("build HN client");
+
+    let thread = client
+        .fetch_comment_roots(&story(1))
+        .await
+        .expect("valid Algolia item must decode");
+
+    server.join().expect("server thread");
+    assert_eq!(thread.text.as_deref(), Some("<p>body"));
+    assert_eq!(thread.comments.len(), 1);
+    let root = &thread.comments[0];
+    assert_eq!(root.comment.by.as_deref(), Some("alice"));
+    assert!(root.comment.children_loaded);
+    assert_eq!(root.comment.kids, vec![21]);
+    assert_eq!(root.children[0].comment.text, "reply");
+    assert_eq!(root.children[0].comment.depth, 1);
+}
+
+fn cached_story(id: u64, kids: Option<Vec<u64>>) -> HnItem {
+    HnItem {
+        id,
+        kind: Some(HnItemKind::Story),
+        by: Some("alice".to_string()),
+        time: Some(1),
+        title: Some(format!("story {id}")),
+        url: None,
+        text: None,
+        score: Some(1),
+        descendants: Some(2),
+        kids,
+        parent: None,
+        parts: None,
+        dead: None,
+        deleted: None,
+    }
+}
+
+fn cached_comment(id: u64, kids: Vec<u64>) -> HnItem {
+    HnItem {
+        id,
+        kind: Some(HnItemKind::Comment),
+        by: Some("bob".to_string()),
+        time: Some(1),
+        title: None,
+        url: None,
+        text: Some(format!("comment {id}")),
+        score: None,
+        descendants: None,
+        kids: Some(kids),
+        parent: None,
+        parts: None,
+        dead: None,
+        deleted: None,
+    }
+}
+
+/// A client whose network is a closed port, so any request fails loudly.
+fn offline_client(dir: &std::path::Path) -> HnClient {
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        "http://127.0.0.1:9".to_string(),
+        ApiBackend::Firebase,
+        16,
+        1,
+        Some(DiskCacheConfig {
+            dir: dir.to_path_buf(),
+            ttl: Duration::from_secs(60),
+        }),
+    )
+    .expect("build HN client");
+    client.set_offline(true);
+    client
+}
+
+#[tokio::test]
+async fn offline_serves_the_cached_listing_up_to_the_first_missing_story() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("create cache");
+    cache
+        .put_feed("top", vec![1, 2, 3, 4])
+        .await
+        .expect("put feed");
+    for id in [1, 2, 4] {
+        cache
+            .put_item(id, cached_story(id, None))
+            .await
+            .expect("put story");
+    }
+    let client = offline_client(dir.path());
+
+    let (story_ids, stories) = client
+        .fetch_initial_stories(FeedKind::Top, 30)
+        .await
+        .expect("cached listing");
+
+    assert_eq!(story_ids, vec![1, 2, 3, 4]);
+    let ids: Vec<u64> = stories.iter().map(|story| story.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert!(stories.iter().all(|story| story.stale_secs.is_none()));
+
+    let error = client
+        .fetch_initial_stories(FeedKind::New, 30)
+        .await
+        .expect_err("no cached new listing");
+    assert_eq!(format!("{error:#}"), "offline: no cached new listing");
+}
+
+#[tokio::test]
+async fn offline_threads_attach_only_fully_cached_replies() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("create cache");
+    cache
+        .put_item(1, cached_story(1, Some(vec![11, 12])))
+        .await
+        .expect("put story");
+    cache
+        .put_item(11, cached_comment(11, vec![21]))
+        .await
+        .expect("put comment");
+    cache
+        .put_item(21, cached_comment(21, vec![]))
+        .await
+        .expect("put reply");
+    cache
+        .put_item(12, cached_comment(12, vec![22]))
+        .await
+        .expect("put comment");
+    let client = offline_client(dir.path());
+
+    let thread = client
+        .fetch_comment_roots(&story(1))
+        .await
+        .expect("cached thread");
+
+    let roots: Vec<(u64, bool, usize)> = thread
+        .comments
+        .iter()
+        .map(|node| {
+            (
+                node.comment.id,
+                node.comment.children_loaded,
+                node.children.len(),
+            )
+        })
+        .collect();
+    assert_eq!(roots, vec![(11, true, 1), (12, false, 0)]);
+    assert_eq!(thread.stale_secs, None);
+
+    let error = client
+        .fetch_comment_roots(&story(2))
+        .await
+        .expect_err("uncached discussion");
+    assert_eq!(
+        format!("{error:#}"),
+        "offline: discussion for story id=2 is not cached"
+    );
+}
+
+#[tokio::test]
+async fn watching_never_answers_from_the_offline_cache() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("create cache");
+    cache
+        .put_item(1, cached_story(1, Some(vec![])))
+        .await
+        .expect("put story");
+    let client = offline_client(dir.path());
+
+    let error = client
+        .fetch_comment_roots_fresh(&story(1))
+        .await
+        .expect_err("a watch poll while offline");
+
+    assert_eq!(format!("{error:#}"), "watching a thread needs the network");
+}
+
+#[tokio::test]
+async fn a_page_loaded_online_extends_the_cached_listing() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (base_url, server) = serve_chunked_once(
+        br#"[{"id":3,"title":"third","points":1,"user":"a","time":1,"comments_count":0}]"#.to_vec(),
+    );
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        base_url,
+        ApiBackend::HackerWeb,
+        16,
+        1,
+        Some(DiskCacheConfig {
+            dir: dir.path().to_path_buf(),
+            ttl: Duration::from_secs(60),
+        }),
+    )
+    .expect("build HN client");
+
+    let stories = client
+        .fetch_more_stories(FeedKind::Top, &[1, 2], 2, 30)
+        .await
+        .expect("page 2");
+    server.join().expect("server thread");
+    assert_eq!(stories.len(), 1);
+
+    // The listing is written in the background.
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("open cache");
+    for _ in 0..100 {
+        if let Some(listing) = cache.get_feed("top").await.expect("read feed") {
+            assert_eq!(listing, vec![1, 2, 3]);
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the appended page never reached the cached listing");
+}
+
+/// The cached item `id` once a background write has retitled it `title`.
+async fn rewritten_item(cache: &FileCache, id: u64, title: &str) -> HnItem {
+    for _ in 0..100 {
+        if let Some(CacheHit::Fresh(item)) =
+            cache.get_item_with_staleness(id).await.expect("read item")
+        {
+            if item.title.as_deref() == Some(title) {
+                return item;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("item id={id} was never rewritten");
+}
+
+#[tokio::test]
+async fn relisting_a_cached_job_without_a_type_keeps_it_a_job() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("create cache");
+    let mut job = cached_story(3, None);
+    job.kind = Some(HnItemKind::Job);
+    cache.put_item(3, job).await.expect("put job");
+    let (base_url, server) = serve_chunked_once(
+        br#"[{"id":3,"title":"relisted","points":1,"user":"a","time":1,"comments_count":0}]"#
+            .to_vec(),
+    );
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        base_url,
+        ApiBackend::HackerWeb,
+        16,
+        1,
+        Some(DiskCacheConfig {
+            dir: dir.path().to_path_buf(),
+            ttl: Duration::from_secs(60),
+        }),
+    )
+    .expect("build HN client");
+
+    client
+        .fetch_more_stories(FeedKind::Top, &[1, 2], 2, 30)
+        .await
+        .expect("page 2");
+    server.join().expect("server thread");
+
+    let item = rewritten_item(&cache, 3, "relisted").await;
+    assert_eq!(item.kind, Some(HnItemKind::Job));
+    cache.put_feed("jobs", vec![3]).await.expect("put feed");
+    let (_, stories) = offline_client(dir.path())
+        .fetch_initial_stories(FeedKind::Jobs, 30)
+        .await
+        .expect("cached listing");
+    assert_eq!(stories[0].kind, Some(HnItemKind::Job));
+}
+
+#[tokio::test]
+async fn a_relisted_poll_keeps_its_options_offline() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("create cache");
+    let mut poll = cached_story(3, Some(vec![]));
+    poll.kind = Some(HnItemKind::Poll);
+    poll.parts = Some(vec![31, 32]);
+    cache.put_item(3, poll).await.expect("put poll");
+    for (id, text) in [(31, "Yes"), (32, "No")] {
+        let mut option = cached_comment(id, vec![]);
+        option.kind = Some(HnItemKind::Pollopt);
+        option.text = Some(text.to_string());
+        option.score = Some(5);
+        cache.put_item(id, option).await.expect("put option");
+    }
+    let (base_url, server) = serve_chunked_once(
+        br#"[{"id":3,"title":"relisted","points":1,"user":"a","time":1,"comments_count":0,"type":"poll"}]"#
+            .to_vec(),
+    );
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        base_url,
+        ApiBackend::HackerWeb,
+        16,
+        1,
+        Some(DiskCacheConfig {
+            dir: dir.path().to_path_buf(),
+            ttl: Duration::from_secs(60),
+        }),
+    )
+    .expect("build HN client");
+
+    client
+        .fetch_more_stories(FeedKind::Top, &[1, 2], 2, 30)
+        .await
+        .expect("page 2");
+    server.join().expect("server thread");
+    rewritten_item(&cache, 3, "relisted").await;
+
+    let mut listed = story(3);
+    listed.comment_count = 0;
+    let thread = offline_client(dir.path())
+        .fetch_comment_roots(&listed)
+        .await
+        .expect("cached poll");
+    let options: Vec<&str> = thread
+        .poll
+        .iter()
+        .map(|option| option.text.as_str())
+        .collect();
+    assert_eq!(options, vec!["Yes", "No"]);
+}
+
+#[tokio::test]
+async fn algolia_paging_follows_pages_not_the_story_count() {
+    let (base_url, server) = serve_json_sequence(vec![
+        r#"{"nbPages": 3, "hits": [
+            {"objectID": "1", "title": "one", "created_at_i": 1},
+            {"objectID": "2", "created_at_i": 1}
+        ]}"#,
+        r#"{"nbPages": 3, "hits": [{"objectID": "3", "title": "three", "created_at_i": 1}]}"#,
+    ]);
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        base_url,
+        ApiBackend::Algolia,
+        16,
+        1,
+        None,
+    )
+    .expect("build HN client");
+
+    let (story_ids, _) = client
+        .fetch_initial_stories(FeedKind::Top, 30)
+        .await
+        .expect("page 0");
+    let more = client
+        .fetch_more_stories(FeedKind::Top, &story_ids, story_ids.len(), 30)
+        .await
+        .expect("page 1");
+
+    let requests = server.join().expect("server thread");
+    assert_eq!(story_ids, vec![1]);
+    assert_eq!(
+        more.iter().map(|story| story.id).collect::<Vec<_>>(),
+        vec![3]
+    );
+    assert!(requests[0].contains("page=0"), "{requests:?}");
+    assert!(requests[1].contains("page=1"), "{requests:?}");
+}
+
+#[tokio::test]
+async fn paging_after_a_failover_continues_down_the_firebase_listing() {
+    let (hackerweb_url, hackerweb) = serve_status_once("502 Bad Gateway");
+    let (firebase_url, firebase) = serve_json_sequence(vec![
+        "[1, 2, 3]",
+        r#"{"id": 3, "type": "story", "title": "three", "by": "a", "time": 1, "score": 1}"#,
+    ]);
+    let client = hackerweb_client(hackerweb_url)
+        .with_firebase_failover(firebase_url, Duration::from_secs(60));
+
+    let stories = client
+        .fetch_more_stories(FeedKind::Top, &[1, 2], 2, 30)
+        .await
+        .expect("firebase pages on for the failed mirror");
+
+    hackerweb.join().expect("hackerweb server thread");
+    let requests = firebase.join().expect("firebase server thread");
+    assert_eq!(
+        stories.iter().map(|story| story.id).collect::<Vec<_>>(),
+        vec![3]
+    );
+    assert!(requests[0].contains("/topstories.json"), "{requests:?}");
+    assert!(requests[1].contains("/item/3.json"), "{requests:?}");
+}
+
+#[tokio::test]
+async fn a_first_page_from_firebase_keeps_paging_through_the_cooldown() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (hackerweb_url, hackerweb) = serve_status_once("502 Bad Gateway");
+    let (firebase_url, firebase) = serve_json_sequence(vec![
+        "[1, 2, 3, 4]",
+        r#"{"id": 1, "type": "story", "title": "one", "by": "a", "time": 1, "score": 1}"#,
+        r#"{"id": 2, "type": "story", "title": "two", "by": "a", "time": 1, "score": 1}"#,
+        "[1, 2, 3, 4]",
+        r#"{"id": 3, "type": "story", "title": "three", "by": "a", "time": 1, "score": 1}"#,
+        r#"{"id": 4, "type": "story", "title": "four", "by": "a", "time": 1, "score": 1}"#,
+    ]);
+    let client = HnClient::new(
+        reqwest::Client::new(),
+        hackerweb_url,
+        ApiBackend::HackerWeb,
+        1,
+        1,
+        Some(DiskCacheConfig {
+            dir: dir.path().to_path_buf(),
+            ttl: Duration::from_secs(60),
+        }),
+    )
+    .expect("build HN client")
+    .with_firebase_failover(firebase_url, Duration::from_secs(60));
+
+    let (story_ids, stories) = client
+        .fetch_initial_stories(FeedKind::Top, 2)
+        .await
+        .expect("firebase serves the first page");
+    let more = client
+        .fetch_more_stories(FeedKind::Top, &story_ids, stories.len(), 2)
+        .await
+        .expect("firebase serves the next page");
+
+    hackerweb.join().expect("hackerweb server thread");
+    let requests = firebase.join().expect("firebase server thread");
+    assert_eq!(story_ids, vec![1, 2, 3, 4]);
+    assert_eq!(
+        more.iter().map(|story| story.id).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert!(requests[3].contains("/topstories.json"), "{requests:?}");
+    // The page was already in the listing, so nothing is appended twice.
+    let cache =
+        FileCache::new(dir.path().to_path_buf(), Duration::from_secs(60)).expect("open cache");
+    for _ in 0..100 {
+        if let Some(listing) = cache.get_feed("top").await.expect("read feed") {
+            assert_eq!(listing, vec![1, 2, 3, 4]);
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the Firebase listing never reached the cache");
+}
+
+#[tokio::test]
+async fn replies_load_from_firebase_even_before_any_failover() {
+    let (firebase_url, firebase) = serve_json_sequence(vec![
+        r#"{"id": 21, "type": "comment", "by": "bob", "time": 1, "text": "reply"}"#,
+    ]);
+    // HackerWeb is a closed port: children must never be asked of it.
+    let client = hackerweb_client("http://127.0.0.1:9".to_string())
+        .with_firebase_failover(firebase_url, Duration::from_secs(60));
+
+    let children = client
+        .fetch_comment_children(&[21], 1)
+        .await
+        .expect("firebase serves the replies");
+
+    firebase.join().expect("firebase server thread");
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].comment.text, "reply");
+    assert_eq!(children[0].comment.depth, 1);
+    assert!(!client.is_failed_over());
+}
+
+#[tokio::test]
+async fn a_comment_parent_is_read_from_the_firebase_item() {
+    let (firebase_url, firebase) = serve_json_sequence(vec![
+        r#"{"id": 52, "type": "comment", "by": "bob", "time": 1, "text": "hi", "parent": 51}"#,
+    ]);
+    let client = hackerweb_client("http://127.0.0.1:9".to_string())
+        .with_firebase_failover(firebase_url, Duration::from_secs(60));
+
+    let parent = client
+        .fetch_comment_parent(52)
+        .await
+        .expect("firebase serves the item");
+
+    firebase.join().expect("firebase server thread");
+    assert_eq!(parent, Some(51));
+}
+
+#[tokio::test]
+async fn a_profile_loads_when_its_activity_cannot_be_listed() {
+    let (firebase_url, server) =
+        serve_chunked_once(br#"{"id":"alice","karma":42,"created":1,"about":"hi"}"#.to_vec());
+    let closed = TcpListener::bind(("127.0.0.1", 0)).expect("bind closed port");
+    let search_url = format!(
+        "http://{}",
+        closed.local_addr().expect("closed port address")
+    );
+    drop(closed);
+    let http = reqwest::Client::new();
+    let search = SearchClient::new(http.clone(), &search_url).expect("search client");
+    let users = UserClient::new(http, firebase_url, search);
+
+    let profile = users.fetch_user("alice").await.expect("profile");
+    server.join().expect("test server");
+
+    assert_eq!(profile.karma, 42);
+    assert!(profile.submissions.is_empty());
+    assert!(profile.comments.is_empty());
+    let error = profile.activity_error.expect("activity error");
+    assert!(error.starts_with("submissions: "), "error: {error}");
+    assert!(error.contains("; comments: "), "error: {error}");
+}
@@ -8,6 +8,7 @@ use tokio::fs;
 #[derive(Debug, Clone)]
 pub(crate) struct FileCache {
     items_dir: PathBuf,
+    feeds_dir: PathBuf,
     ttl: Duration,
 }
 
@@ -23,13 +24,28 @@ struct CachedItem {
     item: HnItem,
 }
 
+/// A feed's story IDs as last listed online — the only way offline mode knows
+/// what a feed contains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFeed {
+    fetched_at: i64,
+    story_ids: Vec<u64>,
+}
+
 impl FileCache {
     pub(crate) fn new(dir: PathBuf, ttl: Duration) -> Result<Self> {
         anyhow::ensure!(ttl.as_secs() > 0, "file cache ttl must be > 0s");
         let items_dir = dir.join("items");
-        std::fs::create_dir_all(&items_dir)
-            .with_context(|| format!("create cache dir {}", items_dir.display()))?;
-        Ok(Self { items_dir, ttl })
+        let feeds_dir = dir.join("feeds");
+        for dir in [&items_dir, &feeds_dir] {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("create cache dir {}", dir.display()))?;
+        }
+        Ok(Self {
+            items_dir,
+            feeds_dir,
+            ttl,
+        })
     }
 
     pub(crate) async fn get_item_with_staleness(&self, id: u64) -> Result<Option<CacheHit>> {
@@ -70,6 +86,33 @@ impl FileCache {
         self.items_dir.join(format!("{id}.json"))
     }
 
+    pub(crate) async fn get_feed(&self, feed: &str) -> Result<Option<Vec<u64>>> {
+        let path = self.feed_path(feed);
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("read cache {}", path.display()));
+            }
+        };
+        let cached: CachedFeed = serde_json::from_slice(&bytes)
+            .with_context(|| format!("decode cache {}", path.display()))?;
+        Ok(Some(cached.story_ids))
+    }
+
+    pub(crate) async fn put_feed(&self, feed: &str, story_ids: Vec<u64>) -> Result<()> {
+        let cached = CachedFeed {
+            fetched_at: now_unix()?,
+            story_ids,
+        };
+        let bytes = serde_json::to_vec(&cached).context("encode feed cache")?;
+        atomic_write(&self.feed_path(feed), &bytes).await
+    }
+
+    fn feed_path(&self, feed: &str) -> PathBuf {
+        self.feeds_dir.join(format!("{feed}.json"))
+    }
+
     pub(crate) async fn cleanup_expired(&self, max_age: Duration) -> Result<usize> {
         anyhow::ensure!(max_age.as_secs() > 0, "max_age must be > 0s");
         let mut removed = 0usize;
@@ -184,6 +227,8 @@ mod tests {
             score: Some(1),
             descendants: Some(0),
             kids: None,
+            parent: None,
+            parts: None,
             dead: None,
             deleted: None,
         }
@@ -232,6 +277,25 @@ mod tests {
         let _ = std::fs::remove_dir_all(dir);
     }
 
+    #[tokio::test]
+    async fn feed_listings_round_trip_and_default_to_none() {
+        let dir = temp_cache_dir("feeds");
+        let cache = FileCache::new(dir.clone(), Duration::from_secs(60)).expect("create cache");
+
+        assert_eq!(cache.get_feed("top").await.expect("get missing feed"), None);
+        cache
+            .put_feed("top", vec![3, 1, 2])
+            .await
+            .expect("put feed");
+        assert_eq!(
+            cache.get_feed("top").await.expect("get feed"),
+            Some(vec![3, 1, 2])
+        );
+        assert_eq!(cache.get_feed("new").await.expect("get other feed"), None);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[tokio::test]
     async fn cleanup_expired_removes_stale_and_stray_files_only() {
         let dir = temp_cache_dir("cleanup");
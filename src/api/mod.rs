@@ -6,12 +6,13 @@ mod hackerweb_response;
 pub mod search;
 mod source;
 pub mod types;
+pub mod user;
 
 pub use client::{DiskCacheConfig, HnClient};
-pub use search::SearchClient;
+pub use search::{CommentHit, SearchClient, SearchPage, SearchQuery};
 #[cfg(test)]
 pub use source::InMemorySource;
 pub use source::Sources;
-#[cfg(test)]
 pub(crate) use source::StorySource;
-pub use types::{ApiBackend, CommentNode, FeedKind, Story, StoryThread};
+pub use types::{ApiBackend, CommentNode, FeedKind, PollOption, Story, StoryThread};
+pub use user::{UserClient, UserProfile};
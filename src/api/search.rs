@@ -1,12 +1,28 @@
-use crate::api::types::Story;
+use crate::api::types::{HnItemKind, Story};
 use crate::logging;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use reqwest::{Client, Url};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
-struct AlgoliaResponse {
+pub(crate) struct AlgoliaResponse {
     hits: Vec<AlgoliaHit>,
+    #[serde(rename = "nbPages", default)]
+    pub(crate) nb_pages: usize,
+}
+
+impl AlgoliaResponse {
+    /// Hits as Stories, skipping the untitled ones (comments, polls' options).
+    pub(crate) fn into_stories(self) -> Result<Vec<Story>> {
+        let mut stories = Vec::new();
+        for hit in self.hits {
+            if let Some(story) = hit.into_story()? {
+                stories.push(story);
+            }
+        }
+        Ok(stories)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,9 +37,50 @@ struct AlgoliaHit {
     #[serde(rename = "created_at_i")]
     created_at_i: Option<i64>,
     num_comments: Option<i64>,
+    comment_text: Option<String>,
+    story_id: Option<u64>,
+    story_title: Option<String>,
+    story_url: Option<String>,
+    parent_id: Option<u64>,
+    /// Algolia's tags; the item type (`story`, `job`, `poll`) is among them.
+    #[serde(rename = "_tags", default)]
+    tags: Vec<String>,
 }
 
 impl AlgoliaHit {
+    /// A `tags=comment` hit, with its story taken from `stories` when the
+    /// lookup found it. Deleted comments (no text) are skipped.
+    fn into_comment_hit(self, stories: &HashMap<u64, Story>) -> Result<Option<CommentHit>> {
+        let id: u64 = self
+            .object_id
+            .parse()
+            .with_context(|| format!("parse Algolia objectID {:?}", self.object_id))?;
+        let (Some(story_id), Some(text)) = (self.story_id, self.comment_text) else {
+            return Ok(None);
+        };
+        let story = stories.get(&story_id).cloned().unwrap_or_else(|| Story {
+            id: story_id,
+            title: self.story_title.unwrap_or_default(),
+            url: self.story_url.filter(|u| !u.is_empty()),
+            text: None,
+            score: 0,
+            by: String::new(),
+            time: 0,
+            comment_count: 0,
+            kids: vec![],
+            kind: None,
+            stale_secs: None,
+        });
+        Ok(Some(CommentHit {
+            id,
+            parent_id: self.parent_id.unwrap_or(story_id),
+            by: self.author.unwrap_or_default(),
+            text,
+            time: self.created_at_i.unwrap_or(0),
+            story,
+        }))
+    }
+
     fn into_story(self) -> Result<Option<Story>> {
         let id: u64 = self
             .object_id
@@ -45,30 +102,218 @@ impl AlgoliaHit {
             time: self.created_at_i.unwrap_or(0),
             comment_count: self.num_comments.unwrap_or(0),
             kids: vec![],
+            kind: self.tags.iter().find_map(|tag| match tag.as_str() {
+                "story" => Some(HnItemKind::Story),
+                "job" => Some(HnItemKind::Job),
+                "poll" => Some(HnItemKind::Poll),
+                _ => None,
+            }),
+            stale_secs: None,
         }))
     }
 }
 
+/// How Algolia orders search hits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchSort {
+    #[default]
+    Relevance,
+    /// Newest first, via `search_by_date`.
+    Date,
+}
+
+/// A parsed `/` query: free text plus `author:NAME`, `points>N`,
+/// `comments>N`, `after:YYYY-MM-DD`, `before:YYYY-MM-DD` and `sort:date`.
+/// Thresholds take any of `>`, `>=`, `<`, `<=` and `=`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    pub author: Option<String>,
+    /// Algolia `numericFilters` entries, e.g. `points>100`.
+    pub numeric_filters: Vec<String>,
+    pub sort: SearchSort,
+}
+
+/// One page of hits, and whether Algolia has another after it.
+#[derive(Debug, Clone)]
+pub struct SearchPage<T> {
+    pub hits: Vec<T>,
+    pub has_more: bool,
+}
+
+/// A comment matching a comment search, with the story it belongs to.
+#[derive(Debug, Clone)]
+pub struct CommentHit {
+    pub id: u64,
+    /// The comment replied to, or the story for a top-level comment.
+    pub parent_id: u64,
+    pub by: String,
+    /// HN HTML, as on the comment itself.
+    pub text: String,
+    pub time: i64,
+    pub story: Story,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            if let Some(author) = word.strip_prefix("author:") {
+                if author.is_empty() {
+                    bail!("author: needs a username");
+                }
+                query.author = Some(author.to_string());
+            } else if let Some(date) = word.strip_prefix("after:") {
+                let since = parse_day(date)?;
+                query.numeric_filters.push(format!("created_at_i>={since}"));
+            } else if let Some(date) = word.strip_prefix("before:") {
+                let until = parse_day(date)?;
+                query.numeric_filters.push(format!("created_at_i<{until}"));
+            } else if let Some(sort) = word.strip_prefix("sort:") {
+                query.sort = match sort {
+                    "date" => SearchSort::Date,
+                    "relevance" => SearchSort::Relevance,
+                    _ => bail!("sort: expects date or relevance, got {sort:?}"),
+                };
+            } else if let Some(filter) = numeric_filter(word)? {
+                query.numeric_filters.push(filter);
+            } else {
+                words.push(word);
+            }
+        }
+        query.text = words.join(" ");
+        if query.text.is_empty() && query.author.is_none() && query.numeric_filters.is_empty() {
+            bail!("search needs some text or a filter");
+        }
+        Ok(query)
+    }
+}
+
+/// `points>N` or `comments>N` as an Algolia numeric filter; `None` when the
+/// word is not a threshold at all.
+fn numeric_filter(word: &str) -> Result<Option<String>> {
+    for (name, attribute) in [("points", "points"), ("comments", "num_comments")] {
+        let Some(rest) = word.strip_prefix(name) else {
+            continue;
+        };
+        let Some(op) = [">=", "<=", ">", "<", "="]
+            .into_iter()
+            .find(|op| rest.starts_with(op))
+        else {
+            continue;
+        };
+        let value = &rest[op.len()..];
+        let value: u64 = value
+            .parse()
+            .with_context(|| format!("{name}{op} needs a whole number, got {value:?}"))?;
+        return Ok(Some(format!("{attribute}{op}{value}")));
+    }
+    Ok(None)
+}
+
+/// Midnight UTC at the start of a `YYYY-MM-DD` day, as a unix timestamp.
+fn parse_day(date: &str) -> Result<i64> {
+    let day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .with_context(|| format!("expected a YYYY-MM-DD date, got {date:?}"))?;
+    Ok(day
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+        .timestamp())
+}
+
 #[derive(Clone)]
 pub struct SearchClient {
     http: Client,
-    endpoint: Url,
+    base_url: Url,
 }
 
+const HITS_PER_PAGE: &str = "30";
+
 impl SearchClient {
-    pub fn new(http: Client, endpoint: &str) -> Result<Self> {
-        let endpoint = Url::parse(endpoint).context("parse Algolia endpoint")?;
-        Ok(Self { http, endpoint })
+    pub fn new(http: Client, base_url: &str) -> Result<Self> {
+        let base_url = Url::parse(base_url).context("parse Algolia base URL")?;
+        Ok(Self { http, base_url })
     }
 
-    /// Search stories via Algolia HN Search API.
-    pub async fn search_stories(&self, query: &str) -> Result<Vec<Story>> {
-        logging::log_info(format!("algolia: searching query={query:?}"));
+    /// Search stories via Algolia HN Search API. `page` is zero-based.
+    pub async fn search_stories(
+        &self,
+        query: &SearchQuery,
+        page: usize,
+    ) -> Result<SearchPage<Story>> {
+        let resp = self.search_hits(query, page, "story").await?;
+        let has_more = page + 1 < resp.nb_pages;
+        Ok(SearchPage {
+            hits: resp.into_stories()?,
+            has_more,
+        })
+    }
 
-        let resp: AlgoliaResponse = self
-            .http
-            .get(self.endpoint.clone())
-            .query(&[("query", query), ("tags", "story"), ("hitsPerPage", "30")])
+    /// Search comments, then look their stories up in one more request so a
+    /// hit opens like any listed story.
+    pub async fn search_comments(
+        &self,
+        query: &SearchQuery,
+        page: usize,
+    ) -> Result<SearchPage<CommentHit>> {
+        let resp = self.search_hits(query, page, "comment").await?;
+        let has_more = page + 1 < resp.nb_pages;
+        let mut story_ids: Vec<u64> = resp.hits.iter().filter_map(|hit| hit.story_id).collect();
+        story_ids.sort_unstable();
+        story_ids.dedup();
+        let stories = self.stories_by_id(&story_ids).await?;
+
+        let mut hits = Vec::new();
+        for hit in resp.hits {
+            if let Some(hit) = hit.into_comment_hit(&stories)? {
+                hits.push(hit);
+            }
+        }
+        Ok(SearchPage { hits, has_more })
+    }
+
+    async fn search_hits(
+        &self,
+        query: &SearchQuery,
+        page: usize,
+        tag: &str,
+    ) -> Result<AlgoliaResponse> {
+        logging::log_info(format!(
+            "algolia: searching {tag} query={query:?} page={page}"
+        ));
+
+        let path = match query.sort {
+            SearchSort::Relevance => "search",
+            SearchSort::Date => "search_by_date",
+        };
+        let mut endpoint = self.base_url.clone();
+        endpoint
+            .path_segments_mut()
+            .map_err(|()| anyhow::anyhow!("Algolia base URL cannot take a path"))?
+            .pop_if_empty()
+            .push(path);
+        let tags = match &query.author {
+            Some(author) => format!("{tag},author_{author}"),
+            None => tag.to_string(),
+        };
+        let page_param = page.to_string();
+        let mut params = vec![
+            ("query", query.text.as_str()),
+            ("tags", tags.as_str()),
+            ("hitsPerPage", HITS_PER_PAGE),
+            ("page", page_param.as_str()),
+        ];
+        let numeric_filters = query.numeric_filters.join(",");
+        if !numeric_filters.is_empty() {
+            params.push(("numericFilters", numeric_filters.as_str()));
+        }
+
+        self.http
+            .get(endpoint)
+            .query(&params)
             .send()
             .await
             .context("fetch algolia search")?
@@ -76,16 +321,43 @@ impl SearchClient {
             .context("algolia search status")?
             .json()
             .await
-            .context("decode algolia search")?;
+            .context("decode algolia search")
+    }
 
-        let mut stories = Vec::new();
-        for hit in resp.hits {
-            if let Some(story) = hit.into_story()? {
-                stories.push(story);
-            }
+    /// The listed stories among `ids`, via `tags=story,(story_1,story_2,…)`.
+    async fn stories_by_id(&self, ids: &[u64]) -> Result<HashMap<u64, Story>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
         }
-
-        Ok(stories)
+        let any_of: Vec<String> = ids.iter().map(|id| format!("story_{id}")).collect();
+        let tags = format!("story,({})", any_of.join(","));
+        let mut endpoint = self.base_url.clone();
+        endpoint
+            .path_segments_mut()
+            .map_err(|()| anyhow::anyhow!("Algolia base URL cannot take a path"))?
+            .pop_if_empty()
+            .push("search");
+        let hits_per_page = ids.len().to_string();
+        let resp: AlgoliaResponse = self
+            .http
+            .get(endpoint)
+            .query(&[
+                ("tags", tags.as_str()),
+                ("hitsPerPage", hits_per_page.as_str()),
+            ])
+            .send()
+            .await
+            .context("fetch algolia stories for comment hits")?
+            .error_for_status()
+            .context("algolia stories status")?
+            .json()
+            .await
+            .context("decode algolia stories")?;
+        Ok(resp
+            .into_stories()?
+            .into_iter()
+            .map(|story| (story.id, story))
+            .collect())
     }
 }
 
@@ -114,4 +386,109 @@ mod tests {
         assert_eq!(stories[1].text, None);
         assert_eq!(stories[2].text, None);
     }
+
+    #[test]
+    fn algolia_tags_carry_the_item_type() {
+        let payload = r#"{
+            "hits": [
+                {"objectID": "1", "title": "Acme is hiring", "_tags": ["job", "author_acme", "story_1"]},
+                {"objectID": "2", "title": "Ask HN", "_tags": ["story", "author_a", "story_2", "ask_hn"]},
+                {"objectID": "3", "title": "Untagged"}
+            ]
+        }"#;
+        let response: AlgoliaResponse = serde_json::from_str(payload).expect("decode algolia");
+
+        let kinds: Vec<_> = response
+            .into_stories()
+            .expect("map hits")
+            .into_iter()
+            .map(|story| story.kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![Some(HnItemKind::Job), Some(HnItemKind::Story), None]
+        );
+    }
+
+    #[test]
+    fn comment_hits_keep_their_story_and_fall_back_to_the_hit_title() {
+        let payload = r#"{
+            "hits": [
+                {"objectID": "11", "comment_text": "<p>first", "author": "ann",
+                 "story_id": 1, "story_title": "Listed", "parent_id": 1},
+                {"objectID": "12", "comment_text": "reply", "author": "bob",
+                 "story_id": 2, "story_title": "Unlisted", "story_url": "https://example.com",
+                 "parent_id": 20},
+                {"objectID": "13", "author": "gone", "story_id": 2, "parent_id": 12}
+            ]
+        }"#;
+        let response: AlgoliaResponse = serde_json::from_str(payload).expect("decode algolia");
+        let listed = Story {
+            id: 1,
+            title: "Listed".to_string(),
+            url: None,
+            text: None,
+            score: 42,
+            by: "pg".to_string(),
+            time: 1,
+            comment_count: 7,
+            kids: vec![],
+            kind: None,
+            stale_secs: None,
+        };
+        let stories = HashMap::from([(1, listed)]);
+
+        let hits: Vec<CommentHit> = response
+            .hits
+            .into_iter()
+            .filter_map(|hit| hit.into_comment_hit(&stories).expect("map hit"))
+            .collect();
+
+        assert_eq!(hits.len(), 2, "the deleted comment is skipped");
+        assert_eq!((hits[0].id, hits[0].parent_id), (11, 1));
+        assert_eq!(hits[0].story.score, 42);
+        assert_eq!(hits[1].by, "bob");
+        assert_eq!(hits[1].story.title, "Unlisted");
+        assert_eq!(hits[1].story.url.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn search_syntax_splits_filters_from_the_text() {
+        let query = SearchQuery::parse(
+            "rust  author:pg points>100 comments>=20 after:2024-01-02 sort:date async",
+        )
+        .expect("parse query");
+
+        assert_eq!(query.text, "rust async");
+        assert_eq!(query.author.as_deref(), Some("pg"));
+        assert_eq!(
+            query.numeric_filters,
+            vec![
+                "points>100".to_string(),
+                "num_comments>=20".to_string(),
+                "created_at_i>=1704153600".to_string(),
+            ]
+        );
+        assert_eq!(query.sort, SearchSort::Date);
+    }
+
+    #[test]
+    fn malformed_search_filters_are_rejected() {
+        for (input, message) in [
+            ("points>many", "points> needs a whole number, got \"many\""),
+            (
+                "before:yesterday",
+                "expected a YYYY-MM-DD date, got \"yesterday\"",
+            ),
+            (
+                "rust sort:top",
+                "sort: expects date or relevance, got \"top\"",
+            ),
+            ("   ", "search needs some text or a filter"),
+        ] {
+            let error = SearchQuery::parse(input).expect_err(input);
+            assert_eq!(format!("{error}"), message, "{input}");
+        }
+    }
 }
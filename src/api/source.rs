@@ -1,4 +1,7 @@
-use super::{CommentNode, FeedKind, HnClient, SearchClient, Story, StoryThread};
+use super::{
+    ApiBackend, CommentHit, CommentNode, FeedKind, HnClient, SearchClient, SearchPage, SearchQuery,
+    Story, StoryThread, UserClient, UserProfile,
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use std::sync::Arc;
@@ -20,26 +23,74 @@ pub trait StorySource: Send + Sync {
 
     fn comment_roots(&self, story: Story) -> BoxFuture<'static, Result<StoryThread>>;
 
+    /// The thread as the network has it now, for watch polls. Sources with
+    /// no cache in front of the network just load it again.
+    fn watch_thread(&self, story: Story) -> BoxFuture<'static, Result<StoryThread>> {
+        self.comment_roots(story)
+    }
+
     fn comment_children(
         &self,
         ids: Vec<u64>,
         depth: usize,
     ) -> BoxFuture<'static, Result<Vec<CommentNode>>>;
+
+    /// The comment or story `comment_id` replies to. Sources that load
+    /// threads whole have nothing to look up.
+    fn comment_parent(&self, _comment_id: u64) -> BoxFuture<'static, Result<Option<u64>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Switch between the network and whatever is already on disk. Sources
+    /// without a disk cache have nothing to switch.
+    fn set_offline(&self, _offline: bool) {}
+
+    /// The backend answering right now, for the title bar, and whether it is
+    /// standing in for a failing one.
+    fn active_backend(&self) -> Option<(ApiBackend, bool)> {
+        None
+    }
 }
 
 pub trait SearchSource: Send + Sync {
-    fn search(&self, query: String) -> BoxFuture<'static, Result<Vec<Story>>>;
+    /// One zero-based page of stories matching `query`.
+    fn search(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<Story>>>;
+
+    /// One zero-based page of comments matching `query`.
+    fn search_comments(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<CommentHit>>>;
+}
+
+pub trait UserSource: Send + Sync {
+    /// The account behind `id` with its recent submissions and comments.
+    fn user(&self, id: String) -> BoxFuture<'static, Result<UserProfile>>;
 }
 
 #[derive(Clone)]
 pub struct Sources {
     pub(crate) stories: Arc<dyn StorySource>,
     pub(crate) search: Arc<dyn SearchSource>,
+    pub(crate) users: Arc<dyn UserSource>,
 }
 
 impl Sources {
-    pub fn new(stories: Arc<dyn StorySource>, search: Arc<dyn SearchSource>) -> Self {
-        Self { stories, search }
+    pub fn new(
+        stories: Arc<dyn StorySource>,
+        search: Arc<dyn SearchSource>,
+        users: Arc<dyn UserSource>,
+    ) -> Self {
+        Self {
+            stories,
+            search,
+            users,
+        }
     }
 }
 
@@ -73,6 +124,11 @@ impl StorySource for HnClient {
         Box::pin(async move { source.fetch_comment_roots(&story).await })
     }
 
+    fn watch_thread(&self, story: Story) -> BoxFuture<'static, Result<StoryThread>> {
+        let source = self.clone();
+        Box::pin(async move { source.fetch_comment_roots_fresh(&story).await })
+    }
+
     fn comment_children(
         &self,
         ids: Vec<u64>,
@@ -81,12 +137,45 @@ impl StorySource for HnClient {
         let source = self.clone();
         Box::pin(async move { source.fetch_comment_children(&ids, depth).await })
     }
+
+    fn comment_parent(&self, comment_id: u64) -> BoxFuture<'static, Result<Option<u64>>> {
+        let source = self.clone();
+        Box::pin(async move { source.fetch_comment_parent(comment_id).await })
+    }
+
+    fn set_offline(&self, offline: bool) {
+        HnClient::set_offline(self, offline);
+    }
+
+    fn active_backend(&self) -> Option<(ApiBackend, bool)> {
+        Some((HnClient::active_backend(self), self.is_failed_over()))
+    }
 }
 
 impl SearchSource for SearchClient {
-    fn search(&self, query: String) -> BoxFuture<'static, Result<Vec<Story>>> {
+    fn search(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<Story>>> {
+        let source = self.clone();
+        Box::pin(async move { source.search_stories(&query, page).await })
+    }
+
+    fn search_comments(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<CommentHit>>> {
         let source = self.clone();
-        Box::pin(async move { source.search_stories(&query).await })
+        Box::pin(async move { source.search_comments(&query, page).await })
+    }
+}
+
+impl UserSource for UserClient {
+    fn user(&self, id: String) -> BoxFuture<'static, Result<UserProfile>> {
+        let source = self.clone();
+        Box::pin(async move { source.fetch_user(&id).await })
     }
 }
 
@@ -95,9 +184,15 @@ impl SearchSource for SearchClient {
 pub struct InMemorySource {
     stories: Vec<Story>,
     comments: std::collections::HashMap<u64, Vec<CommentNode>>,
+    watched_comments: std::collections::HashMap<u64, Vec<CommentNode>>,
     thread_texts: std::collections::HashMap<u64, String>,
+    polls: std::collections::HashMap<u64, Vec<super::PollOption>>,
     children: std::collections::HashMap<u64, CommentNode>,
-    searches: std::collections::HashMap<String, Vec<Story>>,
+    parents: std::collections::HashMap<u64, u64>,
+    /// Result pages per query text.
+    searches: std::collections::HashMap<String, Vec<Vec<Story>>>,
+    comment_searches: std::collections::HashMap<String, Vec<CommentHit>>,
+    users: std::collections::HashMap<String, UserProfile>,
     initial_error: Option<String>,
 }
 
@@ -122,14 +217,46 @@ impl InMemorySource {
         self
     }
 
+    /// What watch polls of `story_id` find: the thread after new replies
+    /// arrived. Without one, a poll sees the `with_comments` thread again.
+    pub fn with_watched_comments(mut self, story_id: u64, comments: Vec<CommentNode>) -> Self {
+        self.watched_comments.insert(story_id, comments);
+        self
+    }
+
+    pub fn with_poll(mut self, story_id: u64, options: Vec<super::PollOption>) -> Self {
+        self.polls.insert(story_id, options);
+        self
+    }
+
     pub fn with_children(mut self, children: Vec<CommentNode>) -> Self {
         self.children
             .extend(children.into_iter().map(|node| (node.comment.id, node)));
         self
     }
 
-    pub fn with_search(mut self, query: impl Into<String>, stories: Vec<Story>) -> Self {
-        self.searches.insert(query.into(), stories);
+    /// What `comment_id` replies to, for walks up from a comment hit.
+    pub fn with_parent(mut self, comment_id: u64, parent_id: u64) -> Self {
+        self.parents.insert(comment_id, parent_id);
+        self
+    }
+
+    pub fn with_search(self, query: impl Into<String>, stories: Vec<Story>) -> Self {
+        self.with_search_pages(query, vec![stories])
+    }
+
+    pub fn with_search_pages(mut self, query: impl Into<String>, pages: Vec<Vec<Story>>) -> Self {
+        self.searches.insert(query.into(), pages);
+        self
+    }
+
+    pub fn with_comment_search(mut self, query: impl Into<String>, hits: Vec<CommentHit>) -> Self {
+        self.comment_searches.insert(query.into(), hits);
+        self
+    }
+
+    pub fn with_user(mut self, id: impl Into<String>, profile: UserProfile) -> Self {
+        self.users.insert(id.into(), profile);
         self
     }
 
@@ -178,7 +305,22 @@ impl StorySource for InMemorySource {
     fn comment_roots(&self, story: Story) -> BoxFuture<'static, Result<StoryThread>> {
         let comments = self.comments.get(&story.id).cloned().unwrap_or_default();
         let text = self.thread_texts.get(&story.id).cloned();
-        Box::pin(async move { Ok(StoryThread { text, comments }) })
+        let poll = self.polls.get(&story.id).cloned().unwrap_or_default();
+        Box::pin(async move {
+            Ok(StoryThread {
+                text,
+                comments,
+                stale_secs: None,
+                poll,
+            })
+        })
+    }
+
+    fn watch_thread(&self, story: Story) -> BoxFuture<'static, Result<StoryThread>> {
+        match self.watched_comments.get(&story.id).cloned() {
+            Some(comments) => Box::pin(async move { Ok(StoryThread::from_comments(comments)) }),
+            None => self.comment_roots(story),
+        }
     }
 
     fn comment_children(
@@ -192,12 +334,58 @@ impl StorySource for InMemorySource {
             .collect();
         Box::pin(async move { Ok(children) })
     }
+
+    fn comment_parent(&self, comment_id: u64) -> BoxFuture<'static, Result<Option<u64>>> {
+        let parent = self.parents.get(&comment_id).copied();
+        Box::pin(async move { Ok(parent) })
+    }
 }
 
 #[cfg(test)]
 impl SearchSource for InMemorySource {
-    fn search(&self, query: String) -> BoxFuture<'static, Result<Vec<Story>>> {
-        let stories = self.searches.get(&query).cloned().unwrap_or_default();
-        Box::pin(async move { Ok(stories) })
+    /// The query text's pages, as `with_search_pages` laid them out.
+    fn search(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<Story>>> {
+        let pages = self.searches.get(&query.text).cloned().unwrap_or_default();
+        let has_more = page + 1 < pages.len();
+        let hits = pages.into_iter().nth(page).unwrap_or_default();
+        Box::pin(async move { Ok(SearchPage { hits, has_more }) })
+    }
+
+    fn search_comments(
+        &self,
+        query: SearchQuery,
+        page: usize,
+    ) -> BoxFuture<'static, Result<SearchPage<CommentHit>>> {
+        let hits = match page {
+            0 => self
+                .comment_searches
+                .get(&query.text)
+                .cloned()
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+        Box::pin(async move {
+            Ok(SearchPage {
+                hits,
+                has_more: false,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+impl UserSource for InMemorySource {
+    fn user(&self, id: String) -> BoxFuture<'static, Result<UserProfile>> {
+        let profile = self.users.get(&id).cloned();
+        Box::pin(async move {
+            match profile {
+                Some(profile) => Ok(profile),
+                None => anyhow::bail!("no such user: {id}"),
+            }
+        })
     }
 }
@@ -11,6 +11,27 @@ pub enum ApiBackend {
     HackerWeb,
     /// Official Firebase API (hacker-news.firebaseio.com/v0) — item-level requests.
     Firebase,
+    /// Algolia HN Search (hn.algolia.com/api/v1) — tagged listings, whole
+    /// comment trees in one request.
+    Algolia,
+}
+
+impl ApiBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HackerWeb => "hackerweb",
+            Self::Firebase => "firebase",
+            Self::Algolia => "algolia",
+        }
+    }
+
+    pub fn default_base_url(self) -> &'static str {
+        match self {
+            Self::HackerWeb => "https://api.hackerwebapp.com",
+            Self::Firebase => "https://hacker-news.firebaseio.com/v0",
+            Self::Algolia => "https://hn.algolia.com/api/v1",
+        }
+    }
 }
 
 /// Which feed to display.
@@ -26,15 +47,6 @@ pub enum FeedKind {
 }
 
 impl FeedKind {
-    pub const ALL: [FeedKind; 6] = [
-        FeedKind::Top,
-        FeedKind::New,
-        FeedKind::Best,
-        FeedKind::Ask,
-        FeedKind::Show,
-        FeedKind::Jobs,
-    ];
-
     pub fn label(self) -> &'static str {
         match self {
             Self::Top => "Top Stories",
@@ -68,6 +80,19 @@ impl FeedKind {
         }
     }
 
+    /// Algolia endpoint and tag standing in for each feed. Algolia has no
+    /// "best" ranking; the client narrows relevance to recent stories instead.
+    pub fn algolia_query(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Top => ("/search", "front_page"),
+            Self::New => ("/search_by_date", "story"),
+            Self::Best => ("/search", "story"),
+            Self::Ask => ("/search_by_date", "ask_hn"),
+            Self::Show => ("/search_by_date", "show_hn"),
+            Self::Jobs => ("/search_by_date", "job"),
+        }
+    }
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::Top => "top",
@@ -104,6 +129,9 @@ pub struct WebStory {
     pub user: Option<String>,
     pub time: i64,
     pub comments_count: i64,
+    /// `link`, `ask`, `job` or `poll`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
 }
 
 impl From<WebStory> for Story {
@@ -124,6 +152,12 @@ impl From<WebStory> for Story {
             time: ws.time,
             comment_count: ws.comments_count,
             kids: vec![],
+            kind: ws.kind.as_deref().map(|kind| match kind {
+                "job" => HnItemKind::Job,
+                "poll" => HnItemKind::Poll,
+                _ => HnItemKind::Story,
+            }),
+            stale_secs: None,
         }
     }
 }
@@ -135,6 +169,25 @@ pub struct WebItem {
     pub content: Option<String>,
     #[serde(default)]
     pub comments: Vec<WebComment>,
+    /// A poll's options, in HN order. Absent for everything else.
+    #[serde(default)]
+    pub poll: Vec<WebPollOption>,
+}
+
+/// One option of a poll inside a `WebItem` response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebPollOption {
+    pub item: String,
+    pub points: Option<i64>,
+}
+
+impl From<WebPollOption> for PollOption {
+    fn from(option: WebPollOption) -> Self {
+        Self {
+            text: option.item,
+            score: option.points.unwrap_or(0),
+        }
+    }
 }
 
 /// A single comment inside a `WebItem` response.
@@ -184,6 +237,73 @@ impl WebComment {
                 collapsed: has_children,
                 children_loaded: true,
                 children_loading: false,
+                muted: false,
+            },
+            children,
+        })
+    }
+}
+
+// ── Algolia response types ──
+
+/// An item with its whole nested tree from `/items/:id`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlgoliaItem {
+    pub id: u64,
+    pub author: Option<String>,
+    pub created_at_i: Option<i64>,
+    /// Self-post body or comment text (HN HTML).
+    pub text: Option<String>,
+    /// Votes, for a story or a poll option.
+    pub points: Option<i64>,
+    #[serde(default)]
+    pub children: Vec<AlgoliaItem>,
+    /// A poll's options, in HN order.
+    #[serde(default)]
+    pub options: Vec<AlgoliaItem>,
+}
+
+impl AlgoliaItem {
+    pub fn into_poll_option(self) -> PollOption {
+        PollOption {
+            text: self.text.unwrap_or_default(),
+            score: self.points.unwrap_or(0),
+        }
+    }
+
+    /// Recursively convert into a `CommentNode` tree. Algolia keeps deleted
+    /// comments as entries with neither author nor text; those are dropped.
+    pub fn into_comment_node(self, depth: usize) -> Option<CommentNode> {
+        if self.author.is_none() && self.text.is_none() {
+            return None;
+        }
+
+        let child_ids: Vec<u64> = self.children.iter().map(|c| c.id).collect();
+        let children: Vec<CommentNode> = self
+            .children
+            .into_iter()
+            .filter_map(|c| c.into_comment_node(depth + 1))
+            .collect();
+
+        let has_children = !children.is_empty();
+
+        let text = self
+            .text
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| "[no text]".to_string());
+
+        Some(CommentNode {
+            comment: Comment {
+                id: self.id,
+                by: self.author,
+                time: self.created_at_i,
+                text,
+                kids: child_ids,
+                depth,
+                collapsed: has_children,
+                children_loaded: true,
+                children_loading: false,
+                muted: false,
             },
             children,
         })
@@ -217,6 +337,10 @@ pub struct HnItem {
     pub score: Option<i64>,
     pub descendants: Option<i64>,
     pub kids: Option<Vec<u64>>,
+    /// What a comment replies to: its parent comment, or the story.
+    pub parent: Option<u64>,
+    /// A poll's option ids (`pollopt` items), in HN order.
+    pub parts: Option<Vec<u64>>,
     pub dead: Option<bool>,
     pub deleted: Option<bool>,
 }
@@ -235,6 +359,14 @@ pub struct Story {
     pub time: i64,
     pub comment_count: i64,
     pub kids: Vec<u64>,
+    /// The HN item type (`story`, `job` or `poll`) when the backend reports
+    /// it. Optional in persisted state: older `state.json` files predate it.
+    #[serde(default)]
+    pub kind: Option<HnItemKind>,
+    /// Age of the disk-cache entry this Story was read from offline, when it
+    /// is past the cache TTL. Never persisted: staleness is about this run.
+    #[serde(skip)]
+    pub stale_secs: Option<u64>,
 }
 
 impl Story {
@@ -254,6 +386,11 @@ impl Story {
 pub struct StoryThread {
     pub text: Option<String>,
     pub comments: Vec<CommentNode>,
+    /// Age of the oldest stale disk-cache entry the thread was assembled
+    /// from offline; `None` when everything was fresh or came off the network.
+    pub stale_secs: Option<u64>,
+    /// A poll's options with their votes. Empty unless the story is a poll.
+    pub poll: Vec<PollOption>,
 }
 
 impl StoryThread {
@@ -261,6 +398,25 @@ impl StoryThread {
         Self {
             text: None,
             comments,
+            stale_secs: None,
+            poll: vec![],
+        }
+    }
+}
+
+/// One choice of an HN poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollOption {
+    /// HN HTML, like comment text.
+    pub text: String,
+    pub score: i64,
+}
+
+impl PollOption {
+    pub fn from_item(item: HnItem) -> Self {
+        Self {
+            text: item.text.unwrap_or_default(),
+            score: item.score.unwrap_or(0),
         }
     }
 }
@@ -295,6 +451,8 @@ impl TryFrom<HnItem> for Story {
                 .ok_or_else(|| anyhow!("item missing time id={}", item.id))?,
             comment_count: item.descendants.unwrap_or(0),
             kids: item.kids.unwrap_or_default(),
+            kind: Some(kind),
+            stale_secs: None,
         })
     }
 }
@@ -310,6 +468,9 @@ pub struct Comment {
     pub collapsed: bool,
     pub children_loaded: bool,
     pub children_loading: bool,
+    /// Written by a muted user: folded to a stub. Set by the app when the
+    /// comment is listed; backends always leave it false.
+    pub muted: bool,
 }
 
 impl Comment {
@@ -330,6 +491,7 @@ impl Comment {
             collapsed: !kids.is_empty(),
             children_loaded: kids.is_empty(),
             children_loading: false,
+            muted: false,
         }
     }
 }
@@ -356,6 +518,8 @@ mod tests {
             score: Some(1),
             descendants: Some(0),
             kids: None,
+            parent: None,
+            parts: None,
             dead: None,
             deleted: None,
         }
@@ -377,11 +541,48 @@ mod tests {
         let thread = StoryThread {
             text: item.content.filter(|text| !text.trim().is_empty()),
             comments: Vec::new(),
+            stale_secs: None,
+            poll: item.poll.into_iter().map(PollOption::from).collect(),
         };
 
         assert_eq!(thread.text.as_deref(), Some("<p>the body"));
     }
 
+    #[test]
+    fn algolia_poll_options_keep_their_order_and_votes() {
+        let payload = r#"{
+            "id": 7,
+            "text": null,
+            "points": 40,
+            "children": [],
+            "options": [
+                {"id": 8, "text": "Yes", "points": 30, "children": []},
+                {"id": 9, "text": "No", "points": null, "children": []}
+            ]
+        }"#;
+        let item: AlgoliaItem = serde_json::from_str(payload).expect("decode algolia poll");
+
+        let poll: Vec<_> = item
+            .options
+            .into_iter()
+            .map(AlgoliaItem::into_poll_option)
+            .collect();
+
+        assert_eq!(
+            poll,
+            vec![
+                PollOption {
+                    text: "Yes".to_string(),
+                    score: 30,
+                },
+                PollOption {
+                    text: "No".to_string(),
+                    score: 0,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn hackerweb_internal_discussion_url_is_not_an_article_link() {
         let payload = r#"{
@@ -420,6 +621,29 @@ mod tests {
         assert_eq!(story.url.as_deref(), Some("https://example.com/article"));
     }
 
+    #[test]
+    fn hackerweb_and_firebase_jobs_carry_their_item_type() {
+        let payload = r#"{
+            "id": 7,
+            "title": "Acme (YC W24) is hiring",
+            "url": "https://acme.dev/jobs",
+            "points": null,
+            "user": null,
+            "time": 1,
+            "comments_count": 0,
+            "type": "job"
+        }"#;
+        let listed: WebStory = serde_json::from_str(payload).expect("decode hackerweb listing");
+        let mut item = ask_hn_item();
+        item.kind = Some(HnItemKind::Job);
+
+        assert_eq!(Story::from(listed).kind, Some(HnItemKind::Job));
+        assert_eq!(
+            Story::try_from(item).expect("job item is a story").kind,
+            Some(HnItemKind::Job)
+        );
+    }
+
     #[test]
     fn a_link_submission_has_no_body_in_either_shape() {
         let mut item = ask_hn_item();
@@ -0,0 +1,108 @@
+use crate::api::search::{CommentHit, SearchClient, SearchQuery, SearchSort};
+use crate::api::types::Story;
+use crate::logging;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+
+/// An HN account and what it posted lately.
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    pub karma: i64,
+    /// Unix seconds.
+    pub created: i64,
+    /// HN HTML, as written on the profile page.
+    pub about: Option<String>,
+    /// Newest first.
+    pub submissions: Vec<Story>,
+    /// Newest first.
+    pub comments: Vec<CommentHit>,
+    /// Why a list above is empty when it could not be fetched.
+    pub activity_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirebaseUser {
+    #[serde(default)]
+    karma: i64,
+    #[serde(default)]
+    created: i64,
+    about: Option<String>,
+}
+
+/// Profiles come from Firebase `/user/:id` whatever the story backend; the
+/// activity lists are Algolia `author_` searches.
+#[derive(Clone)]
+pub struct UserClient {
+    http: Client,
+    firebase_base_url: String,
+    search: SearchClient,
+}
+
+impl UserClient {
+    pub fn new(http: Client, firebase_base_url: String, search: SearchClient) -> Self {
+        Self {
+            http,
+            firebase_base_url,
+            search,
+        }
+    }
+
+    pub async fn fetch_user(&self, id: &str) -> Result<UserProfile> {
+        let by_author = SearchQuery {
+            author: Some(id.to_string()),
+            sort: SearchSort::Date,
+            ..SearchQuery::default()
+        };
+        // The account is the profile; the lists come from another service and
+        // only cost their own rows when it is down.
+        let (account, submissions, comments) = futures::join!(
+            self.fetch_account(id),
+            self.search.search_stories(&by_author, 0),
+            self.search.search_comments(&by_author, 0),
+        );
+        let account = account?;
+        let mut errors = Vec::new();
+        let submissions = submissions.map_or_else(
+            |error| {
+                errors.push(format!("submissions: {error:#}"));
+                Vec::new()
+            },
+            |page| page.hits,
+        );
+        let comments = comments.map_or_else(
+            |error| {
+                errors.push(format!("comments: {error:#}"));
+                Vec::new()
+            },
+            |page| page.hits,
+        );
+        Ok(UserProfile {
+            karma: account.karma,
+            created: account.created,
+            about: account.about.filter(|about| !about.trim().is_empty()),
+            submissions,
+            comments,
+            activity_error: (!errors.is_empty()).then(|| errors.join("; ")),
+        })
+    }
+
+    async fn fetch_account(&self, id: &str) -> Result<FirebaseUser> {
+        logging::log_info(format!("firebase: fetching user id={id}"));
+        let url = format!("{}/user/{id}.json", self.firebase_base_url);
+        let user: Option<FirebaseUser> = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Firebase request failed for user id={id}"))?
+            .error_for_status()
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Firebase returned an error for user id={id}"))?
+            .json()
+            .await
+            .with_context(|| format!("decode Firebase user id={id}"))?;
+        user.with_context(|| format!("no such user: {id}"))
+    }
+}
@@ -1,16 +1,21 @@
-use crate::api::{CommentNode, FeedKind, Sources, Story, StoryThread};
+use crate::api::{
+    CommentHit, CommentNode, FeedKind, PollOption, SearchPage, Sources, Story, StoryThread,
+    UserProfile,
+};
 use crate::article::{Article, ArticleFetcher};
 use crate::browser::{SystemUrlOpener, UrlOpener};
 use crate::config::Config;
-use crate::input::KeyState;
+use crate::input::{KeyState, Keymap};
 use crate::logging;
-use crate::state::StateStore;
-use crate::summarizer::{ConnectionTestError, ConnectionTestSuccess, Summarizer, SummaryEvent};
+use crate::state::{CachedSummary, StateStore, SummaryKey};
+use crate::summarizer::{
+    ConnectionTestError, ConnectionTestSuccess, Summarizer, SummaryEvent, SummaryInput,
+};
 use crate::ui::summary_overlay::{SummaryOverlay, SummaryState};
 use crate::Cli;
 use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -21,33 +26,78 @@ mod article_summary_tests;
 #[cfg(test)]
 mod article_tests;
 mod articles;
+#[cfg(test)]
+mod bookmark_tests;
+mod bookmarks;
 mod comment_tree;
 mod comments;
+mod download;
 mod events;
+mod find;
+#[cfg(test)]
+mod find_tests;
+mod fold;
+#[cfg(test)]
+mod fold_tests;
 #[cfg(test)]
 mod help_tests;
 mod list_nav;
 mod mouse;
+mod mute;
+#[cfg(test)]
+mod mute_tests;
 mod prefetch;
+mod profile_picker;
+#[cfg(test)]
+mod profile_picker_tests;
 mod run;
 mod search;
 mod settings_actions;
 mod settings_connection;
 mod settings_popup;
 mod stories;
+mod story_filter;
+#[cfg(test)]
+mod story_filter_tests;
+mod summary_cache;
+#[cfg(test)]
+mod summary_cache_tests;
+mod summary_chat;
+#[cfg(test)]
+mod summary_chat_tests;
+#[cfg(test)]
+mod summary_citation_tests;
 #[cfg(test)]
 mod summary_error_tests;
 #[cfg(test)]
 mod test_support;
 #[cfg(test)]
 mod tests;
+mod unread;
+#[cfg(test)]
+mod unread_tests;
+mod user;
+#[cfg(test)]
+mod user_tests;
+mod watch;
+#[cfg(test)]
+mod watch_tests;
 
 use self::articles::ArticleStore;
+pub use self::download::OfflineDownload;
+pub use self::find::CommentFind;
+pub use self::fold::CommentFold;
+use self::mute::MuteList;
 use self::prefetch::PrefetchCache;
 pub use self::run::run;
-use self::search::SavedStories;
+pub use self::search::SearchScope;
+use self::search::{ActiveSearch, CommentFocus, SavedStories};
 pub use self::settings_popup::SettingsPopup;
 pub(crate) use self::settings_popup::{ConnectionTestState, SettingsRow};
+use self::story_filter::NamedFilter;
+use self::unread::CommentVisit;
+pub use self::user::UserRow;
+use self::watch::Watch;
 use crate::tasks::TaskLifecycle;
 pub(crate) use crate::tasks::{TaskId, TaskTarget};
 use crate::ui::article_overlay::ArticleOverlay;
@@ -58,6 +108,7 @@ use crate::ui::help::{HelpFocus, HelpOverlay};
 pub enum View {
     Stories,
     Comments,
+    User,
 }
 
 #[derive(Debug)]
@@ -77,9 +128,19 @@ pub enum AppEvent {
         task: TaskId,
         children: Vec<CommentNode>,
     },
+    CommentParentLoaded {
+        task: TaskId,
+        parent: Option<u64>,
+    },
     SearchResultsLoaded {
         task: TaskId,
-        stories: Vec<Story>,
+        mode: StoriesLoadMode,
+        page: SearchPage<Story>,
+    },
+    CommentSearchResultsLoaded {
+        task: TaskId,
+        mode: StoriesLoadMode,
+        page: SearchPage<CommentHit>,
     },
     ArticleLoaded {
         task: TaskId,
@@ -90,13 +151,30 @@ pub enum AppEvent {
         task: TaskId,
         story_id: u64,
     },
+    StoryDownloaded {
+        task: TaskId,
+        complete: bool,
+    },
     Summary {
         task: TaskId,
         event: SummaryEvent,
     },
     SettingsSaved {
         task: TaskId,
-        config: Config,
+        config: Box<Config>,
+    },
+    UserLoaded {
+        task: TaskId,
+        profile: UserProfile,
+    },
+    WatchPolled {
+        task: TaskId,
+        story_id: u64,
+        thread: StoryThread,
+    },
+    WatchChildrenPolled {
+        task: TaskId,
+        children: Vec<CommentNode>,
     },
     ConnectionTestFinished {
         task: TaskId,
@@ -131,6 +209,8 @@ pub(crate) struct PendingSummary {
     story_id: u64,
     comments_ready: bool,
     owns_comments_task: bool,
+    /// Show a cached summary rather than calling the LLM, when one matches.
+    use_cache: bool,
     article: ArticleLeg,
 }
 
@@ -149,6 +229,33 @@ pub struct FeedFilterPopup {
     pub feed_cursor: usize,
 }
 
+/// A row of the `f` popup: one of the HN feeds, or the local Saved
+/// pseudo-feed listing starred stories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FeedRow {
+    Feed(FeedKind),
+    Saved,
+}
+
+impl FeedRow {
+    pub(crate) const ALL: [Self; 7] = [
+        Self::Feed(FeedKind::Top),
+        Self::Feed(FeedKind::New),
+        Self::Feed(FeedKind::Best),
+        Self::Feed(FeedKind::Ask),
+        Self::Feed(FeedKind::Show),
+        Self::Feed(FeedKind::Jobs),
+        Self::Saved,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Feed(feed) => feed.label(),
+            Self::Saved => "Saved",
+        }
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct LayoutAreas {
     pub list_area: Rect,
@@ -176,9 +283,40 @@ pub struct App {
     pub comment_list_state: ListState,
     pub comment_loading: bool,
     pub comment_layout: CommentLayout,
+    /// Age of the oldest part of the open thread, when it came from an
+    /// expired disk-cache entry.
+    pub comment_stale_secs: Option<u64>,
+    /// The open story's options and votes, when it is a poll.
+    pub comment_poll: Vec<PollOption>,
+    /// Where backing out of the thread goes: the feed, or the profile it
+    /// was opened from.
+    comments_back_view: View,
+    watch: Option<Watch>,
+    /// Comments new since the last visit, or brought in by a watch poll,
+    /// that the selection has not reached.
+    pub new_comment_ids: HashSet<u64>,
+    comment_visit: Option<CommentVisit>,
+    /// Levels picked with `z` for the open thread; `None` until then.
+    comment_levels: Option<usize>,
+    pub comment_fold: Option<CommentFold>,
+    pub find_input_active: bool,
+    pub comment_find: Option<CommentFind>,
+
+    /// The account `u` opened, kept while its profile loads.
+    pub user_id: Option<String>,
+    pub user_profile: Option<UserProfile>,
+    pub user_loading: bool,
+    pub user_list_state: ListState,
+    pub user_page_size: usize,
+    user_back_view: View,
+    /// The thread `u` was pressed in: opening a submission from the profile
+    /// replaces it, so leaving the profile reopens it.
+    user_back_story: Option<Story>,
 
     pub last_error: Option<String>,
     pub copied_flash: Option<Instant>,
+    /// What `m d` / `m a` last muted, confirmed in the footer for a moment.
+    pub muted_flash: Option<(String, Instant)>,
     pub layout_areas: LayoutAreas,
 
     sources: Sources,
@@ -187,6 +325,8 @@ pub struct App {
     state_store: Option<StateStore>,
 
     pub has_more_stories: bool,
+    pub offline: bool,
+    pub offline_download: Option<OfflineDownload>,
     prefetched_comments_cache: PrefetchCache,
     article_fetcher: ArticleFetcher,
     articles: ArticleStore,
@@ -200,14 +340,31 @@ pub struct App {
     pub settings_popup: Option<SettingsPopup>,
     config: Config,
     pub keyword_filter: String,
+    /// `[feed_filters]`, applied to a feed's listing under any typed filter.
+    feed_filters: HashMap<FeedKind, NamedFilter>,
+    /// `[mute]`: hides stories everywhere, search included.
+    mute: MuteList,
     pub visible_story_indices: Vec<usize>,
     pub filter_input_active: bool,
 
     pub search_input_active: bool,
     pub search_query: String,
     pub search_active: bool,
+    pub search_scope: SearchScope,
+    active_search: Option<ActiveSearch>,
+    /// Parallel to `stories` while comment-search results are listed.
+    pub comment_hits: Vec<CommentHit>,
+    pending_comment_focus: Option<CommentFocus>,
     saved_stories: Option<SavedStories>,
     pending_summary: Option<PendingSummary>,
+    /// The summary profile picked last; later summaries keep using it.
+    summary_profile: Option<String>,
+    /// Finished summaries, newest first, mirrored to `summaries.json`.
+    pub summary_cache: Vec<CachedSummary>,
+    /// What the running summary will be cached under once it completes.
+    running_summary_key: Option<SummaryKey>,
+    /// The story context behind the summary on show, for follow-ups.
+    summary_input: Option<SummaryInput>,
 
     input: KeyState,
     should_quit: bool,
@@ -217,6 +374,14 @@ pub struct App {
     pending_story_selection_id: Option<u64>,
 
     pub seen_story_ids: HashSet<u64>,
+    /// Story ID → highest comment ID loaded when it was last open.
+    pub comment_read_marks: HashMap<u64, u64>,
+
+    /// Starred stories, most recently starred first.
+    pub bookmarks: Vec<Story>,
+    pub saved_feed_active: bool,
+    /// The HN feed that was on screen when the Saved feed replaced it.
+    feed_before_saved: Option<SavedStories>,
 }
 
 impl App {
@@ -235,6 +400,22 @@ impl App {
         let mut comment_list_state = ListState::default();
         comment_list_state.select(Some(0));
 
+        // A bad [keys] entry should not keep the reader out; run on the
+        // defaults and say why.
+        let (keymap, keys_error) = match config.keys().map(Keymap::from_config) {
+            None => (Keymap::default(), None),
+            Some(Ok(keymap)) => (keymap, None),
+            Some(Err(error)) => (Keymap::default(), Some(format!("config: {error:#}"))),
+        };
+        // Likewise a bad filter: show every feed unfiltered.
+        let (feed_filters, filters_error) = match story_filter::feed_filters(&config) {
+            Ok(filters) => (filters, None),
+            Err(error) => (HashMap::new(), Some(format!("config: {error:#}"))),
+        };
+        let mute = MuteList::new(config.mute().cloned().unwrap_or_default());
+        // Summary budgets from before max_prompt_tokens are ignored; say so.
+        let retired_notice = config.retired_keys_notice();
+
         Self {
             view: View::Stories,
             help_overlay: HelpOverlay::default(),
@@ -250,12 +431,35 @@ impl App {
             comment_list_state,
             comment_loading: false,
             comment_layout: CommentLayout::default(),
-
-            last_error: None,
+            comment_stale_secs: None,
+            comment_poll: vec![],
+            comments_back_view: View::Stories,
+            watch: None,
+            new_comment_ids: HashSet::new(),
+            comment_visit: None,
+            comment_levels: None,
+            comment_fold: None,
+            find_input_active: false,
+            comment_find: None,
+
+            user_id: None,
+            user_profile: None,
+            user_loading: false,
+            user_list_state: ListState::default(),
+            user_page_size: 10,
+            user_back_view: View::Stories,
+            user_back_story: None,
+
+            last_error: keys_error
+                .or(filters_error)
+                .or_else(|| mute.error.clone())
+                .or(retired_notice),
             copied_flash: None,
+            muted_flash: None,
             layout_areas: LayoutAreas::default(),
 
             sources,
+            offline: cli.offline,
             cli,
             tasks: TaskLifecycle::new(
                 tx,
@@ -265,6 +469,7 @@ impl App {
             state_store,
 
             has_more_stories: true,
+            offline_download: None,
             prefetched_comments_cache: PrefetchCache::new(PREFETCH_CACHE_CAP),
             article_fetcher,
             articles: ArticleStore::new(ARTICLE_CACHE_CAP),
@@ -277,15 +482,25 @@ impl App {
             settings_popup: None,
             config,
             keyword_filter: String::new(),
+            feed_filters,
+            mute,
             visible_story_indices: vec![],
             filter_input_active: false,
 
             search_input_active: false,
             search_query: String::new(),
             search_active: false,
+            search_scope: SearchScope::default(),
+            active_search: None,
+            comment_hits: Vec::new(),
+            pending_comment_focus: None,
             saved_stories: None,
             pending_summary: None,
-            input: KeyState::default(),
+            summary_profile: None,
+            summary_cache: Vec::new(),
+            running_summary_key: None,
+            summary_input: None,
+            input: KeyState::new(keymap),
             should_quit: false,
             spinner_idx: 0,
             last_user_activity: Instant::now(),
@@ -293,6 +508,11 @@ impl App {
             pending_story_selection_id: None,
 
             seen_story_ids: HashSet::new(),
+            comment_read_marks: HashMap::new(),
+
+            bookmarks: vec![],
+            saved_feed_active: false,
+            feed_before_saved: None,
         }
     }
 
@@ -315,18 +535,22 @@ impl App {
             self.last_error = logging::last_write_error();
         }
         self.maybe_prefetch_comments();
+        self.maybe_poll_watched_thread();
     }
 
     fn is_busy(&self) -> bool {
         self.story_loading
             || self.comment_loading
+            || self.user_loading
             || self.tasks.is_running(TaskTarget::Stories)
             || self.tasks.is_running(TaskTarget::Search)
+            || self.tasks.is_running(TaskTarget::OfflineDownload)
             || self.tasks.count_where(|target| {
                 matches!(
                     target,
                     TaskTarget::CommentRoots(_)
                         | TaskTarget::CommentChildren(_)
+                        | TaskTarget::CommentParent(_)
                         | TaskTarget::Article(_)
                         | TaskTarget::Summary
                         | TaskTarget::ConnectionTest
@@ -343,6 +567,11 @@ impl App {
         self.should_quit
     }
 
+    /// The bindings keys resolve through, defaults plus `[keys]`.
+    pub fn keymap(&self) -> &Keymap {
+        self.input.keymap()
+    }
+
     /// Which help section to highlight — the overlay in front of the view, if
     /// any. Overlays never stack, so at most one of these is visible.
     pub(crate) fn help_focus(&self) -> HelpFocus {
@@ -367,7 +596,7 @@ impl App {
                 .set_viewport(viewport.width, viewport.height);
         }
         self.help_overlay
-            .set_frame(area, self.view, self.help_focus());
+            .set_frame(area, self.input.keymap(), self.view, self.help_focus());
 
         match self.view {
             View::Stories => {
@@ -376,7 +605,8 @@ impl App {
                 self.story_page_size = (list_area.height as usize).max(1);
             }
             View::Comments => {
-                let (list_area, _) = crate::ui::comment_view::content_areas(area);
+                let (list_area, _) =
+                    crate::ui::comment_view::content_areas(area, self.comment_poll.len());
                 self.layout_areas.list_area = list_area;
                 let viewport_height = (list_area.height as usize).max(1);
 
@@ -386,6 +616,8 @@ impl App {
                         list_area.width as usize,
                         viewport_height,
                         self.spinner_frame(),
+                        &self.new_comment_ids,
+                        "",
                     );
                     return;
                 }
@@ -396,15 +628,25 @@ impl App {
                     .unwrap_or(0)
                     .min(self.comment_list.len() - 1);
                 self.comment_list_state.select(Some(selected));
+                self.new_comment_ids.remove(&self.comment_list[selected].id);
                 let spinner = self.spinner_frame();
                 self.comment_layout.relayout(
                     &self.comment_list,
                     list_area.width as usize,
                     viewport_height,
                     spinner,
+                    &self.new_comment_ids,
+                    self.comment_find
+                        .as_ref()
+                        .map_or("", |find| find.query.as_str()),
                 );
                 self.comment_layout.ensure_visible(selected);
             }
+            View::User => {
+                let (list_area, _) = crate::ui::user_view::content_areas(area);
+                self.layout_areas.list_area = list_area;
+                self.user_page_size = (list_area.height as usize).max(1);
+            }
         }
     }
 }
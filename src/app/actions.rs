@@ -1,6 +1,5 @@
-use super::list_nav::{move_selection_down, move_selection_up, page_down, page_up};
-use super::{App, FeedFilterPopup, SettingsPopup, TaskTarget, View};
-use crate::api::FeedKind;
+use super::list_nav::{move_selection_down, move_selection_up, page_down, page_up, ThreadMotion};
+use super::{App, FeedFilterPopup, FeedRow, SettingsPopup, View};
 use crate::input::{Action, HelpAction, InputLayer, SummaryAction};
 use anyhow::Context;
 use crossterm::event::KeyEventKind;
@@ -10,10 +9,18 @@ impl App {
     pub(crate) fn input_layer(&self) -> InputLayer {
         if self.help_overlay.is_visible() {
             InputLayer::Help
+        } else if self.summary_overlay.profile_picker().is_some() {
+            InputLayer::ProfilePicker
+        } else if self.summary_overlay.is_typing_question() {
+            InputLayer::SummaryChat
         } else if self.summary_overlay.is_visible() {
             InputLayer::Summary
         } else if self.article_overlay.is_visible() {
-            InputLayer::Article
+            if self.article_overlay.is_typing_find() {
+                InputLayer::ArticleFind
+            } else {
+                InputLayer::Article
+            }
         } else if let Some(settings) = &self.settings_popup {
             if settings.editing {
                 InputLayer::SettingsEditor
@@ -26,6 +33,8 @@ impl App {
             InputLayer::FilterText
         } else if self.search_input_active {
             InputLayer::SearchText
+        } else if self.find_input_active {
+            InputLayer::FindText
         } else {
             InputLayer::View
         }
@@ -53,13 +62,7 @@ impl App {
             }
             Action::Summary(action) => {
                 match action {
-                    SummaryAction::Dismiss => {
-                        self.tasks.cancel(TaskTarget::Summary);
-                        // Also drop a summarize still gathering its inputs,
-                        // or it would spring back when they arrive.
-                        self.abandon_pending_summary();
-                        self.summary_overlay.dismiss();
-                    }
+                    SummaryAction::Dismiss => self.dismiss_summary(),
                     SummaryAction::ScrollDown(amount) => self.summary_overlay.scroll_down(amount),
                     SummaryAction::ScrollUp(amount) => self.summary_overlay.scroll_up(amount),
                     SummaryAction::PageDown => {
@@ -77,10 +80,23 @@ impl App {
                             self.last_error = Some(format!("clipboard: {error:#}"));
                         }
                     }
+                    SummaryAction::Regenerate => self.regenerate_summary(),
+                    SummaryAction::PickProfile => self.open_profile_picker(),
                     SummaryAction::OpenHelp => self.help_overlay.open(),
+                    SummaryAction::StartQuestion => self.summary_overlay.start_question(),
+                    SummaryAction::Question(action) => self.handle_summary_question(action),
+                    SummaryAction::NextCitation => self.summary_overlay.select_next_citation(),
+                    SummaryAction::PreviousCitation => {
+                        self.summary_overlay.select_previous_citation()
+                    }
+                    SummaryAction::OpenCitation => self.open_summary_citation(),
                 }
                 return;
             }
+            Action::ProfilePicker(action) => {
+                self.handle_profile_picker_action(action);
+                return;
+            }
             Action::Article(action) => {
                 self.handle_article_action(action);
                 return;
@@ -101,23 +117,57 @@ impl App {
                 self.handle_search_input_action(action);
                 return;
             }
+            Action::FindInput(action) => {
+                self.handle_find_input_action(action);
+                return;
+            }
+            Action::ToggleSearchScope => {
+                self.search_scope = self.search_scope.toggled();
+                return;
+            }
             _ => {}
         }
 
         match (self.view, action) {
             (_, Action::OpenHelp) => self.help_overlay.open(),
+            (
+                View::User,
+                action @ (Action::BackOrQuit
+                | Action::MoveDown
+                | Action::MoveUp
+                | Action::PageDown
+                | Action::PageUp
+                | Action::GoTop
+                | Action::GoBottom
+                | Action::SelectUserRow(_)
+                | Action::Enter
+                | Action::OpenComments
+                | Action::Expand
+                | Action::Refresh),
+            ) => self.handle_user_action(action),
             (View::Stories, Action::BackOrQuit) if self.search_active => {
                 self.exit_search_mode();
             }
             (View::Stories, Action::BackOrQuit) => self.should_quit = true,
+            (View::Comments, Action::BackOrQuit) if self.comment_find.is_some() => {
+                self.comment_find = None;
+            }
             (View::Comments, Action::BackOrQuit) => {
-                self.view = View::Stories;
-                self.maybe_prefetch_comments();
+                self.pending_comment_focus = None;
+                self.view = std::mem::replace(&mut self.comments_back_view, View::Stories);
+                if self.view == View::Stories {
+                    self.maybe_prefetch_comments();
+                }
             }
             (View::Stories, Action::OpenFeedFilter) => {
-                let cursor = FeedKind::ALL
+                let current = if self.saved_feed_active {
+                    FeedRow::Saved
+                } else {
+                    FeedRow::Feed(self.current_feed)
+                };
+                let cursor = FeedRow::ALL
                     .iter()
-                    .position(|&f| f == self.current_feed)
+                    .position(|&row| row == current)
                     .unwrap_or(0);
                 self.feed_filter_popup = Some(FeedFilterPopup {
                     feed_cursor: cursor,
@@ -126,6 +176,9 @@ impl App {
             (View::Stories, Action::OpenFilter) => {
                 self.filter_input_active = true;
             }
+            (View::Stories, Action::StartSearch) if self.offline => {
+                self.last_error = Some("search is unavailable offline".to_string());
+            }
             (View::Stories, Action::StartSearch) => {
                 self.search_input_active = true;
                 self.search_query.clear();
@@ -133,27 +186,32 @@ impl App {
             (View::Stories, Action::Refresh) if self.search_active => {
                 self.submit_search();
             }
+            (View::Stories, Action::Refresh) if self.saved_feed_active => {
+                self.show_bookmarks();
+            }
             (View::Stories, Action::Refresh) => self.refresh_stories(),
             (View::Comments, Action::Refresh) => self.refresh_comments(),
 
+            (View::Stories, Action::Enter | Action::OpenComments | Action::Expand)
+                if self.selected_comment_hit().is_some() =>
+            {
+                self.open_selected_comment_hit();
+            }
             (View::Stories, Action::Enter) => self.open_comments_for_selected_story(),
             (View::Stories, Action::OpenComments) => self.open_comments_for_selected_story(),
             (View::Stories, Action::Expand) => self.open_comments_for_selected_story(),
             (view, action @ (Action::OpenPrimaryBrowser | Action::OpenSecondaryBrowser)) => {
-                let story = match view {
-                    View::Stories => self.selected_story().cloned(),
-                    View::Comments => self.current_story.clone(),
-                };
+                let story = self.story_in_view();
                 let result = story
                     .as_ref()
                     .context(match view {
-                        View::Stories => "no selected story",
+                        View::Stories | View::User => "no selected story",
                         View::Comments => "no current story",
                     })
                     .and_then(|story| {
                         let open_source = matches!(
                             (view, action),
-                            (View::Stories, Action::OpenPrimaryBrowser)
+                            (View::Stories | View::User, Action::OpenPrimaryBrowser)
                                 | (View::Comments, Action::OpenSecondaryBrowser)
                         );
                         let hn_url =
@@ -266,23 +324,46 @@ impl App {
             (View::Comments, Action::Expand) => self.expand_selected_comment(),
             (View::Comments, Action::ToggleCollapse) => self.toggle_selected_comment_collapse(),
 
+            (View::Stories | View::Comments, Action::OpenUser) => self.open_selected_author(),
+            (View::Comments, Action::ToggleWatch) => self.toggle_watch(),
+            (View::Comments, Action::NextUnread) => self.select_unread_comment(true),
+            (View::Comments, Action::PreviousUnread) => self.select_unread_comment(false),
+            (View::Comments, Action::ParentComment) => self.move_in_thread(ThreadMotion::Parent),
+            (View::Comments, Action::NextSibling) => self.move_in_thread(ThreadMotion::NextSibling),
+            (View::Comments, Action::PreviousSibling) => {
+                self.move_in_thread(ThreadMotion::PreviousSibling)
+            }
+            (View::Comments, Action::NextRoot) => self.move_in_thread(ThreadMotion::NextRoot),
+            (View::Comments, Action::PreviousRoot) => {
+                self.move_in_thread(ThreadMotion::PreviousRoot)
+            }
+            (View::Comments, Action::CollapseThread) => self.collapse_selected_thread(),
+            (View::Comments, Action::StartSearch) => self.start_comment_find(),
+            (View::Comments, Action::NextMatch) => self.step_comment_find(true),
+            (View::Comments, Action::PreviousMatch) => self.step_comment_find(false),
+            (View::Comments, Action::CollapseAll) => self.show_comment_levels(1),
+            (View::Comments, Action::ExpandAll) => self.show_comment_levels(usize::MAX),
+            (View::Comments, Action::ShowLevels(levels)) => self.show_comment_levels(levels),
+            (View::Stories | View::Comments, Action::MuteDomain) => self.mute_selected_domain(),
+            (View::Stories | View::Comments, Action::MuteAuthor) => self.mute_selected_author(),
+
+            (_, Action::ToggleBookmark) => self.toggle_bookmark(),
+            (_, Action::ToggleOffline) => self.toggle_offline(),
+            (View::Stories, Action::DownloadOffline) => self.toggle_offline_download(),
+
             (View::Comments, Action::CopyComment) => {
                 self.copy_selected_comment();
             }
 
             (View::Comments, Action::Summarize) => {
-                self.start_summary_for_loaded_comments();
+                self.start_summary_for_loaded_comments(true);
             }
             (View::Stories, Action::Summarize) => {
                 self.summarize_selected_story();
             }
 
-            (view, Action::ViewArticle) => {
-                let story = match view {
-                    View::Stories => self.selected_story().cloned(),
-                    View::Comments => self.current_story.clone(),
-                };
-                if let Some(story) = story {
+            (_, Action::ViewArticle) => {
+                if let Some(story) = self.story_in_view() {
                     self.open_article_overlay(&story);
                 }
             }
@@ -295,6 +376,16 @@ impl App {
         }
     }
 
+    /// The story `o`, `b` and `v` act on: the selected one in a list, or
+    /// the open thread's.
+    pub(super) fn story_in_view(&self) -> Option<crate::api::Story> {
+        match self.view {
+            View::Stories => self.selected_story().cloned(),
+            View::Comments => self.current_story.clone(),
+            View::User => self.selected_user_row().map(|row| row.story().clone()),
+        }
+    }
+
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
         if key.kind == KeyEventKind::Release {
             return;
@@ -1,107 +1,58 @@
 use super::article_tests::{linked_story, listed_self_post, self_post};
-use super::tests::{cli, comment, story, test_article_fetcher};
+use super::tests::{
+    self, app_with_llm, comment, finish_summary, story, summarize_config, FakeLlmStream,
+};
 use super::*;
-use crate::api::{InMemorySource, Sources};
+use crate::api::InMemorySource;
 use crate::config::{Config, SummarizeConfig};
 use crate::input::{Action, SettingsAction, SummaryAction, TextAction};
-use crate::summarizer::{
-    LlmFuture, LlmSession, LlmStream, Summarizer, SummaryChunk, SummaryRequest,
-};
 use crate::ui::summary_overlay::SummaryState;
-use futures::FutureExt;
-use std::sync::{Arc, Mutex};
-
-#[derive(Clone)]
-struct SuccessfulLlmStream;
-
-impl LlmStream for SuccessfulLlmStream {
-    fn start(&self, _request: SummaryRequest) -> LlmFuture {
-        async move {
-            Ok(LlmSession::for_test(
-                "fake/model",
-                vec![Ok(SummaryChunk {
-                    content: "summary".to_string(),
-                    reasoning: String::new(),
-                })],
-            ))
-        }
-        .boxed()
-    }
-}
+use std::path::PathBuf;
 
-#[derive(Clone)]
-struct RecordingLlmStream {
-    prompts: Arc<Mutex<Vec<String>>>,
-}
-
-impl LlmStream for RecordingLlmStream {
-    fn start(&self, request: SummaryRequest) -> LlmFuture {
-        self.prompts
-            .lock()
-            .expect("prompt recorder poisoned")
-            .push(request.user_prompt().to_string());
-        SuccessfulLlmStream.start(request)
-    }
-}
-
-fn summarize_config(include_article: bool) -> SummarizeConfig {
-    SummarizeConfig {
-        model: "fake/model".to_string(),
-        api_key: None,
-        base_url: None,
-        max_comments: 20,
+fn article_app(
+    stories: Vec<Story>,
+    source: InMemorySource,
+    include_article: bool,
+    stream: &FakeLlmStream,
+    config_path: PathBuf,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let summarize = SummarizeConfig {
         include_article,
-        max_article_chars: 20_000,
-        system_prompt: "Summarize".to_string(),
-    }
+        ..summarize_config(Vec::new())
+    };
+    let config = Config::for_test_with_summarize(config_path, summarize);
+    let (mut app, rx) = app_with_llm(source, config, None, stream);
+    let story_ids = stories.iter().map(|story| story.id).collect();
+    app.restore_story_list_state(story_ids, stories, None);
+    (app, rx)
 }
 
 fn app_with_source(
     stories: Vec<Story>,
-    source: Arc<InMemorySource>,
+    source: InMemorySource,
     include_article: bool,
 ) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
-    let sources = Sources::new(source.clone(), source);
-    let (tx, rx) = mpsc::unbounded_channel();
-    let summarize = summarize_config(include_article);
-    let directory = std::env::temp_dir().join(format!(
+    let config_path = std::env::temp_dir().join(format!(
         "hntui-summarize-{}-{include_article}.toml",
         std::process::id()
     ));
-    let config = Config::for_test_with_summarize(directory, summarize.clone());
-    let summarizer = Summarizer::with_stream(Some(summarize), None, Arc::new(SuccessfulLlmStream));
-    let mut app = App::new(
-        cli(),
-        sources,
-        tx,
-        None,
-        config,
-        summarizer,
-        test_article_fetcher(),
-    );
-    let story_ids = stories.iter().map(|story| story.id).collect();
-    app.restore_story_list_state(story_ids, stories, None);
-    (app, rx)
+    article_app(
+        stories,
+        source,
+        include_article,
+        &FakeLlmStream::replying("summary"),
+        config_path,
+    )
 }
 
 fn app_with_summarize(
     stories: Vec<Story>,
     include_article: bool,
 ) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
-    let source = Arc::new(InMemorySource::new(stories.clone()).with_comments(1, vec![comment(11)]));
+    let source = InMemorySource::new(stories.clone()).with_comments(1, vec![comment(11)]);
     app_with_source(stories, source, include_article)
 }
 
-async fn finish_summary(app: &mut App, rx: &mut mpsc::UnboundedReceiver<AppEvent>) {
-    while app.tasks.is_running(TaskTarget::Summary) {
-        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
-            .await
-            .expect("summary timed out")
-            .expect("app event channel closed");
-        app.handle_app_event(event);
-    }
-}
-
 #[tokio::test]
 async fn a_self_post_summary_needs_no_fetch_and_carries_no_degrade_banner() {
     let (mut app, mut rx) = app_with_summarize(vec![self_post(1, "<p>the body")], true);
@@ -174,31 +125,15 @@ async fn toggling_the_article_off_summarizes_immediately_without_a_fetch() {
 async fn settings_toggle_off_preserves_the_pre_article_prompt_bytes() {
     let directory = tempfile::tempdir().expect("temp dir");
     let item = linked_story(1);
-    let source =
-        Arc::new(InMemorySource::new(vec![item.clone()]).with_comments(1, vec![comment(11)]));
-    let sources = Sources::new(source.clone(), source);
-    let (tx, mut rx) = mpsc::unbounded_channel();
-    let summarize = summarize_config(true);
-    let config =
-        Config::for_test_with_summarize(directory.path().join("config.toml"), summarize.clone());
-    let prompts = Arc::new(Mutex::new(Vec::new()));
-    let summarizer = Summarizer::with_stream(
-        Some(summarize),
-        None,
-        Arc::new(RecordingLlmStream {
-            prompts: prompts.clone(),
-        }),
-    );
-    let mut app = App::new(
-        cli(),
-        sources,
-        tx,
-        None,
-        config,
-        summarizer,
-        test_article_fetcher(),
+    let source = InMemorySource::new(vec![item.clone()]).with_comments(1, vec![comment(11)]);
+    let stream = FakeLlmStream::replying("summary");
+    let (mut app, mut rx) = article_app(
+        vec![item.clone()],
+        source,
+        true,
+        &stream,
+        directory.path().join("config.toml"),
     );
-    app.restore_story_list_state(vec![item.id], vec![item.clone()], None);
 
     app.handle_action(Action::OpenSettings);
     for _ in 0..4 {
@@ -233,9 +168,14 @@ async fn settings_toggle_off_preserves_the_pre_article_prompt_bytes() {
     finish_summary(&mut app, &mut rx).await;
 
     assert_eq!(app.summary_overlay.state(), SummaryState::Done);
+    let prompts: Vec<String> = stream
+        .requests()
+        .iter()
+        .map(|request| request.user_prompt().to_string())
+        .collect();
     assert_eq!(
-        prompts.lock().expect("prompt recorder poisoned").as_slice(),
-        ["# story 1\n\nbob: hello\n\n"]
+        prompts,
+        ["# story 1\n\n[11] bob: hello\n\nCite the comments you draw on by their bracketed ID, like [11].\n\n"]
     );
 }
 
@@ -308,19 +248,8 @@ async fn dismissing_summary_preserves_comment_loading_started_by_the_view() {
 
 #[tokio::test]
 async fn a_failed_comment_load_fails_the_waiting_summary_overlay() {
-    let source = Arc::new(InMemorySource::new(vec![linked_story(1)]).with_initial_error("unused"));
-    let sources = Sources::new(source.clone(), source);
-    let (tx, mut rx) = mpsc::unbounded_channel();
-    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
-    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
-    let mut app = App::new(
-        cli(),
-        sources,
-        tx,
-        None,
-        config,
-        summarizer,
-        test_article_fetcher(),
+    let (mut app, mut rx) = tests::app_with_source(
+        InMemorySource::new(vec![linked_story(1)]).with_initial_error("unused"),
     );
     app.restore_story_list_state(vec![1], vec![linked_story(1)], None);
 
@@ -344,8 +273,7 @@ async fn a_failed_comment_load_fails_the_waiting_summary_overlay() {
 #[tokio::test]
 async fn a_zero_comment_self_post_body_completes_an_article_only_summary() {
     let item = story(1);
-    let source =
-        Arc::new(InMemorySource::new(vec![item.clone()]).with_thread_text(1, "<p>the ask hn body"));
+    let source = InMemorySource::new(vec![item.clone()]).with_thread_text(1, "<p>the ask hn body");
     let (mut app, mut rx) = app_with_source(vec![item], source, true);
 
     app.handle_action(Action::Summarize);
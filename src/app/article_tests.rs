@@ -19,7 +19,7 @@ fn app_with_stories_and_opener(
     url_opener: Arc<dyn UrlOpener>,
 ) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
     let source = Arc::new(InMemorySource::new(stories.clone()));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -231,6 +231,25 @@ async fn v_on_a_linked_story_fetches_and_a_failure_lands_in_the_overlay() {
     assert!(!app.tasks.is_running(TaskTarget::Article(1)));
 }
 
+#[tokio::test]
+async fn offline_v_reads_only_the_stored_article() {
+    let (mut app, mut rx) = app_with_stories(vec![linked_story(1)]);
+    app.offline = true;
+
+    app.handle_key(key(KeyCode::Char('v')));
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("article read timed out")
+        .expect("app event channel closed");
+    app.handle_app_event(event);
+
+    assert_eq!(app.article_overlay.state(), ArticleState::Error);
+    assert_eq!(
+        app.article_overlay.error(),
+        Some("not downloaded for offline reading (press D while online)")
+    );
+}
+
 #[tokio::test]
 async fn esc_in_the_article_overlay_cancels_the_fetch_and_closes() {
     let (mut app, _rx) = app_with_stories(vec![linked_story(1)]);
@@ -313,7 +332,7 @@ async fn v_on_a_listed_self_post_resolves_its_body_through_the_source() {
             .with_comments(1, vec![comment(11)])
             .with_thread_text(1, "<p>the ask hn body"),
     );
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -348,7 +367,7 @@ async fn v_on_a_listed_self_post_resolves_its_body_through_the_source() {
 async fn v_on_a_zero_comment_listed_self_post_still_checks_for_its_body() {
     let source =
         Arc::new(InMemorySource::new(vec![story(1)]).with_thread_text(1, "<p>fresh ask hn body"));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -381,7 +400,7 @@ async fn a_listed_self_post_with_no_body_reports_no_article() {
     let source = Arc::new(
         InMemorySource::new(vec![listed_self_post(1)]).with_comments(1, vec![comment(11)]),
     );
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -415,6 +434,8 @@ fn a_prefetched_discussion_serves_the_self_post_body_without_a_task() {
         StoryThread {
             text: Some("<p>prefetched body".to_string()),
             comments: vec![comment(11)],
+            stale_secs: None,
+            poll: vec![],
         },
         &app.stories,
         selected,
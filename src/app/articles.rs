@@ -1,7 +1,7 @@
 use super::{App, AppEvent, TaskTarget};
 use crate::api::Story;
 use crate::article::{body_article, self_post_article, Article};
-use crate::input::ArticleAction;
+use crate::input::{ArticleAction, TextAction};
 use crate::logging;
 use std::collections::HashMap;
 
@@ -54,9 +54,17 @@ impl App {
 
     fn spawn_linked_page_fetch(&mut self, story_id: u64, url: String) {
         let fetcher = self.article_fetcher.clone();
+        let offline = self.offline;
         self.tasks.spawn(
             TaskTarget::Article(story_id),
-            async move { fetcher.fetch(url).await.map_err(anyhow::Error::from) },
+            async move {
+                let article = if offline {
+                    fetcher.fetch_offline(url).await
+                } else {
+                    fetcher.fetch(url).await
+                };
+                article.map_err(anyhow::Error::from)
+            },
             move |task, article| AppEvent::ArticleLoaded {
                 task,
                 story_id,
@@ -129,6 +137,9 @@ impl App {
 
     pub(super) fn handle_article_action(&mut self, action: ArticleAction) {
         match action {
+            ArticleAction::Dismiss if self.article_overlay.has_find() => {
+                self.article_overlay.clear_find();
+            }
             ArticleAction::Dismiss => {
                 self.cancel_article_fetch(self.article_overlay.story_id());
                 self.article_overlay.dismiss();
@@ -155,6 +166,16 @@ impl App {
             ArticleAction::SelectPreviousLink => self.article_overlay.select_previous_link(),
             ArticleAction::OpenSelectedLink => self.open_selected_article_link(),
             ArticleAction::OpenHelp => self.help_overlay.open(),
+            ArticleAction::StartFind => self.article_overlay.start_find(),
+            ArticleAction::Find(action) => match action {
+                TextAction::Submit => self.article_overlay.submit_find(),
+                TextAction::Cancel => self.article_overlay.cancel_find(),
+                TextAction::DeleteBackward => self.article_overlay.pop_find(),
+                TextAction::Insert(character) => self.article_overlay.push_find(character),
+                _ => unreachable!("unsupported article find text action: {action:?}"),
+            },
+            ArticleAction::NextMatch => self.article_overlay.next_match(),
+            ArticleAction::PreviousMatch => self.article_overlay.previous_match(),
         }
     }
 
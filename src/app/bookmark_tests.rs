@@ -0,0 +1,119 @@
+use super::tests::{app_with_parts, comment, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::config::Config;
+use crate::input::{Action, FeedFilterAction};
+use crate::summarizer::Summarizer;
+
+fn bookmark_app(
+    source: InMemorySource,
+    state_store: Option<StateStore>,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    app_with_parts(source, config, state_store, summarizer)
+}
+
+fn saved_feed_index() -> usize {
+    FeedRow::ALL
+        .iter()
+        .position(|&row| row == FeedRow::Saved)
+        .expect("Saved row in the feed popup")
+}
+
+fn ids(stories: &[Story]) -> Vec<u64> {
+    stories.iter().map(|story| story.id).collect()
+}
+
+#[tokio::test]
+async fn a_starred_story_shows_in_the_saved_feed_and_leaving_restores_the_feed() {
+    let (mut app, mut rx) = bookmark_app(
+        InMemorySource::new(vec![story(1), story(2), story(3)]),
+        None,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::SelectStory(1));
+    app.handle_action(Action::ToggleBookmark);
+    app.handle_action(Action::OpenFeedFilter);
+    app.handle_action(Action::FeedFilter(FeedFilterAction::SelectIndex(
+        saved_feed_index(),
+    )));
+
+    assert!(app.saved_feed_active);
+    assert_eq!(ids(&app.stories), vec![2]);
+    assert!(!app.has_more_stories);
+
+    app.handle_action(Action::OpenFeedFilter);
+    assert_eq!(
+        app.feed_filter_popup
+            .as_ref()
+            .map(|popup| popup.feed_cursor),
+        Some(saved_feed_index())
+    );
+    app.handle_action(Action::FeedFilter(FeedFilterAction::SelectIndex(0)));
+
+    assert!(!app.saved_feed_active);
+    assert_eq!(ids(&app.stories), vec![1, 2, 3]);
+    assert!(
+        !app.tasks.is_running(TaskTarget::Stories),
+        "returning to the same feed reuses the snapshot"
+    );
+}
+
+#[tokio::test]
+async fn unstarring_inside_the_saved_feed_removes_the_row() {
+    let (mut app, mut rx) = bookmark_app(InMemorySource::new(vec![story(1), story(2)]), None);
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::ToggleBookmark);
+    app.handle_action(Action::SelectStory(1));
+    app.handle_action(Action::ToggleBookmark);
+    app.handle_action(Action::OpenFeedFilter);
+    app.handle_action(Action::FeedFilter(FeedFilterAction::SelectIndex(
+        saved_feed_index(),
+    )));
+    assert_eq!(ids(&app.stories), vec![2, 1]);
+
+    app.handle_action(Action::ToggleBookmark);
+
+    assert_eq!(ids(&app.stories), vec![1]);
+    assert_eq!(ids(&app.bookmarks), vec![1]);
+    assert_eq!(app.story_list_state.selected(), Some(0));
+}
+
+#[tokio::test]
+async fn the_comments_view_stars_the_open_story() {
+    let (mut app, mut rx) = bookmark_app(
+        InMemorySource::new(vec![story(1)]).with_comments(1, vec![comment(11)]),
+        None,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+
+    app.handle_action(Action::ToggleBookmark);
+
+    assert_eq!(app.view, View::Comments);
+    assert!(app.is_bookmarked(1));
+}
+
+#[tokio::test]
+async fn bookmarks_are_written_next_to_the_story_state() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = StateStore::new(dir.path().to_path_buf());
+    let (mut app, mut rx) = bookmark_app(InMemorySource::new(vec![story(1)]), Some(store.clone()));
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::ToggleBookmark);
+    while app.tasks.is_running(TaskTarget::BookmarksSave) {
+        app.handle_app_event(rx.recv().await.expect("task event"));
+    }
+
+    let saved = store.load_bookmarks().await.expect("load bookmarks");
+    assert_eq!(ids(&saved), vec![1]);
+    assert_eq!(app.last_error, None);
+}
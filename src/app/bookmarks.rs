@@ -0,0 +1,82 @@
+use super::search::SavedStories;
+use super::{App, AppEvent, TaskTarget};
+use crate::api::FeedKind;
+
+impl App {
+    pub fn is_bookmarked(&self, id: u64) -> bool {
+        self.bookmarks.iter().any(|story| story.id == id)
+    }
+
+    /// Star or unstar the story in front of the user: the selected row in the
+    /// list, or the open story in the comments view.
+    pub(super) fn toggle_bookmark(&mut self) {
+        let Some(story) = self.story_in_view() else {
+            return;
+        };
+
+        match self.bookmarks.iter().position(|saved| saved.id == story.id) {
+            Some(index) => {
+                self.bookmarks.remove(index);
+            }
+            None => self.bookmarks.insert(0, story),
+        }
+        if self.saved_feed_active && !self.search_active {
+            self.show_bookmarks();
+        }
+        self.save_bookmarks_background();
+    }
+
+    pub(super) fn enter_saved_feed(&mut self) {
+        if self.search_active {
+            self.exit_search_mode();
+        }
+        if !self.saved_feed_active {
+            self.feed_before_saved = Some(SavedStories::capture(self));
+            self.saved_feed_active = true;
+        }
+        self.tasks.cancel(TaskTarget::Stories);
+        self.cancel_comment_root_tasks();
+        self.story_loading = false;
+        self.last_error = None;
+        self.story_list_state.select(Some(0));
+        *self.story_list_state.offset_mut() = 0;
+        self.show_bookmarks();
+    }
+
+    /// Put back the HN feed the Saved feed replaced, fetching only when the
+    /// user picked a different feed or there was nothing loaded to return to.
+    pub(super) fn leave_saved_feed(&mut self, feed: FeedKind) {
+        if self.search_active {
+            self.exit_search_mode();
+        }
+        self.saved_feed_active = false;
+        if let Some(snapshot) = self.feed_before_saved.take() {
+            snapshot.restore(self);
+        }
+        if feed != self.current_feed || self.stories.is_empty() {
+            self.current_feed = feed;
+            self.refresh_stories();
+        }
+        self.recompute_visible_stories();
+    }
+
+    /// Nothing to fetch: the Saved feed is the bookmark list itself.
+    pub(super) fn show_bookmarks(&mut self) {
+        self.stories = self.bookmarks.clone();
+        self.story_ids = self.stories.iter().map(|story| story.id).collect();
+        self.has_more_stories = false;
+        self.recompute_visible_stories();
+    }
+
+    fn save_bookmarks_background(&mut self) {
+        let Some(store) = self.state_store.clone() else {
+            return;
+        };
+        let stories = self.bookmarks.clone();
+        self.tasks.spawn(
+            TaskTarget::BookmarksSave,
+            async move { store.save_bookmarks(stories).await },
+            |task, ()| AppEvent::TaskCompleted { task },
+        );
+    }
+}
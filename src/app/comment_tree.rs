@@ -1,4 +1,6 @@
 use crate::api::types::{Comment, CommentNode};
+use crate::text::{find_ignore_case, hn_html_to_plain};
+use std::collections::HashSet;
 
 pub(crate) fn flatten_visible_comments(tree: &[CommentNode]) -> Vec<Comment> {
     fn walk(nodes: &[CommentNode], out: &mut Vec<Comment>) {
@@ -15,21 +17,94 @@ pub(crate) fn flatten_visible_comments(tree: &[CommentNode]) -> Vec<Comment> {
     out
 }
 
-pub(crate) fn apply_default_expansion(tree: &mut [CommentNode], visible_levels: usize) {
-    let expand_depth_exclusive = visible_levels.saturating_sub(1);
+/// Ids of loaded comments whose text or author contains `query`, ignoring
+/// case, in display order with collapsed replies included.
+pub(crate) fn find_comments(tree: &[CommentNode], query: &str) -> Vec<u64> {
+    fn walk(nodes: &[CommentNode], query: &str, out: &mut Vec<u64>) {
+        for node in nodes {
+            let comment = &node.comment;
+            let by = comment.by.as_deref().unwrap_or_default();
+            if !find_ignore_case(by, query).is_empty()
+                || !find_ignore_case(&hn_html_to_plain(&comment.text), query).is_empty()
+            {
+                out.push(comment.id);
+            }
+            walk(&node.children, query, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(tree, query, &mut out);
+    out
+}
+
+/// Ids of every loaded comment, collapsed or not, in display order.
+pub(crate) fn comment_ids(tree: &[CommentNode]) -> Vec<u64> {
+    fn walk(nodes: &[CommentNode], out: &mut Vec<u64>) {
+        for node in nodes {
+            out.push(node.comment.id);
+            walk(&node.children, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(tree, &mut out);
+    out
+}
 
-    fn walk(nodes: &mut [CommentNode], expand_depth_exclusive: usize) {
+/// Every loaded comment a summary reads, collapsed or not, in display order.
+/// Muted comments are left out with their replies, which only ever show
+/// behind the mute stub.
+pub(crate) fn summary_comments(tree: &[CommentNode]) -> Vec<Comment> {
+    fn walk(nodes: &[CommentNode], out: &mut Vec<Comment>) {
         for node in nodes {
-            if node.comment.depth < expand_depth_exclusive && !node.comment.kids.is_empty() {
-                node.comment.collapsed = false;
+            if node.comment.muted {
+                continue;
             }
-            if !node.children.is_empty() {
-                walk(&mut node.children, expand_depth_exclusive);
+            out.push(node.comment.clone());
+            walk(&node.children, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(tree, &mut out);
+    out
+}
+
+/// Fold `tree` to show exactly `visible_levels` levels: comments above the
+/// last shown level are expanded, deeper ones collapsed. Returns the expanded
+/// comments whose replies are neither loaded nor loading, in display order.
+pub(crate) fn show_levels(tree: &mut [CommentNode], visible_levels: usize) -> Vec<u64> {
+    let expand_depth_exclusive = visible_levels.saturating_sub(1);
+
+    fn walk(nodes: &mut [CommentNode], expand_depth_exclusive: usize, unloaded: &mut Vec<u64>) {
+        for node in nodes {
+            let comment = &mut node.comment;
+            if !comment.kids.is_empty() {
+                comment.collapsed = comment.depth >= expand_depth_exclusive;
+                if !comment.collapsed && !comment.children_loaded && !comment.children_loading {
+                    unloaded.push(comment.id);
+                }
             }
+            walk(&mut node.children, expand_depth_exclusive, unloaded);
         }
     }
 
-    walk(tree, expand_depth_exclusive);
+    let mut unloaded = Vec::new();
+    walk(tree, expand_depth_exclusive, &mut unloaded);
+    unloaded
+}
+
+/// Mark comments whose author `is_muted`, folding each the first time it
+/// is marked so its replies stay hidden until asked for.
+pub(crate) fn mark_muted_comments(tree: &mut [CommentNode], is_muted: &dyn Fn(&str) -> bool) {
+    for node in tree {
+        if !node.comment.muted && node.comment.by.as_deref().is_some_and(is_muted) {
+            node.comment.muted = true;
+            node.comment.collapsed = true;
+        }
+        mark_muted_comments(&mut node.children, is_muted);
+    }
 }
 
 pub(crate) fn set_collapse(tree: &mut [CommentNode], target: u64, collapsed: bool) -> Option<()> {
@@ -45,6 +120,33 @@ pub(crate) fn set_collapse(tree: &mut [CommentNode], target: u64, collapsed: boo
     None
 }
 
+pub(crate) fn find_node_mut(tree: &mut [CommentNode], target: u64) -> Option<&mut CommentNode> {
+    for node in tree {
+        if node.comment.id == target {
+            return Some(node);
+        }
+        if let Some(found) = find_node_mut(&mut node.children, target) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Ids of the comments above `target`, root first; `None` when it is not in
+/// the tree.
+pub(crate) fn ancestors_of(tree: &[CommentNode], target: u64) -> Option<Vec<u64>> {
+    for node in tree {
+        if node.comment.id == target {
+            return Some(Vec::new());
+        }
+        if let Some(mut path) = ancestors_of(&node.children, target) {
+            path.insert(0, node.comment.id);
+            return Some(path);
+        }
+    }
+    None
+}
+
 pub(crate) fn info_for_comment(
     tree: &[CommentNode],
     target: u64,
@@ -110,6 +212,106 @@ pub(crate) fn attach_children(
     inner(tree, target, &mut children).then_some(())
 }
 
+/// Fold a re-fetched thread into `tree`. Comments already there keep their
+/// collapse and load state and take the newer text and kid list; comments
+/// seen for the first time land after their preceding sibling, expanded
+/// like a fresh load, with their ids added to `arrived`. Only levels loaded
+/// on both sides are compared. A loaded comment whose kids grew below the
+/// levels the poll descended into is returned when expanded, for its
+/// replies to be fetched and merged with [`merge_children`]; a collapsed
+/// one is marked unloaded so expanding it fetches them.
+///
+/// Nothing is removed. A comment deleted or flagged since the last poll
+/// keeps its old text until the thread is loaded again, so a poll
+/// never pulls the comment being read out from under the selection.
+pub(crate) fn merge_thread(
+    tree: &mut Vec<CommentNode>,
+    incoming: Vec<CommentNode>,
+    visible_levels: usize,
+    arrived: &mut HashSet<u64>,
+) -> Vec<u64> {
+    fn collect_ids(node: &CommentNode, arrived: &mut HashSet<u64>) {
+        arrived.insert(node.comment.id);
+        for child in &node.children {
+            collect_ids(child, arrived);
+        }
+    }
+
+    fn merge(
+        tree: &mut Vec<CommentNode>,
+        incoming: Vec<CommentNode>,
+        visible_levels: usize,
+        arrived: &mut HashSet<u64>,
+        grown: &mut Vec<u64>,
+    ) {
+        let mut insert_at = 0;
+        for mut node in incoming {
+            let Some(index) = tree
+                .iter()
+                .position(|existing| existing.comment.id == node.comment.id)
+            else {
+                show_levels(std::slice::from_mut(&mut node), visible_levels);
+                collect_ids(&node, arrived);
+                tree.insert(insert_at, node);
+                insert_at += 1;
+                continue;
+            };
+            insert_at = index + 1;
+
+            let existing = &mut tree[index];
+            existing.comment.text = node.comment.text;
+            let mut kids_grew = false;
+            for kid in node.comment.kids {
+                if !existing.comment.kids.contains(&kid) {
+                    existing.comment.kids.push(kid);
+                    kids_grew = true;
+                }
+            }
+            if !existing.comment.children_loaded {
+                continue;
+            }
+            if node.comment.children_loaded {
+                merge(
+                    &mut existing.children,
+                    node.children,
+                    visible_levels,
+                    arrived,
+                    grown,
+                );
+            } else if kids_grew {
+                if existing.comment.collapsed {
+                    existing.comment.children_loaded = false;
+                } else {
+                    grown.push(existing.comment.id);
+                }
+            }
+        }
+    }
+
+    let mut grown = Vec::new();
+    merge(tree, incoming, visible_levels, arrived, &mut grown);
+    grown
+}
+
+/// [`merge_thread`] for the re-fetched replies of `target`. `None` when it
+/// is no longer in the tree.
+pub(crate) fn merge_children(
+    tree: &mut [CommentNode],
+    target: u64,
+    children: Vec<CommentNode>,
+    visible_levels: usize,
+    arrived: &mut HashSet<u64>,
+) -> Option<Vec<u64>> {
+    let node = find_node_mut(tree, target)?;
+    node.comment.children_loading = false;
+    Some(merge_thread(
+        &mut node.children,
+        children,
+        visible_levels,
+        arrived,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +327,7 @@ mod tests {
             collapsed: !kids.is_empty(),
             children_loaded: kids.is_empty(),
             children_loading: false,
+            muted: false,
         }
     }
 
@@ -169,14 +372,43 @@ mod tests {
     }
 
     #[test]
-    fn apply_default_expansion_expands_only_configured_depths() {
+    fn show_levels_expands_only_configured_depths() {
         let mut tree = tree();
-        apply_default_expansion(&mut tree, 2);
+        show_levels(&mut tree, 2);
 
         assert!(!tree[0].comment.collapsed);
         assert!(tree[0].children[0].comment.collapsed);
     }
 
+    #[test]
+    fn show_levels_folds_both_ways_and_reports_unloaded_replies() {
+        let mut tree = vec![
+            node(
+                1,
+                0,
+                vec![2],
+                vec![node(2, 1, vec![3], vec![node(3, 2, vec![], vec![])])],
+            ),
+            node(5, 0, vec![6], vec![]),
+        ];
+        tree[0].comment.children_loaded = true;
+        tree[0].children[0].comment.children_loaded = true;
+
+        assert_eq!(show_levels(&mut tree, usize::MAX), vec![5]);
+        assert!(!tree[0].children[0].comment.collapsed);
+
+        assert_eq!(show_levels(&mut tree, 1), Vec::<u64>::new());
+        assert!(tree[0].comment.collapsed);
+        assert!(tree[1].comment.collapsed);
+        assert_eq!(
+            flatten_visible_comments(&tree)
+                .iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+    }
+
     #[test]
     fn attach_children_marks_parent_loaded_and_clear_loading() {
         let mut tree = vec![node(1, 0, vec![2], vec![])];
@@ -189,9 +421,105 @@ mod tests {
         assert_eq!(tree[0].children[0].comment.id, 2);
     }
 
+    #[test]
+    fn find_looks_at_authors_and_text_under_collapsed_replies() {
+        let tree = tree();
+
+        assert_eq!(find_comments(&tree, "C4"), vec![4]);
+        assert_eq!(find_comments(&tree, "u3"), vec![3]);
+        assert!(find_comments(&tree, "nobody").is_empty());
+    }
+
+    #[test]
+    fn comment_ids_include_collapsed_replies_in_display_order() {
+        let tree = tree();
+
+        assert_eq!(comment_ids(&tree), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn ancestors_run_from_the_root_down_to_the_parent() {
+        let tree = tree();
+
+        assert_eq!(ancestors_of(&tree, 4), Some(vec![1, 2]));
+        assert_eq!(ancestors_of(&tree, 1), Some(vec![]));
+    }
+
+    #[test]
+    fn merging_a_poll_keeps_collapse_state_and_places_new_replies_by_sibling() {
+        fn loaded(mut node: CommentNode) -> CommentNode {
+            node.comment.children_loaded = true;
+            node
+        }
+
+        let mut tree = tree();
+        tree[0].comment.children_loaded = true;
+        set_collapse(&mut tree, 1, false).expect("root present");
+        let polled = vec![
+            loaded(node(
+                1,
+                0,
+                vec![2, 5, 3],
+                vec![
+                    node(2, 1, vec![4], vec![]),
+                    node(5, 1, vec![], vec![]),
+                    loaded(node(3, 1, vec![6], vec![node(6, 2, vec![], vec![])])),
+                ],
+            )),
+            node(7, 0, vec![], vec![]),
+        ];
+        let mut arrived = HashSet::new();
+
+        merge_thread(&mut tree, polled, 2, &mut arrived);
+
+        assert_eq!(arrived, HashSet::from([5, 6, 7]));
+        assert!(!tree[0].comment.collapsed);
+        assert!(tree[0].children[0].comment.collapsed);
+        assert_eq!(
+            flatten_visible_comments(&tree)
+                .iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 5, 3, 6, 7]
+        );
+    }
+
+    #[test]
+    fn replies_below_the_polled_levels_are_handed_back_for_expanded_comments() {
+        let mut tree = vec![
+            node(1, 0, vec![], vec![]),
+            node(2, 0, vec![3], vec![node(3, 1, vec![], vec![])]),
+        ];
+        tree[1].comment.children_loaded = true;
+        set_collapse(&mut tree, 2, true).expect("root present");
+        let polled = vec![node(1, 0, vec![4], vec![]), node(2, 0, vec![3, 5], vec![])];
+        let mut arrived = HashSet::new();
+
+        let grown = merge_thread(&mut tree, polled, 2, &mut arrived);
+
+        assert_eq!(grown, vec![1]);
+        assert!(tree[0].comment.children_loaded);
+        assert!(!tree[1].comment.children_loaded);
+        assert!(arrived.is_empty());
+
+        let grown = merge_children(
+            &mut tree,
+            1,
+            vec![node(4, 1, vec![], vec![])],
+            2,
+            &mut arrived,
+        )
+        .expect("root present");
+
+        assert!(grown.is_empty());
+        assert_eq!(arrived, HashSet::from([4]));
+        assert_eq!(comment_ids(&tree), vec![1, 4, 2, 3]);
+    }
+
     #[test]
     fn missing_comment_returns_none() {
         let mut tree = tree();
+        assert!(ancestors_of(&tree, 99).is_none());
 
         assert!(set_collapse(&mut tree, 99, false).is_none());
         assert!(set_children_loading(&mut tree, 99, true).is_none());
@@ -1,7 +1,8 @@
 use super::articles::ArticleRequest;
 use super::comment_tree::{
-    apply_default_expansion, flatten_visible_comments, info_for_comment as comment_info_in_tree,
+    ancestors_of, flatten_visible_comments, info_for_comment as comment_info_in_tree,
     set_children_loading as set_children_loading_in_tree, set_collapse as set_collapse_in_tree,
+    show_levels, summary_comments,
 };
 use super::{App, AppEvent, ArticleLeg, CommentLoadKind, PendingSummary, TaskTarget, View};
 use crate::api::{Story, StoryThread};
@@ -10,6 +11,7 @@ use crate::config::default_include_article;
 use crate::summarizer::SummaryInput;
 use crate::ui::theme;
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::time::Instant;
 
 impl App {
@@ -25,6 +27,10 @@ impl App {
         let Some(story) = self.selected_story().cloned() else {
             return;
         };
+        self.open_comments_for(story);
+    }
+
+    pub(super) fn open_comments_for(&mut self, story: Story) {
         self.mark_story_seen(story.id);
 
         if self
@@ -102,12 +108,30 @@ impl App {
         self.comment_loading = false;
         self.tasks
             .cancel_where(|target| matches!(target, TaskTarget::CommentChildren(_)));
+        self.comment_fold = None;
+        let known = if self
+            .current_story
+            .as_ref()
+            .is_some_and(|current| current.id != story.id)
+        {
+            self.stop_watch();
+            self.new_comment_ids.clear();
+            self.comment_levels = None;
+            self.find_input_active = false;
+            self.comment_find = None;
+            HashSet::new()
+        } else {
+            self.loaded_comment_ids()
+        };
         self.last_error = None;
+        self.comment_stale_secs = thread.stale_secs;
+        self.comment_poll = thread.poll;
         story.absorb_text(thread.text);
         self.remember_story_text(&story);
         self.current_story = Some(story);
         self.comment_tree = thread.comments;
-        self.apply_default_comment_expansion();
+        self.apply_comment_levels();
+        self.note_loaded_comments(&known);
         self.rebuild_comment_list(None);
         self.comment_list_state.select(Some(0));
         self.comment_layout.invalidate();
@@ -130,9 +154,21 @@ impl App {
     }
 
     pub(super) fn reset_comment_state(&mut self) {
+        self.stop_watch();
+        self.new_comment_ids.clear();
         self.comment_tree.clear();
-        self.tasks
-            .cancel_where(|target| matches!(target, TaskTarget::CommentChildren(_)));
+        self.comment_stale_secs = None;
+        self.comment_poll.clear();
+        self.tasks.cancel_where(|target| {
+            matches!(
+                target,
+                TaskTarget::CommentChildren(_) | TaskTarget::CommentParent(_)
+            )
+        });
+        self.comment_levels = None;
+        self.comment_fold = None;
+        self.find_input_active = false;
+        self.comment_find = None;
         self.comment_list.clear();
         self.comment_layout.invalidate();
         self.comment_list_state.select(Some(0));
@@ -140,6 +176,7 @@ impl App {
     }
 
     pub(super) fn rebuild_comment_list(&mut self, preserve_comment_id: Option<u64>) {
+        self.mark_muted_comments();
         self.comment_list = flatten_visible_comments(&self.comment_tree);
         self.comment_layout.invalidate();
 
@@ -151,42 +188,57 @@ impl App {
         }
     }
 
-    fn apply_default_comment_expansion(&mut self) {
-        apply_default_expansion(
-            &mut self.comment_tree,
-            theme::COMMENT_DEFAULT_VISIBLE_LEVELS,
-        );
+    /// Levels the open thread shows: the ones picked with `z`, or the default.
+    pub(super) fn visible_comment_levels(&self) -> usize {
+        self.comment_levels
+            .unwrap_or(theme::COMMENT_DEFAULT_VISIBLE_LEVELS)
+    }
+
+    /// Fold a freshly loaded tree. Only levels the reader picked fetch the
+    /// replies the backend left out; the default never costs a request.
+    fn apply_comment_levels(&mut self) {
+        let levels = self.visible_comment_levels();
+        let unloaded = show_levels(&mut self.comment_tree, levels);
+        if self.comment_levels.is_some() {
+            self.queue_comment_fold(unloaded);
+        }
     }
 
-    fn start_loading_comment_children(&mut self, parent_id: u64) {
+    pub(super) fn start_loading_comment_children(&mut self, parent_id: u64) {
+        if self.spawn_comment_children(parent_id) {
+            self.rebuild_comment_list(Some(parent_id));
+        }
+    }
+
+    /// Expand `parent_id` and fetch its replies. False when there is nothing
+    /// to fetch or the fetch is already running.
+    pub(super) fn spawn_comment_children(&mut self, parent_id: u64) -> bool {
         if self
             .tasks
             .is_running(TaskTarget::CommentChildren(parent_id))
         {
-            return;
+            return false;
         }
 
         let Some(info) = comment_info_in_tree(&self.comment_tree, parent_id) else {
             self.last_error = Some(format!("comment not found id={parent_id}"));
-            return;
+            return false;
         };
         let (parent_depth, kids, children_loaded, children_loading) = info;
 
         if kids.is_empty() || children_loaded || children_loading {
-            return;
+            return false;
         }
 
         if set_children_loading_in_tree(&mut self.comment_tree, parent_id, true).is_none() {
             self.last_error = Some(format!("comment not found id={parent_id}"));
-            return;
+            return false;
         }
         if set_collapse_in_tree(&mut self.comment_tree, parent_id, false).is_none() {
             self.last_error = Some(format!("comment not found id={parent_id}"));
-            return;
+            return false;
         }
 
-        self.rebuild_comment_list(Some(parent_id));
-
         let depth = parent_depth.saturating_add(1);
         let source = self.sources.stories.clone();
         self.tasks.spawn(
@@ -194,6 +246,7 @@ impl App {
             async move { source.comment_children(kids, depth).await },
             move |task, children| AppEvent::CommentChildrenLoaded { task, children },
         );
+        true
     }
 
     pub(super) fn cancel_comment_root_tasks(&mut self) {
@@ -230,7 +283,8 @@ impl App {
         let Some(comment) = self.comment_list.get(selected) else {
             return;
         };
-        if comment.kids.is_empty() || comment.collapsed {
+        // A muted comment folds to a stub even without replies to hide.
+        if (comment.kids.is_empty() && !comment.muted) || comment.collapsed {
             return;
         }
 
@@ -250,12 +304,13 @@ impl App {
         let Some(comment) = self.comment_list.get(selected) else {
             return;
         };
-        if comment.kids.is_empty() {
+        if comment.kids.is_empty() && !comment.muted {
             return;
         }
 
         let id = comment.id;
-        let needs_load = !comment.children_loaded && !comment.children_loading;
+        let needs_load =
+            !comment.kids.is_empty() && !comment.children_loaded && !comment.children_loading;
         if set_collapse_in_tree(&mut self.comment_tree, id, false).is_none() {
             self.last_error = Some(format!("comment not found id={id}"));
             return;
@@ -276,7 +331,7 @@ impl App {
         let Some(comment) = self.comment_list.get(selected) else {
             return;
         };
-        if comment.kids.is_empty() {
+        if comment.kids.is_empty() && !comment.muted {
             return;
         }
         if comment.collapsed {
@@ -298,23 +353,60 @@ impl App {
             .as_ref()
             .is_some_and(|current| current.id == story.id);
         if is_current && !self.comment_list.is_empty() {
-            self.start_summary_for_loaded_comments();
+            self.start_summary_for_loaded_comments(true);
             return;
         }
 
         if let Some(thread) = self.prefetched_comments_cache.remove(story.id) {
             self.apply_comments_for_story(story, thread, false);
-            self.start_summary_for_loaded_comments();
+            self.start_summary_for_loaded_comments(true);
             return;
         }
 
         // Comments are not in hand: the load and the article fetch run in
         // parallel, and `begin_summary` shows the overlay while they settle.
-        self.begin_summary(story.clone(), false, true);
+        self.begin_summary(story.clone(), false, true, true);
         self.load_comments_for_story(story, false);
     }
 
-    pub(super) fn start_summary_for_loaded_comments(&mut self) {
+    pub(super) fn dismiss_summary(&mut self) {
+        self.tasks.cancel(TaskTarget::Summary);
+        // Also drop a summarize still gathering its inputs, or it would
+        // spring back when they arrive.
+        self.abandon_pending_summary();
+        self.summary_overlay.dismiss();
+        self.summary_input = None;
+    }
+
+    /// Enter on a citation: close the summary and select the cited comment
+    /// in the thread, unfolding the path down to it.
+    pub(super) fn open_summary_citation(&mut self) {
+        let Some(comment_id) = self.summary_overlay.selected_citation() else {
+            return;
+        };
+        let Some(story) = self.current_story.clone() else {
+            return;
+        };
+        if ancestors_of(&self.comment_tree, comment_id).is_none() {
+            self.last_error = Some(format!(
+                "comment id={comment_id} is not in the loaded thread"
+            ));
+            return;
+        }
+        self.dismiss_summary();
+        self.open_comments_for(story);
+        self.reveal_comment(comment_id);
+    }
+
+    /// Tell the overlay which comments the summary can cite: the whole loaded
+    /// thread, since a cached summary may be reopened with replies folded.
+    pub(super) fn set_summary_comment_ids(&mut self) {
+        let comment_ids = self.loaded_comment_ids();
+        self.summary_overlay.set_comment_ids(comment_ids);
+    }
+
+    /// `use_cache: false` asks the LLM even when a cached summary matches.
+    pub(super) fn start_summary_for_loaded_comments(&mut self, use_cache: bool) {
         let Some(story) = self.current_story.clone() else {
             self.summary_overlay.fail("No story selected".to_string());
             return;
@@ -323,16 +415,30 @@ impl App {
         // Reuse that task, but do not let dismissing the summary cancel work
         // owned by the view.
         let comments_ready = !self.comment_loading || !self.comment_list.is_empty();
-        self.begin_summary(story, comments_ready, false);
+        self.begin_summary(story, comments_ready, false, use_cache);
     }
 
-    fn begin_summary(&mut self, story: Story, comments_ready: bool, owns_comments_task: bool) {
+    fn begin_summary(
+        &mut self,
+        story: Story,
+        comments_ready: bool,
+        owns_comments_task: bool,
+        use_cache: bool,
+    ) {
         self.summary_overlay.begin(&story, self.comment_list.len());
+        self.summary_overlay
+            .set_profile(self.summary_profile.clone());
+        self.running_summary_key = None;
+        self.summary_input = None;
+        if use_cache && comments_ready && self.show_cached_summary(story.id) {
+            return;
+        }
         let article = self.plan_article_leg(&story);
         self.pending_summary = Some(PendingSummary {
             story_id: story.id,
             comments_ready,
             owns_comments_task,
+            use_cache,
             article,
         });
         self.run_or_await_pending_summary();
@@ -341,13 +447,7 @@ impl App {
     /// Decide the article leg, kicking a fetch when one is needed. A story
     /// with nothing to fetch is settled, not failed.
     fn plan_article_leg(&mut self, story: &Story) -> ArticleLeg {
-        let includes_article = self
-            .config
-            .summarize()
-            .map_or_else(default_include_article, |summarize| {
-                summarize.include_article
-            });
-        if !includes_article {
+        if !self.summary_includes_article() {
             return ArticleLeg::Ready(None);
         }
         match self.request_article(story) {
@@ -356,6 +456,14 @@ impl App {
         }
     }
 
+    pub(super) fn summary_includes_article(&self) -> bool {
+        self.config
+            .summarize()
+            .map_or_else(default_include_article, |summarize| {
+                summarize.include_article
+            })
+    }
+
     /// A settled article fetch reaching a summarize that is waiting on it.
     pub(super) fn settle_pending_summary_article(
         &mut self,
@@ -384,6 +492,10 @@ impl App {
             return;
         }
         pending.comments_ready = true;
+        if pending.use_cache && self.show_cached_summary(story_id) {
+            self.abandon_pending_summary();
+            return;
+        }
         self.run_or_await_pending_summary();
     }
 
@@ -450,16 +562,21 @@ impl App {
             ArticleLeg::Failed(reason) => (None, Some(reason)),
             ArticleLeg::Pending => unreachable!("a pending leg cannot start a summary"),
         };
-        self.summary_overlay
-            .set_comment_count(self.comment_list.len());
+        let comments = summary_comments(&self.comment_tree);
+        self.summary_overlay.set_comment_count(comments.len());
+        self.set_summary_comment_ids();
         self.summary_overlay.set_waiting_for(None);
         self.summary_overlay.set_article_notice(notice);
 
         let input = SummaryInput {
             story,
-            comments: self.comment_list.clone(),
+            comments,
             article,
+            poll: self.comment_poll.clone(),
+            profile: self.summary_profile.clone(),
         };
+        self.running_summary_key = self.summary_key(input.story.id);
+        self.summary_input = Some(input.clone());
         let summarizer = self.summarizer.clone();
         self.tasks.spawn_stream(
             TaskTarget::Summary,
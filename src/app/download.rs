@@ -0,0 +1,135 @@
+use super::{App, AppEvent, TaskTarget};
+use crate::api::{CommentNode, Story, StorySource};
+use crate::article::ArticleFetcher;
+use crate::logging;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Stories downloaded side by side. Each fans out further inside the client,
+/// which caps its own request concurrency.
+const DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// Progress of a `D` download, drawn as a bar in the story-list footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfflineDownload {
+    pub total: usize,
+    pub done: usize,
+    /// Stories whose thread or article did not make it to disk.
+    pub failed: usize,
+}
+
+impl App {
+    /// `D`: put every loaded story's whole discussion and its Article on disk,
+    /// so the feed reads the same offline. Pressing it again stops the run.
+    pub(super) fn toggle_offline_download(&mut self) {
+        if self.offline_download.is_some() {
+            self.tasks.cancel(TaskTarget::OfflineDownload);
+            self.offline_download = None;
+            return;
+        }
+        if self.offline {
+            self.last_error = Some("downloading needs the network".to_string());
+            return;
+        }
+        if self.cli.no_file_cache {
+            self.last_error = Some("downloading for offline needs the file cache".to_string());
+            return;
+        }
+        if self.stories.is_empty() {
+            return;
+        }
+
+        let stories = self.stories.clone();
+        self.offline_download = Some(OfflineDownload {
+            total: stories.len(),
+            done: 0,
+            failed: 0,
+        });
+        self.last_error = None;
+        let source = self.sources.stories.clone();
+        let fetcher = self.article_fetcher.clone();
+        let downloads = futures::stream::iter(stories)
+            .map(move |story| download_story(source.clone(), fetcher.clone(), story))
+            .buffer_unordered(DOWNLOAD_CONCURRENCY)
+            .map(anyhow::Ok)
+            .boxed();
+        self.tasks
+            .spawn_stream(TaskTarget::OfflineDownload, downloads, |task, complete| {
+                AppEvent::StoryDownloaded { task, complete }
+            });
+    }
+
+    pub(super) fn handle_story_downloaded(&mut self, complete: bool) {
+        let Some(download) = self.offline_download.as_mut() else {
+            return;
+        };
+        download.done += 1;
+        if !complete {
+            download.failed += 1;
+        }
+    }
+
+    pub(super) fn finish_offline_download(&mut self) {
+        let Some(download) = self.offline_download.take() else {
+            return;
+        };
+        if download.failed > 0 {
+            self.last_error = Some(format!(
+                "offline download: {} of {} stories incomplete (see log)",
+                download.failed, download.total
+            ));
+        }
+    }
+}
+
+/// One story's share of the download. Failures are logged rather than
+/// returned: a dead link must not stop the rest of the feed.
+async fn download_story(
+    source: Arc<dyn StorySource>,
+    fetcher: ArticleFetcher,
+    story: Story,
+) -> bool {
+    let story_id = story.id;
+    let url = story.url.clone();
+    let mut complete = true;
+    if let Err(error) = download_thread(source.as_ref(), story).await {
+        logging::log_error(format!(
+            "offline download: thread story_id={story_id}: {error:#}"
+        ));
+        complete = false;
+    }
+    // A self-post's Article is its body, which the thread already cached.
+    if let Some(url) = url {
+        if let Err(error) = fetcher.fetch(url).await {
+            logging::log_error(format!(
+                "offline download: article story_id={story_id}: {error}"
+            ));
+            complete = false;
+        }
+    }
+    complete
+}
+
+/// Walk the whole discussion, not just the levels the comment view preloads,
+/// so every reply passes through the source's disk cache.
+async fn download_thread(source: &dyn StorySource, story: Story) -> anyhow::Result<()> {
+    let thread = source.comment_roots(story).await?;
+    let mut pending = Vec::new();
+    queue_unloaded_replies(&thread.comments, &mut pending);
+    while let Some((ids, depth)) = pending.pop() {
+        let children = source.comment_children(ids, depth).await?;
+        queue_unloaded_replies(&children, &mut pending);
+    }
+    Ok(())
+}
+
+fn queue_unloaded_replies(nodes: &[CommentNode], pending: &mut Vec<(Vec<u64>, usize)>) {
+    for node in nodes {
+        let comment = &node.comment;
+        if comment.children_loaded {
+            queue_unloaded_replies(&node.children, pending);
+        } else if !comment.kids.is_empty() {
+            pending.push((comment.kids.clone(), comment.depth + 1));
+        }
+    }
+}
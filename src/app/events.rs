@@ -4,6 +4,7 @@ use super::comment_tree::{
 };
 use super::{App, AppEvent, CommentLoadKind, StoriesLoadMode, TaskId, TaskTarget};
 use crate::logging;
+use crate::summarizer::SummaryEvent;
 
 impl App {
     pub fn handle_app_event(&mut self, event: AppEvent) {
@@ -24,25 +25,89 @@ impl App {
                 let TaskTarget::CommentChildren(parent_id) = task.target() else {
                     unreachable!("comment-children event has a non-children target");
                 };
+                let known = self.loaded_comment_ids();
                 if attach_children_in_tree(&mut self.comment_tree, parent_id, children).is_none() {
                     self.last_error = Some(format!("comment not found id={parent_id}"));
                     return;
                 }
-                self.rebuild_comment_list(Some(parent_id));
+                self.note_loaded_comments(&known);
+                if self.is_fold_load(parent_id) {
+                    self.finish_fold_load(parent_id, true);
+                } else {
+                    self.rebuild_comment_list(Some(parent_id));
+                }
+                self.reveal_pending_comment();
+            }
+            AppEvent::CommentParentLoaded { task, parent } => {
+                if !self.tasks.finish(task) {
+                    return;
+                }
+                let TaskTarget::CommentParent(comment_id) = task.target() else {
+                    unreachable!("comment-parent event has a non-parent target");
+                };
+                self.note_comment_parent(comment_id, parent);
             }
-            AppEvent::SearchResultsLoaded { task, stories } => {
+            AppEvent::SearchResultsLoaded { task, mode, page } => {
                 if !self.tasks.finish(task) {
                     return;
                 }
                 assert_eq!(task.target(), TaskTarget::Search);
-                self.story_loading = false;
-                self.last_error = None;
-                self.stories = stories;
-                self.story_ids = self.stories.iter().map(|story| story.id).collect();
-                self.has_more_stories = false;
-                self.story_list_state.select(Some(0));
-                *self.story_list_state.offset_mut() = 0;
+                self.note_search_page_loaded(mode, page.has_more);
+                match mode {
+                    StoriesLoadMode::Replace => {
+                        self.comment_hits.clear();
+                        self.stories = page.hits;
+                        self.story_ids = self.stories.iter().map(|story| story.id).collect();
+                        self.story_list_state.select(Some(0));
+                        *self.story_list_state.offset_mut() = 0;
+                    }
+                    StoriesLoadMode::Append => {
+                        // Date-sorted pages shift as new stories land, so a
+                        // hit can repeat on the next page.
+                        for story in page.hits {
+                            if !self.story_ids.contains(&story.id) {
+                                self.story_ids.push(story.id);
+                                self.stories.push(story);
+                            }
+                        }
+                    }
+                }
                 self.recompute_visible_stories();
+                self.ensure_selected_story_visible();
+            }
+            AppEvent::CommentSearchResultsLoaded { task, mode, page } => {
+                if !self.tasks.finish(task) {
+                    return;
+                }
+                assert_eq!(task.target(), TaskTarget::Search);
+                self.handle_comment_search_results(mode, page);
+            }
+            AppEvent::UserLoaded { task, profile } => {
+                if !self.tasks.finish(task) {
+                    return;
+                }
+                assert_eq!(task.target(), TaskTarget::User);
+                self.handle_user_loaded(profile);
+            }
+            AppEvent::WatchPolled {
+                task,
+                story_id,
+                thread,
+            } => {
+                if !self.tasks.finish(task) {
+                    return;
+                }
+                assert_eq!(task.target(), TaskTarget::WatchPoll);
+                self.handle_watch_polled(story_id, thread);
+            }
+            AppEvent::WatchChildrenPolled { task, children } => {
+                if !self.tasks.finish(task) {
+                    return;
+                }
+                let TaskTarget::CommentChildren(parent_id) = task.target() else {
+                    unreachable!("watch-children event has a non-children target");
+                };
+                self.handle_watch_children_polled(parent_id, children);
             }
             AppEvent::ArticleLoaded {
                 task,
@@ -63,12 +128,22 @@ impl App {
                 assert_eq!(task.target(), TaskTarget::Article(story_id));
                 self.deliver_article(story_id, Ok(None));
             }
+            AppEvent::StoryDownloaded { task, complete } => {
+                if !self.tasks.is_current(task) {
+                    return;
+                }
+                self.handle_story_downloaded(complete);
+            }
             AppEvent::Summary { task, event } => {
                 if !self.tasks.is_current(task) {
                     return;
                 }
                 assert_eq!(task.target(), TaskTarget::Summary);
+                let complete = event == SummaryEvent::Complete;
                 self.summary_overlay.handle_event(event);
+                if complete {
+                    self.cache_completed_summary();
+                }
             }
             AppEvent::SettingsSaved { task, config } => {
                 if !self.tasks.finish(task) {
@@ -77,7 +152,7 @@ impl App {
                 assert_eq!(task.target(), TaskTarget::SettingsSave);
                 self.summarizer
                     .update_config(config.summarize().cloned(), config.api_key_override());
-                self.config = config;
+                self.config = *config;
                 if let Some(popup) = self.settings_popup.as_mut() {
                     popup.mark_saved();
                     popup.api_key_status = self.config.effective_api_key().status();
@@ -88,7 +163,9 @@ impl App {
                 self.handle_connection_test_finished(task, result);
             }
             AppEvent::TaskCompleted { task } => {
-                self.tasks.finish(task);
+                if self.tasks.finish(task) && task.target() == TaskTarget::OfflineDownload {
+                    self.finish_offline_download();
+                }
             }
             AppEvent::TaskFailed { task, message } => {
                 self.handle_task_failure(task, message);
@@ -172,6 +249,7 @@ impl App {
                     .clone()
                     .expect("current story present for foreground comments");
                 self.apply_comments_for_story(story, thread, false);
+                self.reveal_pending_comment();
                 self.maybe_start_pending_summary(story_id);
             }
             CommentLoadKind::Prefetch => {
@@ -193,6 +271,10 @@ impl App {
                 self.story_loading = false;
                 self.last_error = Some(message);
             }
+            TaskTarget::User => {
+                self.user_loading = false;
+                self.last_error = Some(message);
+            }
             TaskTarget::CommentRoots(story_id) => {
                 if self
                     .current_story
@@ -213,7 +295,18 @@ impl App {
                     return;
                 }
                 self.last_error = Some(message);
-                self.rebuild_comment_list(Some(parent_id));
+                if self.is_fold_load(parent_id) {
+                    self.finish_fold_load(parent_id, false);
+                } else {
+                    self.rebuild_comment_list(Some(parent_id));
+                }
+            }
+            TaskTarget::CommentParent(_) => {
+                self.pending_comment_focus = None;
+                self.last_error = Some(message);
+            }
+            TaskTarget::WatchPoll => {
+                self.last_error = Some(format!("watch: {message}"));
             }
             TaskTarget::Article(story_id) => self.deliver_article(story_id, Err(message)),
             TaskTarget::Summary => self.summary_overlay.fail(message),
@@ -226,6 +319,19 @@ impl App {
             TaskTarget::StoryStateSave => {
                 logging::log_error(format!("failed to save story state: {message}"));
             }
+            TaskTarget::BookmarksSave => {
+                self.last_error = Some(format!("bookmarks: {message}"));
+            }
+            TaskTarget::SummaryCacheSave => {
+                logging::log_error(format!("failed to save summary cache: {message}"));
+            }
+            TaskTarget::MuteSave => {
+                self.last_error = Some(format!("mute: {message}"));
+            }
+            TaskTarget::OfflineDownload => {
+                self.offline_download = None;
+                self.last_error = Some(format!("offline download: {message}"));
+            }
         }
     }
 }
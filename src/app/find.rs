@@ -0,0 +1,118 @@
+use super::comment_tree::{ancestors_of, comment_ids, find_comments, set_collapse};
+use super::App;
+use crate::input::TextAction;
+
+/// `/` in the comments view: a query over the loaded thread's text and
+/// authors. Matches are highlighted where they show, and `n`/`N` step
+/// through them, expanding whatever hides the next one.
+#[derive(Debug, Default)]
+pub struct CommentFind {
+    pub query: String,
+    /// Matching comment ids in display order, collapsed ones included.
+    pub matches: Vec<u64>,
+    /// The match last jumped to, for the `3/7` count.
+    pub current: Option<u64>,
+}
+
+impl CommentFind {
+    /// 1-based position of the current match, for the footer.
+    pub fn position(&self) -> Option<usize> {
+        let current = self.current?;
+        self.matches
+            .iter()
+            .position(|&id| id == current)
+            .map(|index| index + 1)
+    }
+}
+
+impl App {
+    pub(super) fn start_comment_find(&mut self) {
+        if self.comment_tree.is_empty() {
+            return;
+        }
+        self.find_input_active = true;
+        self.comment_find = Some(CommentFind::default());
+    }
+
+    pub(super) fn handle_find_input_action(&mut self, action: TextAction) {
+        assert!(self.find_input_active, "find action outside text input");
+        let find = self.comment_find.as_mut().expect("find input has a query");
+        match action {
+            TextAction::Submit => {
+                self.find_input_active = false;
+                if find.query.is_empty() {
+                    self.comment_find = None;
+                    return;
+                }
+                self.step_comment_find(true);
+            }
+            TextAction::Cancel => {
+                self.find_input_active = false;
+                self.comment_find = None;
+            }
+            TextAction::DeleteBackward => {
+                find.query.pop();
+                find.matches = find_comments(&self.comment_tree, &find.query);
+            }
+            TextAction::Insert(character) => {
+                find.query.push(character);
+                find.matches = find_comments(&self.comment_tree, &find.query);
+            }
+            _ => unreachable!("unsupported find text action: {action:?}"),
+        }
+    }
+
+    /// Jump to the next match after the selection, or the previous one
+    /// before it, wrapping at the ends of the thread. Matches are found
+    /// again each time, so replies loaded since the query still count.
+    pub(super) fn step_comment_find(&mut self, forward: bool) {
+        let Some(find) = self.comment_find.as_mut() else {
+            return;
+        };
+        find.matches = find_comments(&self.comment_tree, &find.query);
+        if find.matches.is_empty() {
+            find.current = None;
+            self.last_error = Some(format!("no comments match {:?}", find.query));
+            return;
+        }
+
+        let order = comment_ids(&self.comment_tree);
+        let position_of = |id: u64| order.iter().position(|&other| other == id);
+        let selected = self
+            .comment_list_state
+            .selected()
+            .and_then(|selected| self.comment_list.get(selected))
+            .and_then(|comment| position_of(comment.id));
+        let target = match (forward, selected) {
+            (true, Some(selected)) => find
+                .matches
+                .iter()
+                .copied()
+                .find(|&id| position_of(id).is_some_and(|position| position > selected)),
+            (false, Some(selected)) => find
+                .matches
+                .iter()
+                .copied()
+                .rev()
+                .find(|&id| position_of(id).is_some_and(|position| position < selected)),
+            (_, None) => None,
+        };
+        let target = target.unwrap_or(if forward {
+            find.matches[0]
+        } else {
+            find.matches[find.matches.len() - 1]
+        });
+        find.current = Some(target);
+        self.reveal_comment(target);
+    }
+
+    /// Expand the path down to `id` and select it.
+    pub(super) fn reveal_comment(&mut self, id: u64) {
+        for ancestor in ancestors_of(&self.comment_tree, id).unwrap_or_default() {
+            set_collapse(&mut self.comment_tree, ancestor, false)
+                .expect("ancestor present in the tree");
+        }
+        self.rebuild_comment_list(Some(id));
+        self.ensure_selected_comment_visible();
+    }
+}
@@ -0,0 +1,56 @@
+use super::tests::{app_with_source, comment, key, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::input::Action;
+use crossterm::event::KeyCode;
+
+fn with_text(id: u64, text: &str) -> CommentNode {
+    let mut node = comment(id);
+    node.comment.text = text.to_string();
+    node
+}
+
+fn type_text(app: &mut App, text: &str) {
+    for character in text.chars() {
+        app.handle_key(key(KeyCode::Char(character)));
+    }
+}
+
+#[test]
+fn find_expands_to_hidden_matches_and_cycles_with_n() {
+    let (mut app, _rx) = app_with_source(InMemorySource::default());
+    let mut reply = with_text(11, "a <i>Borrow</i> checker story");
+    reply.comment.depth = 1;
+    let mut root = with_text(10, "hello");
+    root.comment.kids = vec![11];
+    root.comment.collapsed = true;
+    root.children = vec![reply];
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![root, with_text(20, "borrowed time")]),
+        true,
+    );
+    app.handle_action(Action::CollapseAll);
+    assert_eq!(app.comment_list.len(), 2);
+
+    app.handle_key(key(KeyCode::Char('/')));
+    assert!(app.find_input_active);
+    type_text(&mut app, "borrow");
+    assert_eq!(app.comment_find.as_ref().unwrap().matches, vec![11, 20]);
+
+    app.handle_key(key(KeyCode::Enter));
+    assert!(!app.find_input_active);
+    assert_eq!(app.selected_comment_id(), Some(11));
+    assert_eq!(app.comment_find.as_ref().unwrap().position(), Some(1));
+
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.selected_comment_id(), Some(20));
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.selected_comment_id(), Some(11));
+    app.handle_key(key(KeyCode::Char('N')));
+    assert_eq!(app.selected_comment_id(), Some(20));
+
+    app.handle_key(key(KeyCode::Esc));
+    assert!(app.comment_find.is_none());
+    assert_eq!(app.view, View::Comments);
+}
@@ -0,0 +1,125 @@
+use super::comment_tree::{ancestors_of, find_node_mut, show_levels};
+use super::App;
+use std::collections::{HashSet, VecDeque};
+
+/// Reply fetches a fold keeps in flight. Each is one `comment_children`
+/// batch, which the client fans out under its own concurrency cap.
+const FOLD_LOADS_IN_FLIGHT: usize = 4;
+
+/// Replies being fetched so the thread can show the levels picked with `z`,
+/// drawn as a bar in the comments footer. Loaded replies can reveal more
+/// unloaded ones, so `total` grows as the fold walks down.
+#[derive(Debug, Default)]
+pub struct CommentFold {
+    pub total: usize,
+    pub done: usize,
+    queued: VecDeque<u64>,
+    in_flight: HashSet<u64>,
+}
+
+impl App {
+    /// `zM`, `zR`, `z1`…`z9`: fold the open thread to `levels` levels, then
+    /// fetch the replies the backend left out (Firebase stops a few levels
+    /// down). The selection stays put, or moves up to the nearest comment
+    /// still shown.
+    pub(super) fn show_comment_levels(&mut self, levels: usize) {
+        if self.comment_tree.is_empty() {
+            return;
+        }
+        let selected_id = self.selected_comment_id();
+        self.comment_levels = Some(levels);
+        if let Some(fold) = self.comment_fold.as_mut() {
+            fold.queued.clear();
+            fold.total = fold.in_flight.len();
+            fold.done = 0;
+        }
+        let unloaded = show_levels(&mut self.comment_tree, levels);
+        self.queue_comment_fold(unloaded);
+        self.rebuild_keeping_selection(selected_id);
+    }
+
+    /// Add `unloaded` to the fold, starting one if needed, and top up the
+    /// fetches in flight. A fold with nothing left to do ends here.
+    pub(super) fn queue_comment_fold(&mut self, unloaded: Vec<u64>) {
+        let fold = self.comment_fold.get_or_insert_with(CommentFold::default);
+        fold.total += unloaded.len();
+        fold.queued.extend(unloaded);
+        self.pump_comment_fold();
+    }
+
+    pub(super) fn is_fold_load(&self, parent_id: u64) -> bool {
+        self.comment_fold
+            .as_ref()
+            .is_some_and(|fold| fold.in_flight.contains(&parent_id))
+    }
+
+    /// A fold's fetch under `parent_id` settled. Loaded replies are folded to
+    /// the same levels, and any they leave unloaded join the queue.
+    pub(super) fn finish_fold_load(&mut self, parent_id: u64, loaded: bool) {
+        let selected_id = self.selected_comment_id();
+        let Some(fold) = self.comment_fold.as_mut() else {
+            return;
+        };
+        fold.in_flight.remove(&parent_id);
+        fold.done += 1;
+
+        let levels = self.visible_comment_levels();
+        let unloaded = match find_node_mut(&mut self.comment_tree, parent_id) {
+            Some(parent) if loaded => show_levels(&mut parent.children, levels),
+            _ => Vec::new(),
+        };
+        self.queue_comment_fold(unloaded);
+        self.rebuild_keeping_selection(selected_id);
+    }
+
+    fn pump_comment_fold(&mut self) {
+        loop {
+            let Some(fold) = self.comment_fold.as_mut() else {
+                return;
+            };
+            if fold.in_flight.len() >= FOLD_LOADS_IN_FLIGHT {
+                return;
+            }
+            let Some(parent_id) = fold.queued.pop_front() else {
+                if fold.in_flight.is_empty() {
+                    self.comment_fold = None;
+                }
+                return;
+            };
+            if self.spawn_comment_children(parent_id) {
+                let fold = self.comment_fold.as_mut().expect("fold still running");
+                fold.in_flight.insert(parent_id);
+            } else {
+                let fold = self.comment_fold.as_mut().expect("fold still running");
+                fold.done += 1;
+            }
+        }
+    }
+
+    pub(super) fn selected_comment_id(&self) -> Option<u64> {
+        self.comment_list_state
+            .selected()
+            .and_then(|selected| self.comment_list.get(selected))
+            .map(|comment| comment.id)
+    }
+
+    /// Rebuild the list, keeping `selected_id` selected or, once folded
+    /// away, the closest ancestor still shown.
+    pub(super) fn rebuild_keeping_selection(&mut self, selected_id: Option<u64>) {
+        self.rebuild_comment_list(None);
+        let Some(id) = selected_id else {
+            return;
+        };
+        let mut path = ancestors_of(&self.comment_tree, id).unwrap_or_default();
+        path.push(id);
+        let index = path.iter().rev().find_map(|&id| {
+            self.comment_list
+                .iter()
+                .position(|comment| comment.id == id)
+        });
+        if let Some(index) = index {
+            self.comment_list_state.select(Some(index));
+        }
+        self.ensure_selected_comment_visible();
+    }
+}
@@ -0,0 +1,72 @@
+use super::tests::{app_with_source, comment, comment_ids, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::input::Action;
+
+fn reply(id: u64, depth: usize, kids: &[u64]) -> CommentNode {
+    let mut node = comment(id);
+    node.comment.depth = depth;
+    node.comment.kids = kids.to_vec();
+    node.comment.collapsed = !kids.is_empty();
+    node
+}
+
+#[test]
+fn level_folds_apply_to_the_whole_thread_and_keep_the_selection_in_view() {
+    let (mut app, _rx) = app_with_source(InMemorySource::default());
+    let mut child = reply(11, 1, &[12]);
+    child.children = vec![reply(12, 2, &[])];
+    let mut root = reply(10, 0, &[11]);
+    root.children = vec![child];
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![root, comment(20)]),
+        true,
+    );
+    assert_eq!(comment_ids(&app), vec![10, 11, 20]);
+
+    app.handle_action(Action::ExpandAll);
+    assert_eq!(comment_ids(&app), vec![10, 11, 12, 20]);
+
+    app.handle_action(Action::SelectComment(2));
+    app.handle_action(Action::CollapseAll);
+    assert_eq!(comment_ids(&app), vec![10, 20]);
+    assert_eq!(app.selected_comment_id(), Some(10));
+
+    app.handle_action(Action::ShowLevels(2));
+    assert_eq!(comment_ids(&app), vec![10, 11, 20]);
+    assert!(app.comment_fold.is_none());
+}
+
+#[tokio::test]
+async fn expanding_past_the_loaded_levels_fetches_replies_level_by_level() {
+    let mut root = reply(10, 0, &[11]);
+    root.comment.children_loaded = false;
+    let mut child = reply(11, 1, &[12]);
+    child.comment.children_loaded = false;
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(1, vec![root])
+            .with_children(vec![child, reply(12, 2, &[])]),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+
+    app.handle_action(Action::ExpandAll);
+    let fold = app.comment_fold.as_ref().expect("replies are loading");
+    assert_eq!((fold.done, fold.total), (0, 1));
+
+    app.handle_app_event(rx.recv().await.expect("first level"));
+    let fold = app
+        .comment_fold
+        .as_ref()
+        .expect("the next level is loading");
+    assert_eq!((fold.done, fold.total), (1, 2));
+
+    app.handle_app_event(rx.recv().await.expect("second level"));
+    assert!(app.comment_fold.is_none());
+    assert_eq!(comment_ids(&app), vec![10, 11, 12]);
+    assert_eq!(app.selected_comment_id(), Some(10));
+}
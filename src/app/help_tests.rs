@@ -10,7 +10,7 @@ use std::sync::Arc;
 
 fn test_app() -> App {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, _rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -1,4 +1,6 @@
+use super::comment_tree::set_collapse;
 use super::App;
+use crate::api::types::Comment;
 use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 use std::cmp;
@@ -34,6 +36,83 @@ impl App {
         self.comment_list_state
             .select(Some(self.comment_layout.page_up(selected)));
     }
+
+    /// Stays put when there is nowhere to go, e.g. no next sibling.
+    pub(super) fn move_in_thread(&mut self, motion: ThreadMotion) {
+        let Some(selected) = self.comment_list_state.selected() else {
+            return;
+        };
+        if let Some(target) = thread_motion_target(&self.comment_list, selected, motion) {
+            self.comment_list_state.select(Some(target));
+            self.ensure_selected_comment_visible();
+        }
+    }
+
+    /// Fold the top-level thread holding the selection and land on its root.
+    pub(super) fn collapse_selected_thread(&mut self) {
+        let Some(selected) = self.comment_list_state.selected() else {
+            return;
+        };
+        let Some(comment) = self.comment_list.get(selected) else {
+            return;
+        };
+        let root = if comment.depth == 0 {
+            Some(selected)
+        } else {
+            thread_motion_target(&self.comment_list, selected, ThreadMotion::PreviousRoot)
+        };
+        let Some(root) = root else {
+            self.last_error = Some(format!(
+                "thread root not found for comment id={}",
+                comment.id
+            ));
+            return;
+        };
+        let root = &self.comment_list[root];
+        let id = root.id;
+        if !root.kids.is_empty() && set_collapse(&mut self.comment_tree, id, true).is_none() {
+            self.last_error = Some(format!("comment not found id={id}"));
+            return;
+        }
+        self.rebuild_comment_list(Some(id));
+        self.ensure_selected_comment_visible();
+    }
+}
+
+/// Tree-shaped moves through the visible comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ThreadMotion {
+    Parent,
+    NextSibling,
+    PreviousSibling,
+    NextRoot,
+    /// The root of the current thread, or the one before when already there.
+    PreviousRoot,
+}
+
+/// Index `motion` lands on from `selected`. The list is the tree in
+/// display order, so a comment's parent is the nearest shallower comment
+/// above it, and its siblings are the same-depth comments reachable without
+/// passing a shallower one.
+pub(crate) fn thread_motion_target(
+    comments: &[Comment],
+    selected: usize,
+    motion: ThreadMotion,
+) -> Option<usize> {
+    let depth = comments.get(selected)?.depth;
+    let before = || (0..selected).rev();
+    let after = || selected + 1..comments.len();
+    match motion {
+        ThreadMotion::Parent => before().find(|&index| comments[index].depth < depth),
+        ThreadMotion::NextSibling => after()
+            .find(|&index| comments[index].depth <= depth)
+            .filter(|&index| comments[index].depth == depth),
+        ThreadMotion::PreviousSibling => before()
+            .find(|&index| comments[index].depth <= depth)
+            .filter(|&index| comments[index].depth == depth),
+        ThreadMotion::NextRoot => after().find(|&index| comments[index].depth == 0),
+        ThreadMotion::PreviousRoot => before().find(|&index| comments[index].depth == 0),
+    }
 }
 
 pub(crate) fn move_selection_down(state: &mut ListState, len: usize) {
@@ -118,6 +197,43 @@ mod tests {
         assert_eq!(state.offset(), 2);
     }
 
+    #[test]
+    fn thread_motions_follow_depth_in_display_order() {
+        // 0: 10        4: 20
+        // 1:   11      5:   21
+        // 2:     12
+        // 3:   13
+        let comments: Vec<Comment> = [(10, 0), (11, 1), (12, 2), (13, 1), (20, 0), (21, 1)]
+            .into_iter()
+            .map(|(id, depth)| Comment {
+                id,
+                by: None,
+                time: None,
+                text: String::new(),
+                kids: vec![],
+                depth,
+                collapsed: false,
+                children_loaded: true,
+                children_loading: false,
+                muted: false,
+            })
+            .collect();
+        let target = |selected, motion| thread_motion_target(&comments, selected, motion);
+
+        assert_eq!(target(2, ThreadMotion::Parent), Some(1));
+        assert_eq!(target(3, ThreadMotion::Parent), Some(0));
+        assert_eq!(target(0, ThreadMotion::Parent), None);
+        assert_eq!(target(1, ThreadMotion::NextSibling), Some(3));
+        assert_eq!(target(3, ThreadMotion::NextSibling), None);
+        assert_eq!(target(3, ThreadMotion::PreviousSibling), Some(1));
+        assert_eq!(target(5, ThreadMotion::PreviousSibling), None);
+        assert_eq!(target(0, ThreadMotion::NextSibling), Some(4));
+        assert_eq!(target(2, ThreadMotion::NextRoot), Some(4));
+        assert_eq!(target(5, ThreadMotion::NextRoot), None);
+        assert_eq!(target(5, ThreadMotion::PreviousRoot), Some(4));
+        assert_eq!(target(4, ThreadMotion::PreviousRoot), Some(0));
+    }
+
     #[test]
     fn page_up_clamps_to_first_item() {
         let mut state = ListState::default();
@@ -1,8 +1,8 @@
 use super::list_nav::rect_contains;
-use super::{App, View};
-use crate::api::FeedKind;
+use super::{App, FeedRow, View};
 use crate::input::{
-    Action, ArticleAction, FeedFilterAction, HelpAction, InputLayer, SettingsAction, SummaryAction,
+    Action, ArticleAction, FeedFilterAction, HelpAction, InputLayer, ProfilePickerAction,
+    SettingsAction, SummaryAction,
 };
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 
@@ -22,7 +22,7 @@ impl App {
                 MouseEventKind::Down(MouseButton::Left) => Action::Help(HelpAction::Dismiss),
                 _ => Action::Noop,
             },
-            InputLayer::Summary => {
+            InputLayer::Summary | InputLayer::SummaryChat => {
                 let popup = crate::ui::summary_overlay::popup_rect(self.layout_areas.frame_area);
                 match mouse.kind {
                     MouseEventKind::ScrollDown => Action::Summary(SummaryAction::ScrollDown(3)),
@@ -35,7 +35,8 @@ impl App {
                     _ => Action::Noop,
                 }
             }
-            InputLayer::Article => {
+            InputLayer::ProfilePicker => self.profile_picker_mouse_action(mouse),
+            InputLayer::Article | InputLayer::ArticleFind => {
                 let popup = crate::ui::article_overlay::popup_rect(self.layout_areas.frame_area);
                 match mouse.kind {
                     MouseEventKind::ScrollDown => Action::Article(ArticleAction::ScrollDown(3)),
@@ -61,11 +62,13 @@ impl App {
                 }
             }
             InputLayer::FeedFilter => self.feed_filter_mouse_action(mouse),
-            InputLayer::FilterText | InputLayer::SearchText => match mouse.kind {
-                MouseEventKind::ScrollDown => Action::MoveDown,
-                MouseEventKind::ScrollUp => Action::MoveUp,
-                _ => Action::Noop,
-            },
+            InputLayer::FilterText | InputLayer::SearchText | InputLayer::FindText => {
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => Action::MoveDown,
+                    MouseEventKind::ScrollUp => Action::MoveUp,
+                    _ => Action::Noop,
+                }
+            }
             InputLayer::View => self.view_mouse_action(mouse),
         }
     }
@@ -83,8 +86,7 @@ impl App {
                     return Action::FeedFilter(FeedFilterAction::Dismiss);
                 }
                 let item_start_y = popup.y + 3;
-                if mouse.row >= item_start_y
-                    && mouse.row < item_start_y + FeedKind::ALL.len() as u16
+                if mouse.row >= item_start_y && mouse.row < item_start_y + FeedRow::ALL.len() as u16
                 {
                     return Action::FeedFilter(FeedFilterAction::SelectIndex(
                         (mouse.row - item_start_y) as usize,
@@ -96,6 +98,36 @@ impl App {
         }
     }
 
+    fn profile_picker_mouse_action(&self, mouse: MouseEvent) -> Action {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => Action::ProfilePicker(ProfilePickerAction::MoveDown),
+            MouseEventKind::ScrollUp => Action::ProfilePicker(ProfilePickerAction::MoveUp),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(picker) = self.summary_overlay.profile_picker() else {
+                    return Action::Noop;
+                };
+                let rows = picker.rows().len();
+                let Some(popup) = crate::ui::summary_overlay::profile_picker_rect(
+                    self.layout_areas.frame_area,
+                    rows,
+                ) else {
+                    return Action::Noop;
+                };
+                if !rect_contains(popup, mouse.column, mouse.row) {
+                    return Action::ProfilePicker(ProfilePickerAction::Dismiss);
+                }
+                let item_start_y = popup.y + 3;
+                if mouse.row >= item_start_y && mouse.row < item_start_y + rows as u16 {
+                    return Action::ProfilePicker(ProfilePickerAction::SelectIndex(
+                        (mouse.row - item_start_y) as usize,
+                    ));
+                }
+                Action::Noop
+            }
+            _ => Action::Noop,
+        }
+    }
+
     fn view_mouse_action(&self, mouse: MouseEvent) -> Action {
         match mouse.kind {
             MouseEventKind::ScrollDown => return Action::MoveDown,
@@ -139,6 +171,20 @@ impl App {
                     Action::SelectComment(index)
                 }
             }
+            View::User => {
+                if !rect_contains(list_area, mouse.column, mouse.row) {
+                    return Action::Noop;
+                }
+                let index = self.user_list_state.offset() + (mouse.row - list_area.y) as usize;
+                if index >= self.user_row_count() {
+                    return Action::Noop;
+                }
+                if self.user_list_state.selected().unwrap_or(0) == index {
+                    Action::Enter
+                } else {
+                    Action::SelectUserRow(index)
+                }
+            }
         }
     }
 }
@@ -0,0 +1,139 @@
+use super::comment_tree::mark_muted_comments;
+use super::story_filter::{in_domain, story_domain};
+use super::{App, AppEvent, TaskTarget, View};
+use crate::api::Story;
+use crate::config::MuteConfig;
+use regex::{Regex, RegexBuilder};
+use std::time::Instant;
+
+/// The `[mute]` killfile, compiled for testing stories and comment authors.
+#[derive(Debug, Default)]
+pub struct MuteList {
+    config: MuteConfig,
+    keywords: Vec<Regex>,
+    /// Why some of `[mute] keywords` mute nothing: they are not valid regexes.
+    pub error: Option<String>,
+}
+
+impl MuteList {
+    pub fn new(mut config: MuteConfig) -> Self {
+        for domain in &mut config.domains {
+            *domain = domain.trim_start_matches("www.").to_lowercase();
+        }
+        let mut keywords = Vec::new();
+        let mut invalid = Vec::new();
+        for keyword in &config.keywords {
+            match RegexBuilder::new(keyword).case_insensitive(true).build() {
+                Ok(regex) => keywords.push(regex),
+                Err(_) => invalid.push(format!("{keyword:?}")),
+            }
+        }
+        let error = (!invalid.is_empty()).then(|| {
+            format!(
+                "config: [mute] keywords: not a valid regex: {}",
+                invalid.join(", ")
+            )
+        });
+        Self {
+            config,
+            keywords,
+            error,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.config == MuteConfig::default()
+    }
+
+    pub fn hides_story(&self, story: &Story) -> bool {
+        self.config
+            .domains
+            .iter()
+            .any(|domain| in_domain(story, domain))
+            || self.hides_user(&story.by)
+            || self
+                .keywords
+                .iter()
+                .any(|keyword| keyword.is_match(&story.title))
+    }
+
+    pub fn hides_user(&self, user: &str) -> bool {
+        self.config
+            .users
+            .iter()
+            .any(|muted| muted.eq_ignore_ascii_case(user))
+    }
+}
+
+impl App {
+    /// `m d`: mute the domain of the story on screen.
+    pub(super) fn mute_selected_domain(&mut self) {
+        let Some(story) = self.story_in_view() else {
+            return;
+        };
+        let domain = story_domain(&story);
+        if domain == "self" {
+            self.last_error = Some("self-posts have no domain to mute".to_string());
+            return;
+        }
+        let mut config = self.mute.config.clone();
+        if !config.domains.contains(&domain) {
+            config.domains.push(domain.clone());
+        }
+        self.apply_mute(config, domain);
+    }
+
+    /// `m a`: mute the selected story's submitter, or in a thread the
+    /// selected comment's author.
+    pub(super) fn mute_selected_author(&mut self) {
+        let author = match self.view {
+            View::Stories => self
+                .selected_comment_hit()
+                .map(|hit| hit.by.clone())
+                .or_else(|| self.selected_story().map(|story| story.by.clone())),
+            View::Comments => self
+                .comment_list_state
+                .selected()
+                .and_then(|selected| self.comment_list.get(selected))
+                .and_then(|comment| comment.by.clone()),
+            View::User => None,
+        };
+        let Some(author) = author.filter(|author| !author.is_empty()) else {
+            return;
+        };
+        let mut config = self.mute.config.clone();
+        if !self.mute.hides_user(&author) {
+            config.users.push(author.clone());
+        }
+        self.apply_mute(config, author);
+    }
+
+    /// Hide what the new list mutes right away, confirm `muted`, then write
+    /// it to the config file in the background.
+    fn apply_mute(&mut self, config: MuteConfig, muted: String) {
+        self.config = self.config.with_mute(config.clone());
+        self.mute = MuteList::new(config);
+        self.last_error = self.mute.error.clone();
+        self.muted_flash = Some((muted, Instant::now()));
+        self.recompute_visible_stories();
+        if self.view == View::Comments {
+            self.rebuild_keeping_selection(self.selected_comment_id());
+        }
+
+        let config = self.config.clone();
+        self.tasks.spawn(
+            TaskTarget::MuteSave,
+            async move { config.write().await },
+            |task, ()| AppEvent::TaskCompleted { task },
+        );
+    }
+
+    /// Fold newly listed comments by muted users to a stub.
+    pub(super) fn mark_muted_comments(&mut self) {
+        if self.mute.config.users.is_empty() {
+            return;
+        }
+        let mute = &self.mute;
+        mark_muted_comments(&mut self.comment_tree, &|user| mute.hides_user(user));
+    }
+}
@@ -0,0 +1,141 @@
+use super::tests::{app_with_parts, comment, story, visible_ids};
+use super::*;
+use crate::api::InMemorySource;
+use crate::config::Config;
+use crate::input::Action;
+use crate::summarizer::Summarizer;
+use std::path::Path;
+
+fn muted_app(
+    source: InMemorySource,
+    path: &Path,
+    config_toml: &str,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    std::fs::write(path, config_toml).expect("write config");
+    let config = Config::load(Some(path)).expect("load config");
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    app_with_parts(source, config, None, summarizer)
+}
+
+fn linked(id: u64, by: &str, title: &str, url: &str) -> Story {
+    Story {
+        by: by.to_string(),
+        title: title.to_string(),
+        url: Some(url.to_string()),
+        ..story(id)
+    }
+}
+
+#[tokio::test]
+async fn muted_domains_authors_and_keywords_hide_stories_from_the_feed() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (mut app, mut rx) = muted_app(
+        InMemorySource::new(vec![
+            linked(1, "alice", "Rust 2.0", "https://blog.example.com/rust"),
+            linked(2, "spammer", "Great deals", "https://shop.test/deals"),
+            linked(3, "carol", "Crypto moon", "https://news.test/c"),
+            linked(4, "dave", "Terminal tricks", "https://news.test/t"),
+        ]),
+        &dir.path().join("config.toml"),
+        "[mute]\ndomains = [\"www.Example.com\"]\nusers = [\"Spammer\"]\nkeywords = [\"crypto\"]\n",
+    );
+
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    assert_eq!(visible_ids(&mut app), vec![4]);
+}
+
+#[tokio::test]
+async fn muting_the_selected_author_hides_their_stories_and_saves_the_config() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("config.toml");
+    let (mut app, mut rx) = muted_app(
+        InMemorySource::new(vec![
+            linked(1, "alice", "One", "https://a.test/1"),
+            linked(2, "bob", "Two", "https://b.test/2"),
+            linked(3, "alice", "Three", "https://c.test/3"),
+        ]),
+        &path,
+        "",
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.story_list_state.select(Some(0));
+    app.last_error = Some("an earlier failure".to_string());
+
+    app.handle_action(Action::MuteAuthor);
+    assert_eq!(visible_ids(&mut app), vec![2]);
+    assert_eq!(app.last_error, None);
+    assert_eq!(
+        app.muted_flash.as_ref().map(|(muted, _)| muted.as_str()),
+        Some("alice")
+    );
+
+    app.story_list_state.select(Some(0));
+    app.handle_action(Action::MuteDomain);
+    assert_eq!(visible_ids(&mut app), Vec::<u64>::new());
+    assert_eq!(
+        app.muted_flash.as_ref().map(|(muted, _)| muted.as_str()),
+        Some("b.test")
+    );
+
+    while app.tasks.is_running(TaskTarget::MuteSave) {
+        app.handle_app_event(rx.recv().await.expect("save event"));
+    }
+    assert_eq!(app.last_error, None);
+    let saved = Config::load(Some(&path)).expect("reload config");
+    let mute = saved.mute().expect("saved [mute]");
+    assert_eq!(mute.users, vec!["alice"]);
+    assert_eq!(mute.domains, vec!["b.test"]);
+}
+
+#[tokio::test]
+async fn an_invalid_keyword_is_reported_and_the_valid_ones_still_mute() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (mut app, mut rx) = muted_app(
+        InMemorySource::new(vec![
+            linked(1, "alice", "Crypto moon", "https://a.test/1"),
+            linked(2, "bob", "(Rust", "https://b.test/2"),
+        ]),
+        &dir.path().join("config.toml"),
+        "[mute]\nkeywords = [\"crypto\", \"(rust\"]\n",
+    );
+
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("config: [mute] keywords: not a valid regex: \"(rust\"")
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    assert_eq!(visible_ids(&mut app), vec![2]);
+}
+
+#[test]
+fn comments_by_muted_users_fold_to_a_stub_that_can_be_opened() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (mut app, _rx) = muted_app(
+        InMemorySource::default(),
+        &dir.path().join("config.toml"),
+        "[mute]\nusers = [\"bob\"]\n",
+    );
+    let mut reply = comment(11);
+    reply.comment.by = Some("carol".to_string());
+    reply.comment.depth = 1;
+    let mut root = comment(10);
+    root.comment.kids = vec![11];
+    root.children = vec![reply];
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![root, comment(20)]),
+        true,
+    );
+
+    let ids: Vec<u64> = app.comment_list.iter().map(|comment| comment.id).collect();
+    assert_eq!(ids, vec![10, 20]);
+    assert!(app.comment_list.iter().all(|comment| comment.muted));
+
+    app.handle_action(Action::SelectComment(1));
+    app.handle_action(Action::ToggleCollapse);
+    assert!(!app.comment_list[1].collapsed);
+}
@@ -14,6 +14,10 @@ struct PrefetchCandidate {
 impl App {
     pub fn maybe_prefetch_stories(&mut self) {
         if self.search_active {
+            self.maybe_load_more_search_results();
+            return;
+        }
+        if self.saved_feed_active {
             return;
         }
         if self.story_loading || self.is_story_prefetch_in_flight() || !self.has_more_stories {
@@ -23,12 +27,9 @@ impl App {
             return;
         }
 
-        let selected = self.story_list_state.selected().unwrap_or(0);
         let loaded = self.stories.len();
         let should_fill_viewport = loaded < self.story_page_size;
-        let should_prefetch =
-            should_fill_viewport || selected.saturating_mul(10) >= loaded.saturating_mul(8);
-        if !should_prefetch {
+        if !should_fill_viewport && !self.is_selection_near_list_end() {
             return;
         }
 
@@ -52,6 +53,16 @@ impl App {
         );
     }
 
+    /// Whether the selection is in the last fifth of the rows on screen.
+    /// The selection indexes what filters and mutes leave visible, so that
+    /// is what it is measured against: a filtered feed pages once its last
+    /// matches come into view.
+    pub(super) fn is_selection_near_list_end(&self) -> bool {
+        let selected = self.story_list_state.selected().unwrap_or(0);
+        selected.saturating_add(1).saturating_mul(10)
+            >= self.visible_story_count().saturating_mul(8)
+    }
+
     pub fn maybe_prefetch_comments(&mut self) {
         if self.view != View::Stories {
             return;
@@ -318,6 +329,8 @@ mod tests {
             time: 1,
             comment_count: comments,
             kids: vec![id + 100],
+            kind: None,
+            stale_secs: None,
         }
     }
 
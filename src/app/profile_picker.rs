@@ -0,0 +1,62 @@
+use super::{App, TaskTarget};
+use crate::input::ProfilePickerAction;
+
+impl App {
+    /// `p` over a summary: list `[[summarize.profiles]]` to re-run with.
+    pub(super) fn open_profile_picker(&mut self) {
+        let names: Vec<String> = self
+            .config
+            .summarize()
+            .map(|summarize| {
+                summarize
+                    .profiles
+                    .iter()
+                    .map(|profile| profile.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if names.is_empty() {
+            self.last_error =
+                Some("no summary profiles: add [[summarize.profiles]] to config.toml".to_string());
+            return;
+        }
+        self.summary_overlay.open_profile_picker(names);
+    }
+
+    pub(super) fn handle_profile_picker_action(&mut self, action: ProfilePickerAction) {
+        let picked = match action {
+            ProfilePickerAction::Dismiss => {
+                self.summary_overlay.close_profile_picker();
+                return;
+            }
+            ProfilePickerAction::MoveDown => {
+                self.summary_overlay.move_profile_cursor(true);
+                return;
+            }
+            ProfilePickerAction::MoveUp => {
+                self.summary_overlay.move_profile_cursor(false);
+                return;
+            }
+            ProfilePickerAction::Select => self.summary_overlay.take_picked_profile(None),
+            ProfilePickerAction::SelectIndex(index) => {
+                self.summary_overlay.take_picked_profile(Some(index))
+            }
+        };
+        if let Some(profile) = picked {
+            self.rerun_summary(profile);
+        }
+    }
+
+    /// Summarize the open story again under `profile`. A summary still
+    /// gathering its inputs just picks the profile up when it starts.
+    fn rerun_summary(&mut self, profile: Option<String>) {
+        self.summary_profile = profile;
+        if self.pending_summary.is_some() {
+            self.summary_overlay
+                .set_profile(self.summary_profile.clone());
+            return;
+        }
+        self.tasks.cancel(TaskTarget::Summary);
+        self.start_summary_for_loaded_comments(true);
+    }
+}
@@ -0,0 +1,67 @@
+use super::tests::{comment, finish_summary, summarized_app, FakeLlmStream};
+use crate::config::SummaryProfile;
+use crate::input::{Action, InputLayer, ProfilePickerAction, SummaryAction};
+use crate::ui::summary_overlay::SummaryState;
+
+fn profile(name: &str, system_prompt: &str) -> SummaryProfile {
+    SummaryProfile {
+        name: name.to_string(),
+        model: None,
+        max_prompt_tokens: None,
+        system_prompt: Some(system_prompt.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn picking_a_profile_reruns_the_story_and_sticks_for_the_next_summary() {
+    let stream = FakeLlmStream::replying("summary");
+    let (mut app, mut rx) = summarized_app(
+        &stream,
+        vec![
+            profile("tl;dr", "One line."),
+            profile("links", "List links."),
+        ],
+        vec![comment(11)],
+    );
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    app.handle_action(Action::Summary(SummaryAction::PickProfile));
+    assert_eq!(app.input_layer(), InputLayer::ProfilePicker);
+    app.handle_action(Action::ProfilePicker(ProfilePickerAction::MoveDown));
+    app.handle_action(Action::ProfilePicker(ProfilePickerAction::MoveDown));
+    app.handle_action(Action::ProfilePicker(ProfilePickerAction::Select));
+    assert_eq!(app.input_layer(), InputLayer::Summary);
+    finish_summary(&mut app, &mut rx).await;
+    assert_eq!(app.summary_overlay.state(), SummaryState::Done);
+
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+    assert!(app.summary_overlay.cached_at().is_some());
+    app.handle_action(Action::Summary(SummaryAction::Regenerate));
+    finish_summary(&mut app, &mut rx).await;
+
+    let prompts: Vec<String> = stream
+        .requests()
+        .iter()
+        .map(|request| request.system_prompt().to_string())
+        .collect();
+    assert_eq!(prompts, vec!["Summarize", "List links.", "List links."]);
+}
+
+#[tokio::test]
+async fn the_picker_needs_profiles_in_the_config() {
+    let stream = FakeLlmStream::replying("summary");
+    let (mut app, mut rx) = summarized_app(&stream, Vec::new(), vec![comment(11)]);
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    app.handle_action(Action::Summary(SummaryAction::PickProfile));
+
+    assert_eq!(app.input_layer(), InputLayer::Summary);
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("no summary profiles: add [[summarize.profiles]] to config.toml")
+    );
+}
@@ -1,7 +1,10 @@
 use super::{App, AppEvent, TaskTarget, View};
-use crate::api::{DiskCacheConfig, FeedKind, HnClient, SearchClient, Sources};
+use crate::api::{
+    ApiBackend, DiskCacheConfig, FeedKind, HnClient, SearchClient, Sources, UserClient,
+};
 use crate::article::ArticleFetcher;
 use crate::config::Config;
+use crate::logging;
 use crate::state::StateStore;
 use crate::summarizer::Summarizer;
 use crate::tui::Tui;
@@ -21,6 +24,15 @@ fn prepare_article_cache_dir(dir: &std::path::Path) -> Result<std::path::PathBuf
 }
 
 pub async fn run(cli: Cli, config: Config) -> Result<()> {
+    let color_depth = cli
+        .color_depth
+        .unwrap_or_else(ui::color_depth::ColorDepth::detect);
+    ui::color_depth::install(color_depth);
+    let theme = config.theme().cloned().unwrap_or_default();
+    ui::theme::install(
+        ui::theme::Theme::from_config(&theme, color_depth).context("config [theme]")?,
+    );
+
     let resolved_cache_dir = match cli.file_cache_dir.clone() {
         Some(dir) => dir,
         None => {
@@ -44,7 +56,7 @@ pub async fn run(cli: Cli, config: Config) -> Result<()> {
         .pool_idle_timeout(Duration::from_secs(30))
         .build()
         .context("build shared HTTP client")?;
-    let client = HnClient::new(
+    let mut client = HnClient::new(
         http.clone(),
         base_url,
         backend,
@@ -52,8 +64,24 @@ pub async fn run(cli: Cli, config: Config) -> Result<()> {
         cli.concurrency.get(),
         disk_cache,
     )?;
-    client.cleanup_disk_cache_background(Duration::from_secs(60 * 60 * 24));
-    let search = SearchClient::new(http.clone(), "https://hn.algolia.com/api/v1/search")?;
+    if backend == ApiBackend::HackerWeb && !cli.no_failover {
+        client = client.with_firebase_failover(
+            ApiBackend::Firebase.default_base_url().to_string(),
+            Duration::from_secs(cli.failover_cooldown_secs.get()),
+        );
+    }
+    client.set_offline(cli.offline);
+    // Offline, the expired items are all there is to read.
+    if !cli.offline {
+        client.cleanup_disk_cache_background(Duration::from_secs(60 * 60 * 24));
+    }
+    let search = SearchClient::new(http.clone(), ApiBackend::Algolia.default_base_url())?;
+    // Only Firebase serves profiles; a `--base-url` for it is honoured here too.
+    let firebase_base_url = match backend {
+        ApiBackend::Firebase => cli.resolved_base_url(),
+        _ => ApiBackend::Firebase.default_base_url().to_string(),
+    };
+    let users = UserClient::new(http.clone(), firebase_base_url, search.clone());
     let summarizer = Summarizer::new(config.summarize().cloned(), config.api_key_override(), http);
     // localwebrs writes a CWD-relative `cache/cache.sqlite`, so it must run
     // from our cache dir or it litters the user's working directory. The dir
@@ -62,7 +90,7 @@ pub async fn run(cli: Cli, config: Config) -> Result<()> {
     // localwebrs". Independent of --no-file-cache, which is about HN items.
     let article_dir = prepare_article_cache_dir(&resolved_cache_dir)?;
     let article_fetcher = ArticleFetcher::new(config.article_bin(), Some(article_dir));
-    let sources = Sources::new(Arc::new(client), Arc::new(search));
+    let sources = Sources::new(Arc::new(client), Arc::new(search), Arc::new(users));
 
     let (tx, mut rx) = mpsc::unbounded_channel::<AppEvent>();
     let mut app = App::new(
@@ -79,8 +107,25 @@ pub async fn run(cli: Cli, config: Config) -> Result<()> {
         if let Some(state) = store.load_story_list_state().await? {
             let feed = state.feed.as_deref().and_then(FeedKind::from_str_opt);
             app.seen_story_ids.extend(state.seen_story_ids);
+            app.comment_read_marks.extend(state.comment_read_marks);
             app.restore_story_list_state(state.story_ids, state.stories, feed);
         }
+        // Unreadable bookmarks should not keep the reader out. They are only
+        // written back once one is toggled, so the file is left as found.
+        match store.load_bookmarks().await {
+            Ok(bookmarks) => app.bookmarks = bookmarks,
+            Err(error) => {
+                app.last_error
+                    .get_or_insert_with(|| format!("bookmarks: {error:#}"));
+            }
+        }
+        // The summary cache is only a cache: start empty and log why.
+        match store.load_summaries().await {
+            Ok(summaries) => app.summary_cache = summaries,
+            Err(error) => {
+                logging::log_error(format!("failed to load summary cache: {error:#}"));
+            }
+        }
     }
     app.maybe_prefetch_comments();
     app.refresh_stories();
@@ -134,18 +179,23 @@ pub async fn run(cli: Cli, config: Config) -> Result<()> {
 
     drop(tui);
     app.tasks.cancel_and_wait(TaskTarget::StoryStateSave).await;
+    let bookmarks_unsaved = app.tasks.cancel_and_wait(TaskTarget::BookmarksSave).await;
+    let summaries_unsaved = app
+        .tasks
+        .cancel_and_wait(TaskTarget::SummaryCacheSave)
+        .await;
+    if app.tasks.cancel_and_wait(TaskTarget::MuteSave).await {
+        app.config.write().await?;
+    }
     if let Some(store) = &state_store {
-        if !app.story_ids.is_empty() && !app.stories.is_empty() {
-            store
-                .save_story_list_state(
-                    app.story_ids.clone(),
-                    app.stories.clone(),
-                    app.current_feed.as_str().to_string(),
-                    app.seen_story_ids.iter().copied().collect(),
-                )
-                .await?;
+        if bookmarks_unsaved {
+            store.save_bookmarks(app.bookmarks.clone()).await?;
+        }
+        if summaries_unsaved {
+            store.save_summaries(app.summary_cache.clone()).await?;
         }
     }
+    app.save_story_list_state().await?;
 
     Ok(())
 }
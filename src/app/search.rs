@@ -1,5 +1,48 @@
-use super::{App, AppEvent, TaskTarget};
-use crate::api::Story;
+use super::comment_tree::{ancestors_of, info_for_comment};
+use super::{App, AppEvent, StoriesLoadMode, TaskTarget, View};
+use crate::api::{CommentHit, SearchPage, SearchQuery, Story};
+
+/// What `/` looks for. Tab while typing the query switches between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    #[default]
+    Stories,
+    Comments,
+}
+
+impl SearchScope {
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Stories => Self::Comments,
+            Self::Comments => Self::Stories,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Stories => "stories",
+            Self::Comments => "comments",
+        }
+    }
+}
+
+/// The submitted query and the last Algolia page loaded for it.
+#[derive(Debug, Clone)]
+pub(super) struct ActiveSearch {
+    query: SearchQuery,
+    scope: SearchScope,
+    page: usize,
+}
+
+/// A comment hit being opened: select it once its thread is in the tree.
+#[derive(Debug, Clone)]
+pub(super) struct CommentFocus {
+    story_id: u64,
+    comment_id: u64,
+    /// The hit's parent, then each ancestor above it as lookups find them,
+    /// until one is in the loaded tree.
+    ancestors: Vec<u64>,
+}
 
 pub(super) struct SavedStories {
     stories: Vec<Story>,
@@ -8,22 +51,36 @@ pub(super) struct SavedStories {
 }
 
 impl SavedStories {
-    fn capture(app: &App) -> Self {
+    pub(super) fn capture(app: &App) -> Self {
         Self {
             stories: app.stories.clone(),
             story_ids: app.story_ids.clone(),
             has_more_stories: app.has_more_stories,
         }
     }
+
+    pub(super) fn restore(self, app: &mut App) {
+        app.stories = self.stories;
+        app.story_ids = self.story_ids;
+        app.has_more_stories = self.has_more_stories;
+        app.story_list_state.select(Some(0));
+        *app.story_list_state.offset_mut() = 0;
+    }
 }
 
 impl App {
     pub(super) fn submit_search(&mut self) {
         self.search_input_active = false;
-        let query = self.search_query.trim().to_string();
-        if query.is_empty() {
+        if self.search_query.trim().is_empty() {
             return;
         }
+        let query = match SearchQuery::parse(&self.search_query) {
+            Ok(query) => query,
+            Err(error) => {
+                self.last_error = Some(format!("search: {error}"));
+                return;
+            }
+        };
 
         if !self.search_active {
             self.saved_stories = Some(SavedStories::capture(self));
@@ -35,12 +92,209 @@ impl App {
         self.tasks.cancel(TaskTarget::Stories);
         self.cancel_comment_root_tasks();
 
+        let search = ActiveSearch {
+            query,
+            scope: self.search_scope,
+            page: 0,
+        };
+        self.spawn_search_page(&search, 0, StoriesLoadMode::Replace);
+        self.active_search = Some(search);
+    }
+
+    /// Fetch the next Algolia page once the selection nears the end of the
+    /// results, the way feeds page in more stories.
+    pub(super) fn maybe_load_more_search_results(&mut self) {
+        if self.story_loading || !self.has_more_stories || self.tasks.is_running(TaskTarget::Search)
+        {
+            return;
+        }
+        let Some(search) = self.active_search.clone() else {
+            return;
+        };
+        if !self.is_selection_near_list_end() {
+            return;
+        }
+        self.spawn_search_page(&search, search.page + 1, StoriesLoadMode::Append);
+    }
+
+    pub fn is_search_page_in_flight(&self) -> bool {
+        !self.story_loading && self.tasks.is_running(TaskTarget::Search)
+    }
+
+    /// Whether the list shows comment hits rather than stories.
+    pub fn comment_search_active(&self) -> bool {
+        self.active_search
+            .as_ref()
+            .is_some_and(|search| search.scope == SearchScope::Comments)
+    }
+
+    pub fn selected_comment_hit(&self) -> Option<&CommentHit> {
+        let sel = self.story_list_state.selected().unwrap_or(0);
+        let index = if !self.is_story_list_filtered() {
+            sel
+        } else {
+            *self.visible_story_indices.get(sel)?
+        };
+        self.comment_hits.get(index)
+    }
+
+    fn spawn_search_page(&mut self, search: &ActiveSearch, page: usize, mode: StoriesLoadMode) {
         let source = self.sources.search.clone();
-        self.tasks.spawn(
-            TaskTarget::Search,
-            async move { source.search(query).await },
-            move |task, stories| AppEvent::SearchResultsLoaded { task, stories },
-        );
+        let query = search.query.clone();
+        match search.scope {
+            SearchScope::Stories => {
+                self.tasks.spawn(
+                    TaskTarget::Search,
+                    async move { source.search(query, page).await },
+                    move |task, page| AppEvent::SearchResultsLoaded { task, mode, page },
+                );
+            }
+            SearchScope::Comments => {
+                self.tasks.spawn(
+                    TaskTarget::Search,
+                    async move { source.search_comments(query, page).await },
+                    move |task, page| AppEvent::CommentSearchResultsLoaded { task, mode, page },
+                );
+            }
+        }
+    }
+
+    pub(super) fn note_search_page_loaded(&mut self, mode: StoriesLoadMode, has_more: bool) {
+        self.story_loading = false;
+        self.last_error = None;
+        self.has_more_stories = has_more;
+        if mode == StoriesLoadMode::Append {
+            if let Some(search) = self.active_search.as_mut() {
+                search.page += 1;
+            }
+        }
+    }
+
+    /// Comment hits fill the story list with their stories, one row per hit,
+    /// so filtering and the story actions (`o`, `b`, `s`, `v`) keep working.
+    pub(super) fn handle_comment_search_results(
+        &mut self,
+        mode: StoriesLoadMode,
+        page: SearchPage<CommentHit>,
+    ) {
+        self.note_search_page_loaded(mode, page.has_more);
+        match mode {
+            StoriesLoadMode::Replace => {
+                self.comment_hits.clear();
+                self.stories.clear();
+                self.story_list_state.select(Some(0));
+                *self.story_list_state.offset_mut() = 0;
+            }
+            StoriesLoadMode::Append => {}
+        }
+        for hit in page.hits {
+            if self.comment_hits.iter().any(|loaded| loaded.id == hit.id) {
+                continue;
+            }
+            self.stories.push(hit.story.clone());
+            self.comment_hits.push(hit);
+        }
+        self.story_ids = self.stories.iter().map(|story| story.id).collect();
+        self.recompute_visible_stories();
+        self.ensure_selected_story_visible();
+    }
+
+    /// Open the selected hit's story with the matched comment selected.
+    pub(super) fn open_selected_comment_hit(&mut self) {
+        if let Some(hit) = self.selected_comment_hit().cloned() {
+            self.open_comment_hit(hit);
+        }
+    }
+
+    pub(super) fn open_comment_hit(&mut self, hit: CommentHit) {
+        self.pending_comment_focus = Some(CommentFocus {
+            story_id: hit.story.id,
+            comment_id: hit.id,
+            ancestors: vec![hit.parent_id],
+        });
+        self.open_comments_for(hit.story);
+        self.reveal_pending_comment();
+    }
+
+    /// Expand the path down to a pending comment hit and select it. A hit
+    /// below the loaded part of the thread (Firebase stops a few levels
+    /// down) is reached by walking up its parents until one is in the tree,
+    /// then loading the replies level by level; each load calls back here.
+    pub(super) fn reveal_pending_comment(&mut self) {
+        let Some(focus) = self.pending_comment_focus.as_ref() else {
+            return;
+        };
+        let on_story = self
+            .current_story
+            .as_ref()
+            .is_some_and(|story| story.id == focus.story_id);
+        if self.view != View::Comments || !on_story {
+            self.pending_comment_focus = None;
+            return;
+        }
+        if self.comment_loading {
+            return;
+        }
+
+        let comment_id = focus.comment_id;
+        if ancestors_of(&self.comment_tree, comment_id).is_some() {
+            self.pending_comment_focus = None;
+            self.reveal_comment(comment_id);
+            self.expand_selected_comment();
+            return;
+        }
+
+        let loaded = focus
+            .ancestors
+            .iter()
+            .find_map(|&id| info_for_comment(&self.comment_tree, id).map(|info| (id, info)));
+        match loaded {
+            Some((id, (_, kids, false, false))) if !kids.is_empty() => {
+                self.start_loading_comment_children(id)
+            }
+            Some((_, (_, _, _, true))) => {}
+            Some(_) => self.fail_pending_comment(comment_id),
+            None => {
+                let top = *focus.ancestors.last().expect("a hit has a parent");
+                if top == focus.story_id {
+                    self.fail_pending_comment(comment_id);
+                } else if !self.tasks.is_running(TaskTarget::CommentParent(top)) {
+                    let source = self.sources.stories.clone();
+                    self.tasks.spawn(
+                        TaskTarget::CommentParent(top),
+                        async move { source.comment_parent(top).await },
+                        |task, parent| AppEvent::CommentParentLoaded { task, parent },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Continue a pending reveal one level further up the hit's parents.
+    pub(super) fn note_comment_parent(&mut self, comment_id: u64, parent: Option<u64>) {
+        let Some(focus) = self.pending_comment_focus.as_mut() else {
+            return;
+        };
+        if focus.ancestors.last() != Some(&comment_id) {
+            return;
+        }
+        match parent {
+            Some(parent) => {
+                focus.ancestors.push(parent);
+                self.reveal_pending_comment();
+            }
+            None => {
+                let hit = focus.comment_id;
+                self.fail_pending_comment(hit);
+            }
+        }
+    }
+
+    fn fail_pending_comment(&mut self, comment_id: u64) {
+        self.pending_comment_focus = None;
+        self.last_error = Some(format!(
+            "comment id={comment_id} is not in the loaded thread"
+        ));
     }
 
     pub(super) fn cancel_search(&mut self) {
@@ -51,16 +305,15 @@ impl App {
     pub(super) fn exit_search_mode(&mut self) {
         self.tasks.cancel(TaskTarget::Search);
         self.search_active = false;
+        self.active_search = None;
+        self.comment_hits.clear();
         self.search_input_active = false;
         self.story_loading = false;
         self.search_query.clear();
 
         if let Some(saved) = self.saved_stories.take() {
-            self.stories = saved.stories;
-            self.story_ids = saved.story_ids;
-            self.has_more_stories = saved.has_more_stories;
-            self.story_list_state.select(Some(0));
-            *self.story_list_state.offset_mut() = 0;
+            saved.restore(self);
         }
+        self.recompute_visible_stories();
     }
 }
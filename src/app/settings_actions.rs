@@ -1,6 +1,5 @@
 use super::settings_popup::nonempty_owned;
-use super::{App, AppEvent, SettingsPopup, SettingsRow};
-use crate::api::FeedKind;
+use super::{App, AppEvent, FeedRow, SettingsPopup, SettingsRow};
 use crate::app::TaskTarget;
 use crate::config::{default_system_prompt, ConfigEdits, SummarizeConfig};
 use crate::input::{step_bounded, CursorStep, FeedFilterAction, SettingsAction, TextAction};
@@ -14,25 +13,21 @@ impl App {
         match action {
             FeedFilterAction::Dismiss => self.feed_filter_popup = None,
             FeedFilterAction::MoveDown => {
-                step_bounded(
-                    &mut popup.feed_cursor,
-                    CursorStep::Next,
-                    FeedKind::ALL.len(),
-                );
+                step_bounded(&mut popup.feed_cursor, CursorStep::Next, FeedRow::ALL.len());
             }
             FeedFilterAction::MoveUp => {
                 step_bounded(
                     &mut popup.feed_cursor,
                     CursorStep::Previous,
-                    FeedKind::ALL.len(),
+                    FeedRow::ALL.len(),
                 );
             }
             FeedFilterAction::Select => {
-                let selected = FeedKind::ALL[popup.feed_cursor];
+                let selected = FeedRow::ALL[popup.feed_cursor];
                 self.select_feed(selected);
             }
             FeedFilterAction::SelectIndex(index) => {
-                let selected = *FeedKind::ALL
+                let selected = *FeedRow::ALL
                     .get(index)
                     .unwrap_or_else(|| panic!("feed index out of range: {index}"));
                 self.select_feed(selected);
@@ -40,10 +35,20 @@ impl App {
         }
     }
 
-    fn select_feed(&mut self, selected: FeedKind) {
-        let changed = selected != self.current_feed;
+    fn select_feed(&mut self, selected: FeedRow) {
         self.feed_filter_popup = None;
-        if !changed {
+        let selected = match selected {
+            FeedRow::Saved => {
+                self.enter_saved_feed();
+                return;
+            }
+            FeedRow::Feed(feed) if self.saved_feed_active => {
+                self.leave_saved_feed(feed);
+                return;
+            }
+            FeedRow::Feed(feed) => feed,
+        };
+        if selected == self.current_feed {
             return;
         }
         if self.search_active {
@@ -247,17 +252,10 @@ impl App {
             self.last_error = Some("settings: model must be non-empty".to_string());
             return;
         }
-        let max_comments = match parse_positive(&popup.max_comments) {
-            Ok(number) => number,
-            Err(reason) => {
-                self.last_error = Some(format!("settings: max comments {reason}"));
-                return;
-            }
-        };
-        let max_article_chars = match parse_positive(&popup.max_article_chars) {
+        let max_prompt_tokens = match parse_positive(&popup.max_prompt_tokens) {
             Ok(number) => number,
             Err(reason) => {
-                self.last_error = Some(format!("settings: max article chars {reason}"));
+                self.last_error = Some(format!("settings: max prompt tokens {reason}"));
                 return;
             }
         };
@@ -272,21 +270,36 @@ impl App {
         };
         let api_key = nonempty_owned(&popup.api_key);
         let base_url = nonempty_owned(&popup.base_url);
+        // Profiles and per-model budgets are edited in the file, not the popup.
+        let (model_prompt_tokens, profiles) = self
+            .config
+            .summarize()
+            .map(|summarize| {
+                (
+                    summarize.model_prompt_tokens.clone(),
+                    summarize.profiles.clone(),
+                )
+            })
+            .unwrap_or_default();
         let summarize = SummarizeConfig {
             model: model.to_string(),
             api_key,
             base_url,
-            max_comments,
+            max_prompt_tokens,
+            model_prompt_tokens,
             include_article,
-            max_article_chars,
             system_prompt,
+            profiles,
         };
 
         let current = self.config.clone();
         self.tasks.spawn(
             TaskTarget::SettingsSave,
             async move { current.save(ConfigEdits { summarize }).await },
-            |task, config| AppEvent::SettingsSaved { task, config },
+            |task, config| AppEvent::SettingsSaved {
+                task,
+                config: Box::new(config),
+            },
         );
     }
 }
@@ -97,7 +97,7 @@ mod tests {
         (
             App::new(
                 super::super::tests::cli(),
-                Sources::new(source.clone(), source),
+                Sources::new(source.clone(), source.clone(), source),
                 tx,
                 None,
                 Config::for_test(std::env::temp_dir().join("hntui-connection-test.toml")),
@@ -174,10 +174,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn activating_the_eighth_row_starts_a_connection_test() {
+    async fn activating_the_seventh_row_starts_a_connection_test() {
         let mut app = test_app();
         app.handle_action(Action::OpenSettings);
-        app.settings_popup.as_mut().expect("settings").cursor = 7;
+        app.settings_popup.as_mut().expect("settings").cursor = 6;
 
         app.handle_action(Action::Settings(SettingsAction::Activate));
 
@@ -195,7 +195,7 @@ mod tests {
     async fn closing_rejects_a_queued_connection_result() {
         let (mut app, mut rx) = test_app_with_events();
         app.handle_action(Action::OpenSettings);
-        app.settings_popup.as_mut().expect("settings").cursor = 7;
+        app.settings_popup.as_mut().expect("settings").cursor = 6;
         app.handle_action(Action::Settings(SettingsAction::Activate));
         let queued = next_connection_event(&mut rx).await;
 
@@ -210,7 +210,7 @@ mod tests {
     async fn beginning_an_edit_rejects_a_queued_connection_result_and_resets_idle() {
         let (mut app, mut rx) = test_app_with_events();
         app.handle_action(Action::OpenSettings);
-        app.settings_popup.as_mut().expect("settings").cursor = 7;
+        app.settings_popup.as_mut().expect("settings").cursor = 6;
         app.handle_action(Action::Settings(SettingsAction::Activate));
         let queued = next_connection_event(&mut rx).await;
 
@@ -228,7 +228,7 @@ mod tests {
     async fn a_new_connection_test_supersedes_a_queued_result() {
         let (mut app, mut rx) = test_app_with_events();
         app.handle_action(Action::OpenSettings);
-        app.settings_popup.as_mut().expect("settings").cursor = 7;
+        app.settings_popup.as_mut().expect("settings").cursor = 6;
         app.handle_action(Action::Settings(SettingsAction::Activate));
         let first = next_connection_event(&mut rx).await;
         app.handle_action(Action::Settings(SettingsAction::Activate));
@@ -1,7 +1,4 @@
-use crate::config::{
-    default_include_article, default_max_article_chars, default_max_comments, Config,
-    SummarizeConfig,
-};
+use crate::config::{default_include_article, default_max_prompt_tokens, Config, SummarizeConfig};
 use std::time::{Duration, Instant};
 
 pub(super) fn nonempty_owned(value: &str) -> Option<String> {
@@ -14,20 +11,18 @@ pub(crate) enum SettingsField {
     Model,
     ApiKey,
     BaseUrl,
-    MaxComments,
+    MaxPromptTokens,
     IncludeArticle,
-    MaxArticleChars,
     SystemPrompt,
 }
 
 impl SettingsField {
-    pub(crate) const ALL: [Self; 7] = [
+    pub(crate) const ALL: [Self; 6] = [
         Self::Model,
         Self::ApiKey,
         Self::BaseUrl,
-        Self::MaxComments,
+        Self::MaxPromptTokens,
         Self::IncludeArticle,
-        Self::MaxArticleChars,
         Self::SystemPrompt,
     ];
 
@@ -36,9 +31,8 @@ impl SettingsField {
             Self::Model => "Model",
             Self::ApiKey => "API Key",
             Self::BaseUrl => "Base URL",
-            Self::MaxComments => "Max Comments",
+            Self::MaxPromptTokens => "Max Prompt Tokens",
             Self::IncludeArticle => "Include Article",
-            Self::MaxArticleChars => "Max Article Chars",
             Self::SystemPrompt => "System Prompt",
         }
     }
@@ -55,13 +49,12 @@ pub(crate) enum SettingsRow {
 }
 
 impl SettingsRow {
-    const ALL: [Self; 8] = [
+    const ALL: [Self; 7] = [
         Self::Field(SettingsField::Model),
         Self::Field(SettingsField::ApiKey),
         Self::Field(SettingsField::BaseUrl),
-        Self::Field(SettingsField::MaxComments),
+        Self::Field(SettingsField::MaxPromptTokens),
         Self::Field(SettingsField::IncludeArticle),
-        Self::Field(SettingsField::MaxArticleChars),
         Self::Field(SettingsField::SystemPrompt),
         Self::TestConnection,
     ];
@@ -87,9 +80,8 @@ pub struct SettingsPopup {
     pub model: String,
     pub api_key: String,
     pub base_url: String,
-    pub max_comments: String,
+    pub max_prompt_tokens: String,
     pub include_article: String,
-    pub max_article_chars: String,
     pub system_prompt: String,
     pub api_key_status: Option<String>,
     pub(crate) connection_test: ConnectionTestState,
@@ -133,9 +125,8 @@ impl SettingsPopup {
                 model: c.model.clone(),
                 api_key: c.api_key.clone().unwrap_or_default(),
                 base_url: c.base_url.clone().unwrap_or_default(),
-                max_comments: c.max_comments.to_string(),
+                max_prompt_tokens: c.max_prompt_tokens.to_string(),
                 include_article: c.include_article.to_string(),
-                max_article_chars: c.max_article_chars.to_string(),
                 system_prompt: c.system_prompt.clone(),
                 api_key_status,
                 connection_test: ConnectionTestState::Idle,
@@ -150,9 +141,8 @@ impl SettingsPopup {
                 model: String::new(),
                 api_key: String::new(),
                 base_url: String::new(),
-                max_comments: default_max_comments().to_string(),
+                max_prompt_tokens: default_max_prompt_tokens().to_string(),
                 include_article: default_include_article().to_string(),
-                max_article_chars: default_max_article_chars().to_string(),
                 system_prompt: String::new(),
                 api_key_status,
                 connection_test: ConnectionTestState::Idle,
@@ -186,9 +176,8 @@ impl SettingsPopup {
             SettingsField::Model => &self.model,
             SettingsField::ApiKey => &self.api_key,
             SettingsField::BaseUrl => &self.base_url,
-            SettingsField::MaxComments => &self.max_comments,
+            SettingsField::MaxPromptTokens => &self.max_prompt_tokens,
             SettingsField::IncludeArticle => &self.include_article,
-            SettingsField::MaxArticleChars => &self.max_article_chars,
             SettingsField::SystemPrompt => &self.system_prompt,
         }
     }
@@ -228,9 +217,8 @@ impl SettingsPopup {
             SettingsField::Model => &mut self.model,
             SettingsField::ApiKey => &mut self.api_key,
             SettingsField::BaseUrl => &mut self.base_url,
-            SettingsField::MaxComments => &mut self.max_comments,
+            SettingsField::MaxPromptTokens => &mut self.max_prompt_tokens,
             SettingsField::IncludeArticle => &mut self.include_article,
-            SettingsField::MaxArticleChars => &mut self.max_article_chars,
             SettingsField::SystemPrompt => &mut self.system_prompt,
         }
     }
@@ -452,11 +440,11 @@ mod tests {
     }
 
     #[test]
-    fn connection_test_is_the_eighth_non_editable_settings_row() {
+    fn connection_test_is_the_seventh_non_editable_settings_row() {
         let mut popup = SettingsPopup::from_summarize(None, None);
 
-        assert_eq!(SettingsPopup::rows().len(), 8);
-        popup.cursor = 7;
+        assert_eq!(SettingsPopup::rows().len(), 7);
+        popup.cursor = 6;
         assert_eq!(popup.selected_row(), SettingsRow::TestConnection);
         assert_eq!(popup.selected_field(), None);
         assert_eq!(popup.connection_test, ConnectionTestState::Idle);
@@ -1,5 +1,8 @@
+use super::story_filter::{FilterContext, NamedFilter, StoryFilter};
 use super::{App, AppEvent, StoriesLoadMode, TaskTarget};
-use crate::api::{FeedKind, Story};
+use crate::api::{ApiBackend, FeedKind, Story};
+use crate::ui::now_unix;
+use anyhow::Result;
 
 impl App {
     pub fn restore_story_list_state(
@@ -25,12 +28,25 @@ impl App {
     }
 
     pub(super) fn save_story_list_state_background(&mut self) {
-        if self.search_active {
-            return;
-        }
         let Some(store) = self.state_store.clone() else {
             return;
         };
+        // Search results and the Saved feed are not the feed to restore, so
+        // only what was read is saved over the listing already on disk.
+        if self.search_active || self.saved_feed_active {
+            let seen_story_ids: Vec<u64> = self.seen_story_ids.iter().copied().collect();
+            let comment_read_marks = self.comment_read_marks.clone().into_iter().collect();
+            self.tasks.spawn(
+                TaskTarget::StoryStateSave,
+                async move {
+                    store
+                        .save_reading_marks(seen_story_ids, comment_read_marks)
+                        .await
+                },
+                |task, ()| AppEvent::TaskCompleted { task },
+            );
+            return;
+        }
         if self.story_ids.is_empty() || self.stories.is_empty() {
             return;
         }
@@ -39,17 +55,51 @@ impl App {
         let stories = self.stories.clone();
         let feed = self.current_feed.as_str().to_string();
         let seen_story_ids: Vec<u64> = self.seen_story_ids.iter().copied().collect();
+        let comment_read_marks = self.comment_read_marks.clone().into_iter().collect();
         self.tasks.spawn(
             TaskTarget::StoryStateSave,
             async move {
                 store
-                    .save_story_list_state(story_ids, stories, feed, seen_story_ids)
+                    .save_story_list_state(
+                        story_ids,
+                        stories,
+                        feed,
+                        seen_story_ids,
+                        comment_read_marks,
+                    )
                     .await
             },
             |task, ()| AppEvent::TaskCompleted { task },
         );
     }
 
+    /// [`save_story_list_state_background`](Self::save_story_list_state_background),
+    /// awaited: for quitting, once any save in flight has been cancelled.
+    pub(crate) async fn save_story_list_state(&self) -> Result<()> {
+        let Some(store) = &self.state_store else {
+            return Ok(());
+        };
+        let seen_story_ids: Vec<u64> = self.seen_story_ids.iter().copied().collect();
+        let comment_read_marks = self.comment_read_marks.clone().into_iter().collect();
+        if self.search_active || self.saved_feed_active {
+            return store
+                .save_reading_marks(seen_story_ids, comment_read_marks)
+                .await;
+        }
+        if self.story_ids.is_empty() || self.stories.is_empty() {
+            return Ok(());
+        }
+        store
+            .save_story_list_state(
+                self.story_ids.clone(),
+                self.stories.clone(),
+                self.current_feed.as_str().to_string(),
+                seen_story_ids,
+                comment_read_marks,
+            )
+            .await
+    }
+
     pub fn is_story_seen(&self, id: u64) -> bool {
         self.seen_story_ids.contains(&id)
     }
@@ -88,9 +138,28 @@ impl App {
         );
     }
 
+    pub(super) fn toggle_offline(&mut self) {
+        if self.cli.no_file_cache {
+            self.last_error = Some("offline mode needs the file cache".to_string());
+            return;
+        }
+        self.offline = !self.offline;
+        self.sources.stories.set_offline(self.offline);
+        self.last_error = None;
+        // Back online, replace the cached listing with a live one.
+        if !self.offline && !self.search_active && !self.saved_feed_active {
+            self.refresh_stories();
+        }
+    }
+
+    /// The backend serving stories, and whether it is a failover stand-in.
+    pub fn active_backend(&self) -> Option<(ApiBackend, bool)> {
+        self.sources.stories.active_backend()
+    }
+
     pub fn selected_story(&self) -> Option<&Story> {
         let sel = self.story_list_state.selected().unwrap_or(0);
-        if self.keyword_filter.is_empty() {
+        if !self.is_story_list_filtered() {
             self.stories.get(sel)
         } else {
             self.visible_story_indices
@@ -100,30 +169,55 @@ impl App {
     }
 
     pub fn visible_story_count(&self) -> usize {
-        if self.keyword_filter.is_empty() {
+        if !self.is_story_list_filtered() {
             self.stories.len()
         } else {
             self.visible_story_indices.len()
         }
     }
 
+    /// The `[feed_filters]` entry for the feed on screen. Search results
+    /// and the Saved feed are only ever filtered by hand.
+    pub fn active_feed_filter(&self) -> Option<&NamedFilter> {
+        if self.search_active || self.saved_feed_active {
+            return None;
+        }
+        self.feed_filters.get(&self.current_feed)
+    }
+
+    /// Whether `visible_story_indices` stands between the list and
+    /// `stories`: something typed, a feed filter, or a mute list.
+    pub fn is_story_list_filtered(&self) -> bool {
+        !self.keyword_filter.is_empty()
+            || self.active_feed_filter().is_some()
+            || !self.mute.is_empty()
+    }
+
+    /// Typed text that is not a valid filter is matched against titles
+    /// whole, so a half-typed `(` or a plain regex still narrows the list.
     pub fn recompute_visible_stories(&mut self) {
-        if self.keyword_filter.is_empty() {
+        if !self.is_story_list_filtered() {
             self.visible_story_indices.clear();
         } else {
-            let re = regex::RegexBuilder::new(&self.keyword_filter)
-                .case_insensitive(true)
-                .build();
+            let typed = (!self.keyword_filter.is_empty()).then(|| {
+                StoryFilter::parse(&self.keyword_filter)
+                    .unwrap_or_else(|_| StoryFilter::title(&self.keyword_filter))
+            });
+            let feed = self.active_feed_filter().map(|named| &named.filter);
+            let context = FilterContext {
+                now: now_unix(),
+                seen: &self.seen_story_ids,
+            };
             self.visible_story_indices = self
                 .stories
                 .iter()
                 .enumerate()
-                .filter(|(_, s)| match &re {
-                    Ok(re) => re.is_match(&s.title),
-                    Err(_) => s
-                        .title
-                        .to_lowercase()
-                        .contains(&self.keyword_filter.to_lowercase()),
+                .filter(|(_, story)| {
+                    !self.mute.hides_story(story)
+                        && feed.is_none_or(|filter| filter.matches(story, &context))
+                        && typed
+                            .as_ref()
+                            .is_none_or(|filter| filter.matches(story, &context))
                 })
                 .map(|(i, _)| i)
                 .collect();
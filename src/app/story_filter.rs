@@ -0,0 +1,557 @@
+use crate::api::types::HnItemKind;
+use crate::api::{FeedKind, Story};
+use crate::config::Config;
+use crate::ui::domain_from_url;
+use anyhow::{bail, Context, Result};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+
+/// A story filter: terms like `points>=50`, `domain:github.com`,
+/// `author:pg`, `age<6h`, `is:ask` or `is:seen`, combined with `and`
+/// (or just a space), `or`, `not` / `-term` and parentheses. Bare words
+/// match the title as a case-insensitive regex, as the plain title filter
+/// always did.
+#[derive(Debug, Clone)]
+pub struct StoryFilter(Expr);
+
+/// A `[filters]` entry, as `[feed_filters]` applies it to a feed.
+#[derive(Debug, Clone)]
+pub struct NamedFilter {
+    pub name: String,
+    pub filter: StoryFilter,
+}
+
+/// Each feed's filter from `[feed_filters]`, compiled from `[filters]`.
+pub fn feed_filters(config: &Config) -> Result<HashMap<FeedKind, NamedFilter>> {
+    let mut filters = HashMap::new();
+    for (feed, name) in config.feed_filters().into_iter().flatten() {
+        let kind = FeedKind::from_str_opt(feed)
+            .with_context(|| format!("[feed_filters]: unknown feed {feed:?}"))?;
+        let source = config
+            .filters()
+            .and_then(|filters| filters.get(name))
+            .with_context(|| format!("[feed_filters]: no filter named {name:?} in [filters]"))?;
+        let filter = StoryFilter::parse(source).with_context(|| format!("[filters] {name}"))?;
+        filters.insert(
+            kind,
+            NamedFilter {
+                name: name.clone(),
+                filter,
+            },
+        );
+    }
+    Ok(filters)
+}
+
+/// What a filter tests a story against besides the story itself.
+pub struct FilterContext<'a> {
+    pub now: i64,
+    pub seen: &'a HashSet<u64>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    All(Vec<Expr>),
+    Any(Vec<Expr>),
+    Not(Box<Expr>),
+    Title(TitlePattern),
+    /// The story's domain or one of its subdomains; `self` for self-posts.
+    Domain(String),
+    Author(String),
+    Points(Threshold),
+    Comments(Threshold),
+    /// Seconds since the story was submitted.
+    Age(Threshold),
+    Kind(StoryKind),
+    Seen,
+}
+
+#[derive(Debug, Clone)]
+enum TitlePattern {
+    Regex(Regex),
+    /// Lowercased text, for input that is not a valid regex.
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Threshold {
+    op: Comparison,
+    value: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Greater,
+    AtLeast,
+    Less,
+    AtMost,
+    Equal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoryKind {
+    Story,
+    Ask,
+    Show,
+    Job,
+}
+
+impl StoryKind {
+    /// A job by the item type the backend reported; HN types Ask and Show
+    /// as plain stories, so those are read off the title prefix.
+    pub fn of(story: &Story) -> Self {
+        if story.kind == Some(HnItemKind::Job) {
+            return Self::Job;
+        }
+        let title = story.title.to_lowercase();
+        if title.starts_with("ask hn") {
+            Self::Ask
+        } else if title.starts_with("show hn") {
+            Self::Show
+        } else {
+            Self::Story
+        }
+    }
+}
+
+impl StoryFilter {
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            bail!("filter is empty");
+        }
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let expr = parser.parse_or()?;
+        if parser.position < parser.tokens.len() {
+            bail!("unmatched )");
+        }
+        Ok(Self(expr))
+    }
+
+    /// The whole input as one title pattern, for what the filter input
+    /// held before the language existed and for text that does not parse.
+    pub fn title(input: &str) -> Self {
+        Self(Expr::Title(TitlePattern::new(input)))
+    }
+
+    pub fn matches(&self, story: &Story, context: &FilterContext) -> bool {
+        self.0.matches(story, context)
+    }
+}
+
+impl Expr {
+    fn matches(&self, story: &Story, context: &FilterContext) -> bool {
+        match self {
+            Self::All(terms) => terms.iter().all(|term| term.matches(story, context)),
+            Self::Any(terms) => terms.iter().any(|term| term.matches(story, context)),
+            Self::Not(term) => !term.matches(story, context),
+            Self::Title(pattern) => pattern.matches(&story.title),
+            Self::Domain(domain) => in_domain(story, domain),
+            Self::Author(author) => story.by.eq_ignore_ascii_case(author),
+            Self::Points(threshold) => threshold.holds(story.score),
+            Self::Comments(threshold) => threshold.holds(story.comment_count),
+            Self::Age(threshold) => threshold.holds(context.now - story.time),
+            Self::Kind(kind) => StoryKind::of(story) == *kind,
+            Self::Seen => context.seen.contains(&story.id),
+        }
+    }
+}
+
+/// The story's lowercased domain, `self` for a self-post.
+pub(super) fn story_domain(story: &Story) -> String {
+    story
+        .url
+        .as_deref()
+        .and_then(domain_from_url)
+        .unwrap_or_else(|| "self".to_string())
+        .to_lowercase()
+}
+
+/// Whether the story links to lowercased `domain` or a subdomain of it.
+pub(super) fn in_domain(story: &Story, domain: &str) -> bool {
+    let story_domain = story_domain(story);
+    story_domain == domain
+        || story_domain
+            .strip_suffix(domain)
+            .is_some_and(|rest| rest.ends_with('.'))
+}
+
+impl TitlePattern {
+    fn new(pattern: &str) -> Self {
+        match RegexBuilder::new(pattern).case_insensitive(true).build() {
+            Ok(regex) => Self::Regex(regex),
+            Err(_) => Self::Text(pattern.to_lowercase()),
+        }
+    }
+
+    fn matches(&self, title: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(title),
+            Self::Text(text) => title.to_lowercase().contains(text),
+        }
+    }
+}
+
+impl Threshold {
+    fn holds(self, value: i64) -> bool {
+        match self.op {
+            Comparison::Greater => value > self.value,
+            Comparison::AtLeast => value >= self.value,
+            Comparison::Less => value < self.value,
+            Comparison::AtMost => value <= self.value,
+            Comparison::Equal => value == self.value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    /// `quoted` words are never `and`/`or`/`not` or a `-` negation.
+    Word {
+        text: String,
+        quoted: bool,
+    },
+}
+
+/// Words split at whitespace and parentheses; double quotes keep both in
+/// a word, so `title:"rust (lang)"` is one term.
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    for character in input.chars() {
+        match character {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            _ if in_quotes => word.push(character),
+            '(' if word.is_empty() && !quoted => tokens.push(Token::Open),
+            ')' => {
+                flush(&mut word, &mut quoted, &mut tokens);
+                tokens.push(Token::Close);
+            }
+            _ if character.is_whitespace() => flush(&mut word, &mut quoted, &mut tokens),
+            _ => word.push(character),
+        }
+    }
+    if in_quotes {
+        bail!("unclosed quote");
+    }
+    flush(&mut word, &mut quoted, &mut tokens);
+    Ok(tokens)
+}
+
+fn flush(word: &mut String, quoted: &mut bool, tokens: &mut Vec<Token>) {
+    if !word.is_empty() || *quoted {
+        tokens.push(Token::Word {
+            text: std::mem::take(word),
+            quoted: *quoted,
+        });
+    }
+    *quoted = false;
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut any = vec![self.parse_and()?];
+        while self.eat_keyword("or") {
+            any.push(self.parse_and()?);
+        }
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            Expr::Any(any)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut all = vec![self.parse_unary()?];
+        while !matches!(self.tokens.get(self.position), None | Some(Token::Close))
+            && !self.is_keyword("or")
+        {
+            self.eat_keyword("and");
+            all.push(self.parse_unary()?);
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            Expr::All(all)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        let Some(token) = self.tokens.get(self.position).cloned() else {
+            bail!("filter ends where a term was expected");
+        };
+        self.position += 1;
+        match token {
+            Token::Open => {
+                let inner = self.parse_or()?;
+                if self.tokens.get(self.position) != Some(&Token::Close) {
+                    bail!("unclosed (");
+                }
+                self.position += 1;
+                Ok(inner)
+            }
+            Token::Close => bail!("unexpected )"),
+            Token::Word { text, quoted: true } => parse_term(&text),
+            Token::Word {
+                text,
+                quoted: false,
+            } => match text.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => Ok(Expr::Not(Box::new(parse_term(rest)?))),
+                _ => parse_term(&text),
+            },
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(
+            self.tokens.get(self.position),
+            Some(Token::Word { text, quoted: false }) if text.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+}
+
+fn parse_term(word: &str) -> Result<Expr> {
+    if let Some((field, value)) = word.split_once(':') {
+        let term = match field {
+            "title" => Some(Expr::Title(TitlePattern::new(nonempty(field, value)?))),
+            "domain" | "site" => Some(Expr::Domain(nonempty(field, value)?.to_lowercase())),
+            "author" | "by" => Some(Expr::Author(nonempty(field, value)?.to_string())),
+            "is" => Some(match value {
+                "ask" => Expr::Kind(StoryKind::Ask),
+                "show" => Expr::Kind(StoryKind::Show),
+                "job" => Expr::Kind(StoryKind::Job),
+                "story" => Expr::Kind(StoryKind::Story),
+                "seen" => Expr::Seen,
+                "unseen" => Expr::Not(Box::new(Expr::Seen)),
+                _ => bail!("is: expects ask, show, job, story, seen or unseen, got {value:?}"),
+            }),
+            // Not a field; `C++:` and friends are title text.
+            _ => None,
+        };
+        if let Some(term) = term {
+            return Ok(term);
+        }
+    }
+    if let Some(term) = threshold_term(word)? {
+        return Ok(term);
+    }
+    Ok(Expr::Title(TitlePattern::new(word)))
+}
+
+fn nonempty<'a>(field: &str, value: &'a str) -> Result<&'a str> {
+    if value.is_empty() {
+        bail!("{field}: needs a value");
+    }
+    Ok(value)
+}
+
+/// `points>N`, `comments>=N` or `age<6h`; `None` when the word is not a
+/// threshold at all.
+fn threshold_term(word: &str) -> Result<Option<Expr>> {
+    for name in ["points", "score", "comments", "age"] {
+        let Some(rest) = word.strip_prefix(name) else {
+            continue;
+        };
+        let Some((op, comparison)) = [
+            (">=", Comparison::AtLeast),
+            ("<=", Comparison::AtMost),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+            ("=", Comparison::Equal),
+        ]
+        .into_iter()
+        .find(|(op, _)| rest.starts_with(op)) else {
+            continue;
+        };
+        let value = &rest[op.len()..];
+        let threshold = |value| Threshold {
+            op: comparison,
+            value,
+        };
+        return Ok(Some(match name {
+            "age" => Expr::Age(threshold(parse_age(value).with_context(|| {
+                format!("age{op} needs a duration like 30m, 6h or 2d, got {value:?}")
+            })?)),
+            _ => {
+                let value: i64 = value
+                    .parse()
+                    .with_context(|| format!("{name}{op} needs a whole number, got {value:?}"))?;
+                if name == "comments" {
+                    Expr::Comments(threshold(value))
+                } else {
+                    Expr::Points(threshold(value))
+                }
+            }
+        }));
+    }
+    Ok(None)
+}
+
+/// `30m`, `6h`, `2d` or `1w`, in seconds.
+fn parse_age(value: &str) -> Option<i64> {
+    let unit = value.chars().last()?;
+    let seconds = match unit {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let count: i64 = value[..value.len() - 1].parse().ok()?;
+    count.checked_mul(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn story(id: u64, title: &str, url: Option<&str>, score: i64, comments: i64) -> Story {
+        Story {
+            id,
+            title: title.to_string(),
+            url: url.map(str::to_string),
+            text: None,
+            score,
+            by: format!("user{id}"),
+            time: NOW - 60 * 60 * id as i64,
+            comment_count: comments,
+            kids: vec![],
+            kind: None,
+            stale_secs: None,
+        }
+    }
+
+    fn matching(filter: &str, stories: &[Story], seen: &HashSet<u64>) -> Vec<u64> {
+        let filter = StoryFilter::parse(filter).expect("filter parses");
+        let context = FilterContext { now: NOW, seen };
+        stories
+            .iter()
+            .filter(|story| filter.matches(story, &context))
+            .map(|story| story.id)
+            .collect()
+    }
+
+    fn stories() -> Vec<Story> {
+        vec![
+            story(
+                1,
+                "Rust 2.0 released",
+                Some("https://blog.rust-lang.org/x"),
+                300,
+                120,
+            ),
+            story(2, "Ask HN: What are you reading?", None, 40, 80),
+            story(
+                3,
+                "Show HN: A TUI for HN",
+                Some("https://github.com/a/b"),
+                12,
+                3,
+            ),
+            Story {
+                kind: Some(HnItemKind::Job),
+                ..story(
+                    4,
+                    "Acme (YC W24) is hiring engineers",
+                    Some("https://acme.dev"),
+                    0,
+                    0,
+                )
+            },
+            story(5, "Why Go?", Some("https://www.github.com/go"), 90, 45),
+        ]
+    }
+
+    #[test]
+    fn terms_combine_with_and_or_not_and_parentheses() {
+        let stories = stories();
+        let seen = HashSet::from([1]);
+
+        assert_eq!(matching("points>=50 -is:seen", &stories, &seen), vec![5]);
+        assert_eq!(
+            matching(
+                "is:ask or (domain:github.com and comments<10)",
+                &stories,
+                &seen
+            ),
+            vec![2, 3]
+        );
+        assert_eq!(matching("not is:story", &stories, &seen), vec![2, 3, 4]);
+        assert_eq!(matching("is:job", &stories, &seen), vec![4]);
+        assert_eq!(matching("age<3h author:USER1", &stories, &seen), vec![1]);
+        assert_eq!(matching("domain:self", &stories, &seen), vec![2]);
+    }
+
+    #[test]
+    fn only_the_reported_item_type_makes_a_job() {
+        let hiring = story(6, "Who is hiring? (October)", None, 0, 0);
+        let anonymous = Story {
+            by: String::new(),
+            ..story(7, "Acme is hiring", Some("https://acme.dev"), 0, 0)
+        };
+
+        assert_eq!(StoryKind::of(&hiring), StoryKind::Story);
+        assert_eq!(StoryKind::of(&anonymous), StoryKind::Story);
+    }
+
+    #[test]
+    fn bare_and_quoted_words_match_titles_like_the_old_filter() {
+        let stories = stories();
+        let seen = HashSet::new();
+
+        assert_eq!(matching("rust|go", &stories, &seen), vec![1, 5]);
+        assert_eq!(matching("\"are you\"", &stories, &seen), vec![2]);
+        assert_eq!(matching("title:\"(YC\"", &stories, &seen), vec![4]);
+        assert_eq!(matching("tui \"or\" hn", &stories, &seen), vec![3]);
+    }
+
+    #[test]
+    fn malformed_filters_say_what_is_wrong() {
+        for (input, message) in [
+            ("", "filter is empty"),
+            ("(points>5", "unclosed ("),
+            ("points>5)", "unmatched )"),
+            (
+                "is:poll",
+                "is: expects ask, show, job, story, seen or unseen, got \"poll\"",
+            ),
+            ("points>lots", "points> needs a whole number, got \"lots\""),
+            (
+                "age<soon",
+                "age< needs a duration like 30m, 6h or 2d, got \"soon\"",
+            ),
+            ("rust or", "filter ends where a term was expected"),
+        ] {
+            let error = StoryFilter::parse(input).expect_err(input);
+            assert_eq!(error.to_string(), message, "for {input:?}");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::tests::{app_with_parts, story, visible_ids};
+use super::*;
+use crate::api::InMemorySource;
+use crate::config::Config;
+use crate::input::{Action, TextAction};
+use crate::summarizer::Summarizer;
+
+fn filtered_app(
+    source: InMemorySource,
+    config_toml: &str,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, config_toml).expect("write config");
+    let config = Config::load(Some(&path)).expect("load config");
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    app_with_parts(source, config, None, summarizer)
+}
+
+fn scored(id: u64, score: i64) -> Story {
+    Story { score, ..story(id) }
+}
+
+const QUALITY: &str =
+    "[filters]\nquality = \"points>=50 -is:seen\"\n\n[feed_filters]\ntop = \"quality\"\n";
+
+#[tokio::test]
+async fn a_feed_filter_from_config_hides_low_score_and_seen_stories() {
+    let (mut app, mut rx) = filtered_app(
+        InMemorySource::new(vec![scored(1, 120), scored(2, 8), scored(3, 75)]),
+        QUALITY,
+    );
+    app.seen_story_ids.insert(3);
+
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    assert_eq!(
+        app.active_feed_filter().map(|named| named.name.as_str()),
+        Some("quality")
+    );
+    assert_eq!(visible_ids(&mut app), vec![1]);
+}
+
+#[tokio::test]
+async fn a_filtered_feed_pages_once_its_last_visible_story_is_selected() {
+    let stories = (1..=40)
+        .map(|id| scored(id, if matches!(id, 1 | 2 | 3 | 35) { 80 } else { 8 }))
+        .collect();
+    let (mut app, mut rx) = filtered_app(InMemorySource::new(stories), QUALITY);
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    assert_eq!(app.stories.len(), 30);
+
+    app.story_list_state.select(Some(0));
+    app.maybe_prefetch_stories();
+    assert!(!app.tasks.is_running(TaskTarget::Stories));
+
+    app.story_list_state.select(Some(2));
+    app.maybe_prefetch_stories();
+    app.handle_app_event(rx.recv().await.expect("more stories event"));
+
+    assert_eq!(app.stories.len(), 40);
+    assert_eq!(visible_ids(&mut app), vec![1, 2, 3, 35]);
+}
+
+#[tokio::test]
+async fn a_typed_filter_narrows_what_the_feed_filter_lets_through() {
+    let (mut app, mut rx) = filtered_app(
+        InMemorySource::new(vec![scored(1, 120), scored(2, 8), scored(3, 75)]),
+        QUALITY,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    assert_eq!(visible_ids(&mut app), vec![1, 3]);
+
+    app.handle_action(Action::OpenFilter);
+    for character in "points<100".chars() {
+        app.handle_action(Action::FilterInput(TextAction::Insert(character)));
+    }
+
+    assert_eq!(visible_ids(&mut app), vec![3]);
+}
+
+#[test]
+fn a_feed_filter_naming_a_missing_filter_is_reported_and_ignored() {
+    let (app, _rx) = filtered_app(
+        InMemorySource::default(),
+        "[feed_filters]\ntop = \"quality\"\n",
+    );
+
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("config: [feed_filters]: no filter named \"quality\" in [filters]")
+    );
+    assert!(app.active_feed_filter().is_none());
+}
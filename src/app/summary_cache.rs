@@ -0,0 +1,98 @@
+use super::comment_tree::{comment_ids, summary_comments};
+use super::{App, AppEvent, TaskTarget};
+use crate::state::{CachedSummary, SummaryKey, SUMMARY_CACHE_CAPACITY};
+use crate::text::stable_hash;
+use crate::ui::now_unix;
+use crate::ui::summary_overlay::SummaryState;
+
+impl App {
+    /// What a summary of `story_id` would be cached under right now: the
+    /// model of the profile in use, what shapes its prompt (system prompt,
+    /// budgets, whether the article goes in) and how many comments the
+    /// thread has loaded. Counting the tree rather than the list keeps folds,
+    /// expands and mute stubs from missing the cache.
+    pub(super) fn summary_key(&self, story_id: u64) -> Option<SummaryKey> {
+        let config = self
+            .summarizer
+            .resolved_config(self.summary_profile.as_deref())?;
+        let budgets: Vec<usize> = config
+            .models()
+            .map(|model| config.prompt_tokens_for(model))
+            .collect();
+        let prompt = format!(
+            "{}\0{budgets:?}\0{}",
+            config.system_prompt,
+            self.summary_includes_article()
+        );
+        Some(SummaryKey {
+            story_id,
+            model: config.model,
+            prompt_hash: stable_hash(&prompt),
+            comment_count: comment_ids(&self.comment_tree).len(),
+        })
+    }
+
+    /// Put the cached summary for `story_id` on screen, if one matches.
+    pub(super) fn show_cached_summary(&mut self, story_id: u64) -> bool {
+        let Some(key) = self.summary_key(story_id) else {
+            return false;
+        };
+        let Some(cached) = self.summary_cache.iter().find(|cached| cached.key == key) else {
+            return false;
+        };
+        self.summary_input = self.loaded_summary_input(story_id);
+        self.summary_overlay
+            .set_comment_count(summary_comments(&self.comment_tree).len());
+        self.summary_overlay.show_cached(
+            cached.summary.clone(),
+            cached.model_name.clone(),
+            cached.created_at,
+        );
+        self.set_summary_comment_ids();
+        true
+    }
+
+    /// Keep the summary that just finished streaming, replacing any older
+    /// one under the same key.
+    pub(super) fn cache_completed_summary(&mut self) {
+        let Some(key) = self.running_summary_key.take() else {
+            return;
+        };
+        if self.summary_overlay.summary().is_empty() {
+            return;
+        }
+        self.summary_cache.retain(|cached| cached.key != key);
+        self.summary_cache.insert(
+            0,
+            CachedSummary {
+                key,
+                model_name: self.summary_overlay.model_name().to_string(),
+                summary: self.summary_overlay.summary().to_string(),
+                created_at: now_unix(),
+            },
+        );
+        self.summary_cache.truncate(SUMMARY_CACHE_CAPACITY);
+
+        let Some(store) = self.state_store.clone() else {
+            return;
+        };
+        let summaries = self.summary_cache.clone();
+        self.tasks.spawn(
+            TaskTarget::SummaryCacheSave,
+            async move { store.save_summaries(summaries).await },
+            |task, ()| AppEvent::TaskCompleted { task },
+        );
+    }
+
+    /// `r` over a finished or failed summary: ask the LLM again, skipping
+    /// the cache. The fresh summary replaces the cached one.
+    pub(super) fn regenerate_summary(&mut self) {
+        if !matches!(
+            self.summary_overlay.state(),
+            SummaryState::Done | SummaryState::Error
+        ) {
+            return;
+        }
+        self.start_summary_for_loaded_comments(false);
+    }
+}
@@ -0,0 +1,150 @@
+use super::tests::{app_with_llm, comment, story, summarize_config, FakeLlmStream};
+use super::*;
+use crate::api::InMemorySource;
+use crate::config::{Config, SummarizeConfig};
+use crate::input::{Action, SummaryAction};
+use crate::ui::summary_overlay::SummaryState;
+
+fn cached_app(
+    stream: &FakeLlmStream,
+    state_dir: &std::path::Path,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let config = Config::for_test_with_summarize(
+        state_dir.join("config.toml"),
+        summarize_config(Vec::new()),
+    );
+    let (mut app, rx) = app_with_llm(
+        InMemorySource::new(vec![story(1)]),
+        config,
+        Some(StateStore::new(state_dir.to_path_buf())),
+        stream,
+    );
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![comment(11), comment(12)]),
+        true,
+    );
+    (app, rx)
+}
+
+async fn settle(app: &mut App, rx: &mut mpsc::UnboundedReceiver<AppEvent>) {
+    while app.tasks.is_running(TaskTarget::Summary)
+        || app.tasks.is_running(TaskTarget::SummaryCacheSave)
+    {
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("summary timed out")
+            .expect("app event channel closed");
+        app.handle_app_event(event);
+    }
+}
+
+#[tokio::test]
+async fn a_second_summarize_shows_the_cached_summary_without_calling_the_llm() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stream = FakeLlmStream::numbered("summary");
+    let (mut app, mut rx) = cached_app(&stream, dir.path());
+
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+    app.handle_action(Action::Summarize);
+
+    assert_eq!(stream.requests().len(), 1);
+    assert_eq!(app.summary_overlay.state(), SummaryState::Done);
+    assert_eq!(app.summary_overlay.summary(), "summary 1");
+    assert_eq!(app.summary_overlay.model_name(), "fake/model");
+    assert!(app.summary_overlay.cached_at().is_some());
+
+    let stored = StateStore::new(dir.path().to_path_buf())
+        .load_summaries()
+        .await
+        .expect("load summaries");
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].key.comment_count, 2);
+}
+
+#[tokio::test]
+async fn regenerate_replaces_the_cached_summary() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stream = FakeLlmStream::numbered("summary");
+    let (mut app, mut rx) = cached_app(&stream, dir.path());
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+
+    app.handle_action(Action::Summary(SummaryAction::Regenerate));
+    assert_eq!(app.summary_overlay.cached_at(), None);
+    settle(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+    app.handle_action(Action::Summarize);
+
+    assert_eq!(stream.requests().len(), 2);
+    assert_eq!(app.summary_overlay.summary(), "summary 2");
+    assert_eq!(app.summary_cache.len(), 1);
+}
+
+#[tokio::test]
+async fn a_new_prompt_budget_misses_the_cache() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stream = FakeLlmStream::numbered("summary");
+    let (mut app, mut rx) = cached_app(&stream, dir.path());
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+
+    let summarize = SummarizeConfig {
+        max_prompt_tokens: 8_000,
+        ..summarize_config(Vec::new())
+    };
+    app.summarizer.update_config(Some(summarize), None);
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+
+    assert_eq!(stream.requests().len(), 2);
+    assert_eq!(app.summary_overlay.summary(), "summary 2");
+}
+
+#[tokio::test]
+async fn new_comments_miss_the_cache() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stream = FakeLlmStream::numbered("summary");
+    let (mut app, mut rx) = cached_app(&stream, dir.path());
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![comment(11), comment(12), comment(13)]),
+        true,
+    );
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+
+    assert_eq!(stream.requests().len(), 2);
+    assert_eq!(app.summary_cache.len(), 2);
+}
+
+#[tokio::test]
+async fn folding_the_thread_still_hits_the_cache() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stream = FakeLlmStream::numbered("summary");
+    let (mut app, mut rx) = cached_app(&stream, dir.path());
+    let mut reply = comment(12);
+    reply.comment.depth = 1;
+    let mut root = comment(11);
+    root.comment.kids = vec![12];
+    root.children = vec![reply];
+    app.apply_comments_for_story(story(1), StoryThread::from_comments(vec![root]), true);
+    assert_eq!(app.comment_list.len(), 2);
+    app.handle_action(Action::Summarize);
+    settle(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+
+    app.handle_action(Action::CollapseAll);
+    assert_eq!(app.comment_list.len(), 1);
+    app.handle_action(Action::Summarize);
+
+    assert_eq!(stream.requests().len(), 1);
+    assert_eq!(app.summary_overlay.summary(), "summary 1");
+}
@@ -0,0 +1,58 @@
+use super::comment_tree::summary_comments;
+use super::{App, AppEvent, TaskTarget};
+use crate::input::TextAction;
+use crate::summarizer::SummaryInput;
+
+impl App {
+    pub(super) fn handle_summary_question(&mut self, action: TextAction) {
+        match action {
+            TextAction::Submit => {
+                if let Some(question) = self.summary_overlay.take_question() {
+                    self.ask_summary_follow_up(question);
+                }
+            }
+            TextAction::Cancel => self.summary_overlay.cancel_question(),
+            TextAction::DeleteBackward => self.summary_overlay.pop_question(),
+            TextAction::Insert(character) => self.summary_overlay.push_question(character),
+            _ => unreachable!("unsupported summary question text action: {action:?}"),
+        }
+    }
+
+    /// Send `question` with the story context and the exchange so far; the
+    /// answer streams into the overlay like the summary did.
+    fn ask_summary_follow_up(&mut self, question: String) {
+        let Some(input) = self.summary_input.clone() else {
+            self.last_error = Some("the story behind this summary is gone".to_string());
+            return;
+        };
+        self.summary_overlay.ask(question);
+        let transcript = self.summary_overlay.transcript();
+        let summarizer = self.summarizer.clone();
+        self.tasks.spawn_stream(
+            TaskTarget::Summary,
+            summarizer.follow_up(input, transcript),
+            |task, event| AppEvent::Summary { task, event },
+        );
+    }
+
+    /// The context for follow-ups on a cached summary: the loaded comments
+    /// and the article when it is already fetched.
+    pub(super) fn loaded_summary_input(&self, story_id: u64) -> Option<SummaryInput> {
+        let story = self
+            .current_story
+            .clone()
+            .filter(|story| story.id == story_id)?;
+        let article = self
+            .summary_includes_article()
+            .then(|| self.articles.get(story_id))
+            .flatten()
+            .map(|article| article.content.clone());
+        Some(SummaryInput {
+            story,
+            comments: summary_comments(&self.comment_tree),
+            article,
+            poll: self.comment_poll.clone(),
+            profile: self.summary_profile.clone(),
+        })
+    }
+}
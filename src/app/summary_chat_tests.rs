@@ -0,0 +1,85 @@
+use super::tests::{comment, finish_summary, story, summarized_app, FakeLlmStream};
+use super::*;
+use crate::input::{Action, InputLayer, SummaryAction, TextAction};
+use crate::summarizer::ChatMessage;
+
+fn ask(app: &mut App, question: &str) {
+    app.handle_action(Action::Summary(SummaryAction::StartQuestion));
+    assert_eq!(app.input_layer(), InputLayer::SummaryChat);
+    for character in question.chars() {
+        app.handle_action(Action::Summary(SummaryAction::Question(
+            TextAction::Insert(character),
+        )));
+    }
+    app.handle_action(Action::Summary(SummaryAction::Question(TextAction::Submit)));
+}
+
+#[tokio::test]
+async fn follow_ups_carry_the_story_context_and_the_exchange_so_far() {
+    let stream = FakeLlmStream::numbered("reply");
+    let (mut app, mut rx) = summarized_app(&stream, Vec::new(), vec![comment(11)]);
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    ask(&mut app, "pricing?");
+    assert_eq!(app.input_layer(), InputLayer::Summary);
+    finish_summary(&mut app, &mut rx).await;
+    ask(&mut app, "and support?");
+    finish_summary(&mut app, &mut rx).await;
+
+    assert_eq!(app.summary_overlay.state(), SummaryState::Done);
+    assert_eq!(app.summary_overlay.summary(), "reply 1");
+    assert_eq!(
+        app.summary_overlay.chat_answers(),
+        vec!["reply 2", "reply 3"]
+    );
+    let requests = stream.requests();
+    assert_eq!(requests[2].messages()[0], requests[0].messages()[0]);
+    assert_eq!(
+        requests[2].messages()[1..],
+        [
+            ChatMessage::assistant("reply 1"),
+            ChatMessage::user("pricing?"),
+            ChatMessage::assistant("reply 2"),
+            ChatMessage::user("and support?"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn a_cached_summary_can_be_asked_about() {
+    let stream = FakeLlmStream::numbered("reply");
+    let (mut app, mut rx) = summarized_app(&stream, Vec::new(), vec![comment(11)]);
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+    app.handle_action(Action::Summarize);
+    assert!(app.summary_overlay.cached_at().is_some());
+
+    ask(&mut app, "pricing?");
+    finish_summary(&mut app, &mut rx).await;
+
+    assert_eq!(app.summary_overlay.chat_answers(), vec!["reply 2"]);
+    let requests = stream.requests();
+    assert_eq!(requests[1].messages()[0], requests[0].messages()[0]);
+}
+
+#[test]
+fn a_blank_question_or_esc_sends_nothing() {
+    let stream = FakeLlmStream::numbered("reply");
+    let (mut app, _rx) = summarized_app(&stream, Vec::new(), vec![comment(11)]);
+    app.summary_overlay.begin(&story(1), 1);
+    app.summary_overlay
+        .show_cached("summary".to_string(), "fake/model".to_string(), 0);
+
+    ask(&mut app, "   ");
+    app.handle_action(Action::Summary(SummaryAction::StartQuestion));
+    app.handle_action(Action::Summary(SummaryAction::Question(
+        TextAction::Insert('x'),
+    )));
+    app.handle_action(Action::Summary(SummaryAction::Question(TextAction::Cancel)));
+
+    assert_eq!(app.input_layer(), InputLayer::Summary);
+    assert!(!app.tasks.is_running(TaskTarget::Summary));
+    assert!(app.summary_overlay.chat_answers().is_empty());
+}
@@ -0,0 +1,60 @@
+use super::tests::{comment, finish_summary, summarized_app, FakeLlmStream};
+use super::*;
+use crate::input::{Action, SummaryAction};
+
+fn citing_app() -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let stream = FakeLlmStream::replying("Pricing is steep [11]. Support is good [20, 99].");
+    let mut reply = comment(11);
+    reply.comment.depth = 1;
+    let mut root = comment(10);
+    root.comment.kids = vec![11];
+    root.children = vec![reply];
+    summarized_app(&stream, Vec::new(), vec![root, comment(20)])
+}
+
+#[tokio::test]
+async fn enter_on_a_citation_closes_the_summary_and_selects_the_comment() {
+    let (mut app, mut rx) = citing_app();
+    app.handle_action(Action::SelectComment(2));
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    app.handle_action(Action::Summary(SummaryAction::PreviousCitation));
+    assert_eq!(app.summary_overlay.selected_citation(), Some(20));
+    app.handle_action(Action::Summary(SummaryAction::NextCitation));
+    assert_eq!(app.summary_overlay.selected_citation(), Some(11));
+    app.handle_action(Action::Summary(SummaryAction::OpenCitation));
+
+    assert!(!app.summary_overlay.is_visible());
+    assert_eq!(app.view, View::Comments);
+    assert_eq!(app.selected_comment_id(), Some(11));
+}
+
+#[tokio::test]
+async fn enter_without_a_selected_citation_keeps_the_summary_open() {
+    let (mut app, mut rx) = citing_app();
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    app.handle_action(Action::Summary(SummaryAction::OpenCitation));
+
+    assert!(app.summary_overlay.is_visible());
+}
+
+#[tokio::test]
+async fn a_cached_summary_reopened_after_folding_still_links_reply_citations() {
+    let (mut app, mut rx) = citing_app();
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+    app.handle_action(Action::Summary(SummaryAction::Dismiss));
+
+    app.handle_action(Action::CollapseAll);
+    assert_eq!(app.comment_list.len(), 2);
+    app.handle_action(Action::Summarize);
+    app.handle_action(Action::Summary(SummaryAction::NextCitation));
+    assert_eq!(app.summary_overlay.selected_citation(), Some(11));
+    app.handle_action(Action::Summary(SummaryAction::OpenCitation));
+
+    assert_eq!(app.view, View::Comments);
+    assert_eq!(app.selected_comment_id(), Some(11));
+}
@@ -8,6 +8,7 @@ use crate::summarizer::{
 };
 use crate::ui::summary_overlay::SummaryState;
 use futures::FutureExt;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
@@ -59,16 +60,17 @@ fn app_with_http_failure(
 ) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
     let item = story(1);
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, rx) = mpsc::unbounded_channel();
     let summarize = SummarizeConfig {
         model: "fake/model".to_string(),
         api_key: None,
         base_url: None,
-        max_comments: 20,
+        max_prompt_tokens: 32_000,
+        model_prompt_tokens: BTreeMap::new(),
         include_article: false,
-        max_article_chars: 20_000,
         system_prompt: "Summarize".to_string(),
+        profiles: Vec::new(),
     };
     let config = Config::for_test_with_summarize(
         std::env::temp_dir().join("hntui-summary-friendly-error.toml"),
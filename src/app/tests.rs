@@ -1,12 +1,16 @@
 use super::test_support::{controlled_root_request, ControlledStorySource};
 use super::*;
 use crate::api::{ApiBackend, InMemorySource, Sources};
+use crate::config::{SummarizeConfig, SummaryProfile};
 use crate::input::{Action, SummaryAction};
-use crate::summarizer::{Summarizer, SummaryEvent};
+use crate::summarizer::{
+    LlmFuture, LlmSession, LlmStream, Summarizer, SummaryChunk, SummaryEvent, SummaryRequest,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
+use std::collections::BTreeMap;
 use std::num::{NonZeroU64, NonZeroUsize};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 pub(super) fn story(id: u64) -> Story {
     Story {
@@ -19,6 +23,8 @@ pub(super) fn story(id: u64) -> Story {
         time: 1,
         comment_count: 0,
         kids: vec![],
+        kind: None,
+        stale_secs: None,
     }
 }
 
@@ -34,6 +40,7 @@ pub(super) fn comment(id: u64) -> CommentNode {
             collapsed: false,
             children_loaded: true,
             children_loading: false,
+            muted: false,
         },
         children: vec![],
     }
@@ -46,11 +53,16 @@ pub(super) fn cli() -> Cli {
         cache_size: NonZeroUsize::new(100).unwrap(),
         concurrency: NonZeroUsize::new(4).unwrap(),
         no_file_cache: true,
+        offline: false,
         file_cache_dir: None,
         log_file: None,
         file_cache_ttl_secs: NonZeroU64::new(3600).unwrap(),
         api_backend: ApiBackend::HackerWeb,
         base_url: None,
+        no_failover: false,
+        failover_cooldown_secs: NonZeroU64::new(300).unwrap(),
+        watch_interval_secs: NonZeroU64::new(30).unwrap(),
+        color_depth: None,
         config: None,
         env_file: None,
     }
@@ -75,9 +87,161 @@ pub(super) fn left_click(column: u16, row: u16) -> MouseEvent {
     }
 }
 
+/// An app over `source` with the test config, no state store and no
+/// summarizer.
+pub(super) fn app_with_source(source: InMemorySource) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    app_with_parts(source, config, None, summarizer)
+}
+
+/// [`app_with_source`] for tests that bring their own config, state store or
+/// summarizer.
+pub(super) fn app_with_parts(
+    source: InMemorySource,
+    config: Config,
+    state_store: Option<StateStore>,
+    summarizer: Summarizer,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let source = Arc::new(source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, rx) = mpsc::unbounded_channel();
+    let app = App::new(
+        cli(),
+        sources,
+        tx,
+        state_store,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+    (app, rx)
+}
+
+pub(super) fn summarize_config(profiles: Vec<SummaryProfile>) -> SummarizeConfig {
+    SummarizeConfig {
+        model: "fake/model".to_string(),
+        api_key: None,
+        base_url: None,
+        max_prompt_tokens: 32_000,
+        model_prompt_tokens: BTreeMap::new(),
+        include_article: false,
+        system_prompt: "Summarize".to_string(),
+        profiles,
+    }
+}
+
+/// An LLM that answers every request in one chunk and keeps the requests
+/// for the test to inspect.
+#[derive(Clone)]
+pub(super) struct FakeLlmStream {
+    reply: &'static str,
+    numbered: bool,
+    requests: Arc<Mutex<Vec<SummaryRequest>>>,
+}
+
+impl FakeLlmStream {
+    /// Answers `reply` every time.
+    pub(super) fn replying(reply: &'static str) -> Self {
+        Self {
+            reply,
+            numbered: false,
+            requests: Arc::default(),
+        }
+    }
+
+    /// Answers `"{reply} {n}"` to the n-th request, so a test can tell
+    /// which call produced what is on screen.
+    pub(super) fn numbered(reply: &'static str) -> Self {
+        Self {
+            numbered: true,
+            ..Self::replying(reply)
+        }
+    }
+
+    pub(super) fn requests(&self) -> MutexGuard<'_, Vec<SummaryRequest>> {
+        self.requests.lock().expect("request recorder poisoned")
+    }
+}
+
+impl LlmStream for FakeLlmStream {
+    fn start(&self, request: SummaryRequest) -> LlmFuture {
+        let mut requests = self.requests();
+        requests.push(request);
+        let content = if self.numbered {
+            format!("{} {}", self.reply, requests.len())
+        } else {
+            self.reply.to_string()
+        };
+        async move {
+            Ok(LlmSession::for_test(
+                "fake/model",
+                vec![Ok(SummaryChunk {
+                    content,
+                    reasoning: String::new(),
+                })],
+            ))
+        }
+        .boxed()
+    }
+}
+
+/// [`app_with_parts`] with a summarizer that runs `config`'s `[summarize]`
+/// against `stream`.
+pub(super) fn app_with_llm(
+    source: InMemorySource,
+    config: Config,
+    state_store: Option<StateStore>,
+    stream: &FakeLlmStream,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let summarizer =
+        Summarizer::with_stream(config.summarize().cloned(), None, Arc::new(stream.clone()));
+    app_with_parts(source, config, state_store, summarizer)
+}
+
+/// Story 1 open on `comments`, ready to summarize through `stream` with
+/// `profiles` configured.
+pub(super) fn summarized_app(
+    stream: &FakeLlmStream,
+    profiles: Vec<SummaryProfile>,
+    comments: Vec<CommentNode>,
+) -> (App, mpsc::UnboundedReceiver<AppEvent>) {
+    let config = Config::for_test_with_summarize(
+        std::env::temp_dir().join("hntui-test-config.toml"),
+        summarize_config(profiles),
+    );
+    let (mut app, rx) = app_with_llm(InMemorySource::new(vec![story(1)]), config, None, stream);
+    app.apply_comments_for_story(story(1), StoryThread::from_comments(comments), true);
+    (app, rx)
+}
+
+pub(super) async fn finish_summary(app: &mut App, rx: &mut mpsc::UnboundedReceiver<AppEvent>) {
+    while app.tasks.is_running(TaskTarget::Summary) {
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("summary timed out")
+            .expect("app event channel closed");
+        app.handle_app_event(event);
+    }
+}
+
+pub(super) fn comment_ids(app: &App) -> Vec<u64> {
+    app.comment_list.iter().map(|comment| comment.id).collect()
+}
+
+/// Ids of the stories the filters and mutes leave in the list, in order.
+pub(super) fn visible_ids(app: &mut App) -> Vec<u64> {
+    (0..app.visible_story_count())
+        .map(|index| {
+            app.story_list_state.select(Some(index));
+            app.selected_story().expect("visible story").id
+        })
+        .collect()
+}
+
 fn app_with_scrollable_summary() -> App {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, _rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -104,7 +268,7 @@ fn app_with_scrollable_summary() -> App {
 #[tokio::test]
 async fn refresh_loads_initial_stories_through_the_app_event_seam() {
     let source = Arc::new(InMemorySource::new(vec![story(1), story(2)]));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -137,7 +301,7 @@ async fn refresh_loads_initial_stories_through_the_app_event_seam() {
 #[tokio::test]
 async fn opening_a_story_loads_comments_from_the_in_memory_source() {
     let source = Arc::new(InMemorySource::new(vec![story(1)]).with_comments(1, vec![comment(11)]));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -175,7 +339,7 @@ async fn a_self_post_body_delivered_with_the_discussion_reaches_the_story() {
             .with_comments(1, vec![comment(11)])
             .with_thread_text(1, "<p>the ask hn body"),
     );
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -205,10 +369,54 @@ async fn a_self_post_body_delivered_with_the_discussion_reaches_the_story() {
     assert_eq!(app.stories[0].text.as_deref(), Some("<p>the ask hn body"));
 }
 
+#[tokio::test]
+async fn a_poll_thread_keeps_its_options_until_another_story_opens() {
+    let options = vec![
+        PollOption {
+            text: "yes".to_string(),
+            score: 5,
+        },
+        PollOption {
+            text: "no".to_string(),
+            score: 2,
+        },
+    ];
+    let source = Arc::new(
+        InMemorySource::new(vec![story(1), story(2)])
+            .with_comments(1, vec![comment(11)])
+            .with_poll(1, options.clone()),
+    );
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let article_fetcher = test_article_fetcher();
+    let mut app = App::new(
+        cli(),
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        article_fetcher,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+    assert_eq!(app.comment_poll, options);
+
+    app.handle_action(Action::BackOrQuit);
+    app.handle_action(Action::MoveDown);
+    app.handle_action(Action::Enter);
+    assert!(app.comment_poll.is_empty());
+}
+
 #[tokio::test]
 async fn stale_story_result_is_dropped_after_a_refresh() {
     let source = Arc::new(InMemorySource::new(vec![story(1)]));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -241,7 +449,7 @@ async fn stale_story_result_is_dropped_after_a_refresh() {
 async fn source_error_is_surfaced_by_the_app() {
     let source =
         Arc::new(InMemorySource::new(Vec::new()).with_initial_error("fixture source failed"));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -266,7 +474,7 @@ async fn source_error_is_surfaced_by_the_app() {
 #[tokio::test]
 async fn search_uses_the_in_memory_search_source_without_pagination_state() {
     let source = Arc::new(InMemorySource::new(vec![story(1)]).with_search("rust", vec![story(9)]));
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -292,6 +500,314 @@ async fn search_uses_the_in_memory_search_source_without_pagination_state() {
     assert!(!app.has_more_stories);
 }
 
+#[tokio::test]
+async fn scrolling_near_the_end_of_search_results_appends_the_next_page_once() {
+    let (mut app, mut rx) = app_with_source(InMemorySource::new(vec![story(1)]).with_search_pages(
+        "rust",
+        vec![
+            vec![story(9), story(10), story(11), story(12), story(13)],
+            vec![story(13), story(14)],
+        ],
+    ));
+    app.search_query = "rust".to_string();
+    app.submit_search();
+    app.handle_app_event(rx.recv().await.expect("search event"));
+    assert!(app.has_more_stories);
+
+    app.story_list_state.select(Some(2));
+    app.maybe_prefetch_stories();
+    assert!(!app.tasks.is_running(TaskTarget::Search));
+
+    app.story_list_state.select(Some(4));
+    app.maybe_prefetch_stories();
+    app.handle_app_event(rx.recv().await.expect("second page event"));
+
+    assert_eq!(
+        app.stories.iter().map(|story| story.id).collect::<Vec<_>>(),
+        vec![9, 10, 11, 12, 13, 14]
+    );
+    assert!(!app.has_more_stories);
+}
+
+#[tokio::test]
+async fn a_comment_hit_opens_its_thread_with_an_unloaded_reply_selected() {
+    let mut root = comment(50);
+    root.comment.kids = vec![51];
+    let mut parent = comment(51);
+    parent.comment.depth = 1;
+    parent.comment.kids = vec![52];
+    parent.comment.collapsed = true;
+    parent.comment.children_loaded = false;
+    root.children = vec![parent];
+    let mut reply = comment(52);
+    reply.comment.depth = 2;
+    let hit = crate::api::CommentHit {
+        id: 52,
+        parent_id: 51,
+        by: "bob".to_string(),
+        text: "<p>rust is nice".to_string(),
+        time: 1,
+        story: story(5),
+    };
+    let source = Arc::new(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(5, vec![root])
+            .with_children(vec![reply])
+            .with_comment_search("rust", vec![hit]),
+    );
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let mut app = App::new(
+        cli(),
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+
+    app.handle_action(Action::StartSearch);
+    app.handle_key(key(KeyCode::Tab));
+    app.search_query = "rust".to_string();
+    app.submit_search();
+    app.handle_app_event(rx.recv().await.expect("comment search event"));
+
+    assert_eq!(app.comment_hits.len(), 1);
+    assert_eq!(app.selected_story().map(|story| story.id), Some(5));
+
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("thread event"));
+    app.handle_app_event(rx.recv().await.expect("reply event"));
+
+    assert_eq!(app.view, View::Comments);
+    let selected = app
+        .comment_list_state
+        .selected()
+        .expect("a selected comment");
+    assert_eq!(app.comment_list[selected].id, 52);
+    assert!(app.pending_comment_focus.is_none());
+}
+
+#[tokio::test]
+async fn a_comment_hit_below_the_loaded_levels_walks_up_its_parents_then_loads_down() {
+    let mut root = comment(50);
+    root.comment.kids = vec![51];
+    let mut parent = comment(51);
+    parent.comment.depth = 1;
+    parent.comment.kids = vec![52];
+    parent.comment.collapsed = true;
+    parent.comment.children_loaded = false;
+    root.children = vec![parent];
+    let mut middle = comment(52);
+    middle.comment.depth = 2;
+    middle.comment.kids = vec![53];
+    middle.comment.collapsed = true;
+    middle.comment.children_loaded = false;
+    let mut reply = comment(53);
+    reply.comment.depth = 3;
+    let hit = crate::api::CommentHit {
+        id: 53,
+        parent_id: 52,
+        by: "bob".to_string(),
+        text: "<p>rust is nice".to_string(),
+        time: 1,
+        story: story(5),
+    };
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(5, vec![root])
+            .with_children(vec![middle, reply])
+            .with_parent(52, 51),
+    );
+
+    app.open_comment_hit(hit);
+    app.handle_app_event(rx.recv().await.expect("thread event"));
+    assert!(app.tasks.is_running(TaskTarget::CommentParent(52)));
+    app.handle_app_event(rx.recv().await.expect("parent event"));
+    app.handle_app_event(rx.recv().await.expect("level 2 event"));
+    app.handle_app_event(rx.recv().await.expect("level 3 event"));
+
+    assert_eq!(comment_ids(&app), vec![50, 51, 52, 53]);
+    assert_eq!(app.selected_comment_id(), Some(53));
+    assert!(app.pending_comment_focus.is_none());
+    assert_eq!(app.last_error, None);
+}
+
+#[tokio::test]
+async fn a_comment_hit_whose_parents_run_out_is_reported() {
+    let hit = crate::api::CommentHit {
+        id: 53,
+        parent_id: 52,
+        by: "bob".to_string(),
+        text: "gone".to_string(),
+        time: 1,
+        story: story(5),
+    };
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_comments(5, vec![comment(50)]));
+
+    app.open_comment_hit(hit);
+    app.handle_app_event(rx.recv().await.expect("thread event"));
+    app.handle_app_event(rx.recv().await.expect("parent event"));
+
+    assert!(app.pending_comment_focus.is_none());
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("comment id=53 is not in the loaded thread")
+    );
+}
+
+#[test]
+fn a_malformed_search_filter_keeps_the_feed_and_reports_why() {
+    let source = Arc::new(InMemorySource::new(vec![story(1)]));
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let mut app = App::new(
+        cli(),
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+    app.stories = vec![story(1)];
+    app.search_query = "rust points>lots".to_string();
+
+    app.submit_search();
+
+    assert!(!app.search_active);
+    assert!(!app.tasks.is_running(TaskTarget::Search));
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("search: points> needs a whole number, got \"lots\"")
+    );
+}
+
+#[tokio::test]
+async fn offline_blocks_search_and_going_back_online_refreshes() {
+    let source = Arc::new(InMemorySource::new(vec![story(1)]));
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let offline_cli = Cli {
+        no_file_cache: false,
+        offline: true,
+        ..cli()
+    };
+    let mut app = App::new(
+        offline_cli,
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+
+    app.handle_action(Action::StartSearch);
+    assert!(!app.search_input_active);
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("search is unavailable offline")
+    );
+
+    app.handle_action(Action::ToggleOffline);
+    assert!(!app.offline);
+    assert_eq!(app.last_error, None);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    assert_eq!(
+        app.stories.iter().map(|story| story.id).collect::<Vec<_>>(),
+        vec![1]
+    );
+}
+
+#[tokio::test]
+async fn offline_toggle_needs_the_file_cache() {
+    let source = Arc::new(InMemorySource::default());
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let mut app = App::new(
+        cli(),
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+
+    app.handle_action(Action::ToggleOffline);
+
+    assert!(!app.offline);
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("offline mode needs the file cache")
+    );
+}
+
+#[tokio::test]
+async fn downloading_for_offline_reports_progress_and_incomplete_stories() {
+    let mut root = comment(11);
+    root.comment.kids = vec![21];
+    root.comment.children_loaded = false;
+    let linked = Story {
+        url: Some("https://example.com/post".to_string()),
+        ..story(2)
+    };
+    let source = Arc::new(
+        InMemorySource::new(vec![story(1), linked])
+            .with_comments(1, vec![root])
+            .with_children(vec![comment(21)]),
+    );
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let file_cache_cli = Cli {
+        no_file_cache: false,
+        ..cli()
+    };
+    let mut app = App::new(
+        file_cache_cli,
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        test_article_fetcher(),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::DownloadOffline);
+    assert_eq!(
+        app.offline_download,
+        Some(OfflineDownload {
+            total: 2,
+            done: 0,
+            failed: 0,
+        })
+    );
+    while app.offline_download.is_some() {
+        app.handle_app_event(rx.recv().await.expect("download event"));
+    }
+
+    assert!(!app.tasks.is_running(TaskTarget::OfflineDownload));
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("offline download: 1 of 2 stories incomplete (see log)")
+    );
+}
+
 #[tokio::test]
 async fn expanding_a_comment_loads_children_from_the_in_memory_source() {
     let mut parent = comment(11);
@@ -305,7 +821,7 @@ async fn expanding_a_comment_loads_children_from_the_in_memory_source() {
             .with_comments(1, vec![parent])
             .with_children(vec![child]),
     );
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -341,7 +857,7 @@ async fn settings_popup_open_edit_save_flows_only_through_actions() {
     let directory = tempfile::tempdir().expect("temp dir");
     let path = directory.path().join("config.toml");
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(path.clone());
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -382,7 +898,7 @@ async fn settings_popup_open_edit_save_flows_only_through_actions() {
 #[tokio::test]
 async fn feed_popup_open_move_select_uses_the_same_action_ladder() {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, _rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -408,7 +924,7 @@ async fn feed_popup_open_move_select_uses_the_same_action_ladder() {
 #[test]
 fn mouse_selection_changes_flow_through_indexed_actions() {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, _rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -434,16 +950,89 @@ fn mouse_selection_changes_flow_through_indexed_actions() {
         StoryThread::from_comments(vec![comment(11), comment(12)]),
         true,
     );
-    app.comment_layout.relayout(&app.comment_list, 80, 10, '⠋');
+    app.comment_layout
+        .relayout(&app.comment_list, 80, 10, '⠋', &HashSet::new(), "");
     app.handle_mouse(left_click(1, 2));
 
     assert_eq!(app.comment_list_state.selected(), Some(1));
 }
 
+#[test]
+fn thread_motions_walk_the_tree_and_collapse_the_whole_thread() {
+    let source = Arc::new(InMemorySource::default());
+    let sources = Sources::new(source.clone(), source.clone(), source);
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let article_fetcher = test_article_fetcher();
+    let mut app = App::new(
+        cli(),
+        sources,
+        tx,
+        None,
+        config,
+        summarizer,
+        article_fetcher,
+    );
+    let mut grandchild = comment(13);
+    grandchild.comment.depth = 2;
+    let mut child = comment(12);
+    child.comment.depth = 1;
+    child.comment.kids = vec![13];
+    child.children = vec![grandchild];
+    let mut root = comment(11);
+    root.comment.kids = vec![12];
+    root.children = vec![child];
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![root, comment(21)]),
+        true,
+    );
+    app.handle_action(Action::ExpandAll);
+    let selected_id = |app: &App| app.comment_list[app.comment_list_state.selected().unwrap()].id;
+
+    app.handle_action(Action::SelectComment(2));
+    app.handle_action(Action::ParentComment);
+    assert_eq!(selected_id(&app), 12);
+
+    app.handle_action(Action::NextRoot);
+    assert_eq!(selected_id(&app), 21);
+    app.handle_action(Action::PreviousRoot);
+    assert_eq!(selected_id(&app), 11);
+
+    app.handle_action(Action::SelectComment(2));
+    app.handle_action(Action::CollapseThread);
+    assert_eq!(selected_id(&app), 11);
+    assert_eq!(
+        app.comment_list
+            .iter()
+            .map(|comment| comment.id)
+            .collect::<Vec<_>>(),
+        vec![11, 21]
+    );
+}
+
+#[test]
+fn collapsing_a_thread_without_a_visible_root_reports_it() {
+    let (mut app, _rx) = app_with_source(InMemorySource::default());
+    let mut orphan = comment(12);
+    orphan.comment.depth = 1;
+    app.apply_comments_for_story(story(1), StoryThread::from_comments(vec![orphan]), true);
+
+    app.handle_action(Action::SelectComment(0));
+    app.handle_action(Action::CollapseThread);
+
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("thread root not found for comment id=12")
+    );
+    assert_eq!(comment_ids(&app), vec![12]);
+}
+
 #[test]
 fn summary_copy_failure_is_surfaced_on_the_app() {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, _rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -503,7 +1092,7 @@ async fn refresh_cancels_comment_prefetch_at_the_action_boundary() {
     let article_fetcher = test_article_fetcher();
     let mut app = App::new(
         cli(),
-        Sources::new(story_source, search_source),
+        Sources::new(story_source, search_source.clone(), search_source),
         tx,
         None,
         config,
@@ -552,7 +1141,7 @@ async fn foreground_navigation_supersedes_same_story_prefetch() {
     let article_fetcher = test_article_fetcher();
     let mut app = App::new(
         cli(),
-        Sources::new(story_source, search_source),
+        Sources::new(story_source, search_source.clone(), search_source),
         tx,
         None,
         config,
@@ -599,7 +1188,7 @@ async fn foreground_navigation_supersedes_same_story_prefetch() {
 #[tokio::test]
 async fn dismissing_summary_cancels_stream_and_rejects_queued_chunks() {
     let source = Arc::new(InMemorySource::default());
-    let sources = Sources::new(source.clone(), source);
+    let sources = Sources::new(source.clone(), source.clone(), source);
     let (tx, mut rx) = mpsc::unbounded_channel();
     let config = Config::for_test(std::env::temp_dir().join("hntui-test-config.toml"));
     let summarizer = Summarizer::new(None, None, reqwest::Client::new());
@@ -653,3 +1242,48 @@ async fn dismissing_summary_cancels_stream_and_rejects_queued_chunks() {
     assert!(!app.summary_overlay.is_visible());
     assert!(!app.tasks.is_running(TaskTarget::Summary));
 }
+
+#[tokio::test]
+async fn a_folded_subthread_still_counts_under_a_tight_prompt_budget() {
+    let stream = FakeLlmStream::replying("summary");
+    let summarize = SummarizeConfig {
+        max_prompt_tokens: 30,
+        ..summarize_config(Vec::new())
+    };
+    let config = Config::for_test_with_summarize(
+        std::env::temp_dir().join("hntui-test-config.toml"),
+        summarize,
+    );
+    let (mut app, mut rx) =
+        app_with_llm(InMemorySource::new(vec![story(1)]), config, None, &stream);
+    let mut argued = comment(10);
+    argued.comment.kids = vec![11, 12, 13];
+    argued.children = (11..=13)
+        .map(|id| {
+            let mut reply = comment(id);
+            reply.comment.depth = 1;
+            reply
+        })
+        .collect();
+    let mut muted = comment(40);
+    muted.comment.by = Some("troll".to_string());
+    muted.comment.muted = true;
+    app.apply_comments_for_story(
+        story(1),
+        StoryThread::from_comments(vec![comment(20), comment(30), argued, muted]),
+        true,
+    );
+    app.handle_action(Action::CollapseAll);
+    assert_eq!(comment_ids(&app), vec![20, 30, 10, 40]);
+
+    app.handle_action(Action::Summarize);
+    finish_summary(&mut app, &mut rx).await;
+
+    let prompt = stream.requests()[0].user_prompt().to_string();
+    assert!(prompt.contains("[10] bob: hello"), "prompt: {prompt}");
+    assert!(!prompt.contains("[20]"), "prompt: {prompt}");
+    assert!(
+        prompt.contains("…[5 of 6 comments left out]"),
+        "prompt: {prompt}"
+    );
+}
@@ -0,0 +1,90 @@
+use super::comment_tree::comment_ids;
+use super::App;
+use std::collections::HashSet;
+
+/// The open story's read mark as it stood when the story was opened:
+/// comments above it arrived since the last visit.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CommentVisit {
+    story_id: u64,
+    /// `None` on a first visit, when nothing counts as new.
+    since: Option<u64>,
+}
+
+impl App {
+    /// After a load into the open thread: flag comments newer than the last
+    /// visit that `known` (the tree before the load) did not have, then move
+    /// the story's mark past everything loaded.
+    pub(super) fn note_loaded_comments(&mut self, known: &HashSet<u64>) {
+        let Some(story_id) = self.current_story.as_ref().map(|story| story.id) else {
+            return;
+        };
+        let visit = match self.comment_visit {
+            Some(visit) if visit.story_id == story_id => visit,
+            _ => {
+                let visit = CommentVisit {
+                    story_id,
+                    since: self.comment_read_marks.get(&story_id).copied(),
+                };
+                self.comment_visit = Some(visit);
+                visit
+            }
+        };
+
+        let ids = comment_ids(&self.comment_tree);
+        if let Some(since) = visit.since {
+            self.new_comment_ids.extend(
+                ids.iter()
+                    .copied()
+                    .filter(|id| *id > since && !known.contains(id)),
+            );
+        }
+        let Some(newest) = ids.into_iter().max() else {
+            return;
+        };
+        if self
+            .comment_read_marks
+            .get(&story_id)
+            .is_some_and(|&mark| mark >= newest)
+        {
+            return;
+        }
+        self.comment_read_marks.insert(story_id, newest);
+        self.save_story_list_state_background();
+    }
+
+    /// Ids in the open tree, for the `known` side of `note_loaded_comments`.
+    pub(super) fn loaded_comment_ids(&self) -> HashSet<u64> {
+        comment_ids(&self.comment_tree).into_iter().collect()
+    }
+
+    /// `Tab` / `Shift+Tab`: select the next or previous unread comment in
+    /// thread order, wrapping, and open the replies hiding it.
+    pub(super) fn select_unread_comment(&mut self, forward: bool) {
+        let order = comment_ids(&self.comment_tree);
+        let current = self
+            .comment_list_state
+            .selected()
+            .and_then(|selected| self.comment_list.get(selected))
+            .and_then(|comment| order.iter().position(|&id| id == comment.id));
+        let (before, after) = match current {
+            Some(index) => (&order[..index], &order[index + 1..]),
+            None => (&order[..0], &order[..]),
+        };
+        let is_unread = |id: &&u64| self.new_comment_ids.contains(*id);
+        let target = if forward {
+            after.iter().chain(before).find(is_unread)
+        } else {
+            before
+                .iter()
+                .rev()
+                .chain(after.iter().rev())
+                .find(is_unread)
+        };
+        let Some(&target) = target else {
+            return;
+        };
+
+        self.reveal_comment(target);
+    }
+}
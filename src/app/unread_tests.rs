@@ -0,0 +1,147 @@
+use super::tests::{app_with_parts, app_with_source, comment, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::config::Config;
+use crate::input::{Action, FeedFilterAction};
+use crate::summarizer::Summarizer;
+
+fn thread() -> Vec<CommentNode> {
+    let mut parent = comment(10);
+    parent.comment.kids = vec![30];
+    parent.comment.collapsed = true;
+    let mut reply = comment(30);
+    reply.comment.depth = 1;
+    parent.children = vec![reply];
+    vec![parent, comment(20), comment(40)]
+}
+
+async fn open_first_story(app: &mut App, rx: &mut mpsc::UnboundedReceiver<AppEvent>) {
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+}
+
+#[tokio::test]
+async fn a_first_visit_flags_nothing_and_records_the_newest_comment() {
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_comments(1, thread()));
+
+    open_first_story(&mut app, &mut rx).await;
+
+    assert!(app.new_comment_ids.is_empty());
+    assert_eq!(app.comment_read_marks.get(&1), Some(&40));
+}
+
+#[tokio::test]
+async fn comments_newer_than_the_last_visit_are_flagged_and_tab_walks_them() {
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_comments(1, thread()));
+    app.comment_read_marks.insert(1, 25);
+
+    open_first_story(&mut app, &mut rx).await;
+
+    assert_eq!(app.new_comment_ids, HashSet::from([30, 40]));
+    assert_eq!(app.comment_read_marks.get(&1), Some(&40));
+
+    app.handle_action(Action::NextUnread);
+    assert_eq!(app.selected_comment_id(), Some(30));
+    app.prepare_frame(Rect::new(0, 0, 80, 24));
+    assert_eq!(app.new_comment_ids, HashSet::from([40]));
+
+    app.handle_action(Action::NextUnread);
+    assert_eq!(app.selected_comment_id(), Some(40));
+    app.prepare_frame(Rect::new(0, 0, 80, 24));
+    assert!(app.new_comment_ids.is_empty());
+
+    app.handle_action(Action::PreviousUnread);
+    assert_eq!(app.selected_comment_id(), Some(40));
+}
+
+#[tokio::test]
+async fn read_marks_are_saved_from_the_saved_feed_without_replacing_the_listing() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = StateStore::new(dir.path().to_path_buf());
+    let config = Config::for_test(dir.path().join("config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let (mut app, mut rx) = app_with_parts(
+        InMemorySource::new(vec![story(1), story(2)]).with_comments(1, thread()),
+        config,
+        Some(store.clone()),
+        summarizer,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    while app.tasks.is_running(TaskTarget::StoryStateSave) {
+        app.handle_app_event(rx.recv().await.expect("state save event"));
+    }
+    app.handle_action(Action::ToggleBookmark);
+    let saved_feed = FeedRow::ALL
+        .iter()
+        .position(|&row| row == FeedRow::Saved)
+        .expect("Saved row in the feed popup");
+    app.handle_action(Action::OpenFeedFilter);
+    app.handle_action(Action::FeedFilter(FeedFilterAction::SelectIndex(
+        saved_feed,
+    )));
+    assert!(app.saved_feed_active);
+
+    app.handle_action(Action::Enter);
+    while app.comment_loading || app.tasks.is_running(TaskTarget::StoryStateSave) {
+        app.handle_app_event(rx.recv().await.expect("task event"));
+    }
+
+    let state = store
+        .load_story_list_state()
+        .await
+        .expect("load state")
+        .expect("saved state");
+    assert_eq!(state.comment_read_marks.get(&1), Some(&40));
+    assert_eq!(state.story_ids, vec![1, 2]);
+    assert_eq!(app.last_error, None);
+}
+
+#[tokio::test]
+async fn quitting_from_the_saved_feed_keeps_the_read_marks() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = StateStore::new(dir.path().to_path_buf());
+    let config = Config::for_test(dir.path().join("config.toml"));
+    let summarizer = Summarizer::new(None, None, reqwest::Client::new());
+    let (mut app, mut rx) = app_with_parts(
+        InMemorySource::new(vec![story(1), story(2)]).with_comments(1, thread()),
+        config,
+        Some(store.clone()),
+        summarizer,
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    while app.tasks.is_running(TaskTarget::StoryStateSave) {
+        app.handle_app_event(rx.recv().await.expect("state save event"));
+    }
+    app.handle_action(Action::ToggleBookmark);
+    let saved_feed = FeedRow::ALL
+        .iter()
+        .position(|&row| row == FeedRow::Saved)
+        .expect("Saved row in the feed popup");
+    app.handle_action(Action::OpenFeedFilter);
+    app.handle_action(Action::FeedFilter(FeedFilterAction::SelectIndex(
+        saved_feed,
+    )));
+    app.handle_action(Action::Enter);
+    while app.comment_loading {
+        app.handle_app_event(rx.recv().await.expect("comments event"));
+    }
+    app.handle_action(Action::BackOrQuit);
+
+    app.tasks.cancel_and_wait(TaskTarget::StoryStateSave).await;
+    app.save_story_list_state().await.expect("save on quit");
+
+    let state = store
+        .load_story_list_state()
+        .await
+        .expect("load state")
+        .expect("saved state");
+    assert_eq!(state.comment_read_marks.get(&1), Some(&40));
+    assert!(state.seen_story_ids.contains(&1));
+    assert_eq!(state.story_ids, vec![1, 2]);
+}
@@ -0,0 +1,186 @@
+use super::list_nav::{ensure_visible, move_selection_down, move_selection_up, page_down, page_up};
+use super::{App, AppEvent, TaskTarget, View};
+use crate::api::{CommentHit, Story, UserProfile};
+use crate::input::Action;
+
+/// One line of the profile's activity list: submissions first, then
+/// comments, each newest first.
+#[derive(Debug, Clone, Copy)]
+pub enum UserRow<'a> {
+    Submission(&'a Story),
+    Comment(&'a CommentHit),
+}
+
+impl<'a> UserRow<'a> {
+    /// The story a row is about: a submission, or the one commented on.
+    pub fn story(self) -> &'a Story {
+        match self {
+            Self::Submission(story) => story,
+            Self::Comment(hit) => &hit.story,
+        }
+    }
+}
+
+impl App {
+    /// `u`: the author of the selected story, comment hit or comment.
+    pub(super) fn open_selected_author(&mut self) {
+        let author = match self.view {
+            View::Stories => self
+                .selected_comment_hit()
+                .map(|hit| hit.by.clone())
+                .or_else(|| self.selected_story().map(|story| story.by.clone())),
+            View::Comments => self
+                .comment_list_state
+                .selected()
+                .and_then(|selected| self.comment_list.get(selected))
+                .and_then(|comment| comment.by.clone()),
+            View::User => None,
+        };
+        if let Some(author) = author.filter(|author| !author.is_empty()) {
+            self.open_user(author);
+        }
+    }
+
+    pub(super) fn open_user(&mut self, id: String) {
+        if self.offline {
+            self.last_error = Some("user profiles are unavailable offline".to_string());
+            return;
+        }
+        if self.view != View::User {
+            self.user_back_view = self.view;
+            self.user_back_story = match self.view {
+                View::Comments => self.current_story.clone(),
+                View::Stories | View::User => None,
+            };
+        }
+        self.view = View::User;
+        self.last_error = None;
+        self.user_id = Some(id.clone());
+        self.user_profile = None;
+        self.user_loading = true;
+        self.user_list_state.select(Some(0));
+        *self.user_list_state.offset_mut() = 0;
+
+        let source = self.sources.users.clone();
+        self.tasks.spawn(
+            TaskTarget::User,
+            async move { source.user(id).await },
+            |task, profile| AppEvent::UserLoaded { task, profile },
+        );
+    }
+
+    pub(super) fn handle_user_loaded(&mut self, profile: UserProfile) {
+        self.user_loading = false;
+        self.last_error = profile.activity_error.clone();
+        self.user_profile = Some(profile);
+        self.user_list_state.select(Some(0));
+        *self.user_list_state.offset_mut() = 0;
+    }
+
+    pub(super) fn close_user(&mut self) {
+        self.tasks.cancel(TaskTarget::User);
+        self.user_loading = false;
+        self.view = self.user_back_view;
+        let back_story = self.user_back_story.take();
+        match self.view {
+            View::Stories => self.maybe_prefetch_comments(),
+            View::Comments => {
+                let replaced = back_story.filter(|story| {
+                    self.current_story
+                        .as_ref()
+                        .is_none_or(|current| current.id != story.id)
+                });
+                if let Some(story) = replaced {
+                    self.open_comments_for(story);
+                }
+            }
+            View::User => {}
+        }
+    }
+
+    pub fn user_row_count(&self) -> usize {
+        self.user_profile.as_ref().map_or(0, |profile| {
+            profile.submissions.len() + profile.comments.len()
+        })
+    }
+
+    pub fn user_row(&self, index: usize) -> Option<UserRow<'_>> {
+        let profile = self.user_profile.as_ref()?;
+        match profile.submissions.get(index) {
+            Some(story) => Some(UserRow::Submission(story)),
+            None => profile
+                .comments
+                .get(index - profile.submissions.len())
+                .map(UserRow::Comment),
+        }
+    }
+
+    pub(super) fn selected_user_row(&self) -> Option<UserRow<'_>> {
+        self.user_row(self.user_list_state.selected().unwrap_or(0))
+    }
+
+    /// Open the selected submission's thread, or a comment's thread with the
+    /// comment selected. Backing out of it returns here.
+    fn open_selected_user_row(&mut self) {
+        let Some(row) = self.selected_user_row() else {
+            return;
+        };
+        match row {
+            UserRow::Submission(story) => {
+                let story = story.clone();
+                self.comments_back_view = View::User;
+                self.open_comments_for(story);
+            }
+            UserRow::Comment(hit) => {
+                let hit = hit.clone();
+                self.comments_back_view = View::User;
+                self.open_comment_hit(hit);
+            }
+        }
+    }
+
+    fn ensure_selected_user_row_visible(&mut self) {
+        let count = self.user_row_count();
+        ensure_visible(&mut self.user_list_state, count, self.user_page_size);
+    }
+
+    /// The profile's share of `handle_action`: navigation and opening rows.
+    pub(super) fn handle_user_action(&mut self, action: Action) {
+        let count = self.user_row_count();
+        match action {
+            Action::BackOrQuit => {
+                self.close_user();
+                return;
+            }
+            Action::MoveDown => move_selection_down(&mut self.user_list_state, count),
+            Action::MoveUp => move_selection_up(&mut self.user_list_state),
+            Action::PageDown => page_down(&mut self.user_list_state, count, self.user_page_size),
+            Action::PageUp => page_up(&mut self.user_list_state, self.user_page_size),
+            Action::GoTop => {
+                self.user_list_state.select(Some(0));
+                *self.user_list_state.offset_mut() = 0;
+            }
+            Action::GoBottom => {
+                if count > 0 {
+                    self.user_list_state.select(Some(count - 1));
+                }
+            }
+            Action::SelectUserRow(index) => {
+                assert!(index < count, "user row selection out of range: {index}");
+                self.user_list_state.select(Some(index));
+            }
+            Action::Enter | Action::OpenComments | Action::Expand => {
+                self.open_selected_user_row();
+                return;
+            }
+            Action::Refresh => {
+                if let Some(id) = self.user_id.clone() {
+                    self.open_user(id);
+                }
+                return;
+            }
+            _ => return,
+        }
+        self.ensure_selected_user_row_visible();
+    }
+}
@@ -0,0 +1,140 @@
+use super::tests::{app_with_source, comment, comment_ids, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::input::Action;
+
+fn alice() -> UserProfile {
+    let mut commented_on = story(7);
+    commented_on.by = "carol".to_string();
+    UserProfile {
+        karma: 1234,
+        created: 1_160_418_111,
+        about: Some("hi".to_string()),
+        submissions: vec![story(1)],
+        comments: vec![CommentHit {
+            id: 71,
+            parent_id: 7,
+            by: "alice".to_string(),
+            text: "a reply".to_string(),
+            time: 1,
+            story: commented_on,
+        }],
+        activity_error: None,
+    }
+}
+
+#[tokio::test]
+async fn a_profile_opens_from_the_story_author_and_its_comments_open_in_place() {
+    let mut reply = comment(71);
+    reply.comment.by = Some("alice".to_string());
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(7, vec![comment(70), reply])
+            .with_user("alice", alice()),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::OpenUser);
+    assert_eq!(app.view, View::User);
+    assert!(app.user_loading);
+    app.handle_app_event(rx.recv().await.expect("user event"));
+
+    assert!(!app.user_loading);
+    assert_eq!(app.user_profile.as_ref().map(|p| p.karma), Some(1234));
+    assert_eq!(app.user_row_count(), 2);
+    assert!(matches!(app.user_row(0), Some(UserRow::Submission(s)) if s.id == 1));
+    assert!(matches!(app.user_row(1), Some(UserRow::Comment(c)) if c.id == 71));
+
+    app.handle_action(Action::MoveDown);
+    app.handle_action(Action::Enter);
+    assert_eq!(app.view, View::Comments);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+    assert_eq!(app.current_story.as_ref().map(|s| s.id), Some(7));
+    let selected = app
+        .comment_list_state
+        .selected()
+        .expect("a selected comment");
+    assert_eq!(app.comment_list[selected].id, 71);
+
+    app.handle_action(Action::BackOrQuit);
+    assert_eq!(app.view, View::User);
+    app.handle_action(Action::BackOrQuit);
+    assert_eq!(app.view, View::Stories);
+}
+
+#[tokio::test]
+async fn leaving_a_profile_returns_to_the_thread_it_was_opened_from() {
+    let mut by_alice = comment(10);
+    by_alice.comment.by = Some("alice".to_string());
+    let mut profile = alice();
+    profile.submissions = vec![story(2)];
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(1, vec![by_alice])
+            .with_comments(2, vec![comment(20)])
+            .with_user("alice", profile),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("thread A event"));
+
+    app.handle_action(Action::OpenUser);
+    app.handle_app_event(rx.recv().await.expect("user event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("thread B event"));
+    assert_eq!(app.current_story.as_ref().map(|s| s.id), Some(2));
+
+    app.handle_action(Action::BackOrQuit);
+    assert_eq!(app.view, View::User);
+    app.handle_action(Action::BackOrQuit);
+    assert_eq!(app.view, View::Comments);
+    if app.comment_loading {
+        app.handle_app_event(rx.recv().await.expect("thread A reload event"));
+    }
+    assert_eq!(app.current_story.as_ref().map(|s| s.id), Some(1));
+    assert_eq!(comment_ids(&app), vec![10]);
+
+    app.handle_action(Action::BackOrQuit);
+    assert_eq!(app.view, View::Stories);
+}
+
+#[tokio::test]
+async fn profiles_are_refused_offline() {
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_user("alice", alice()));
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.offline = true;
+
+    app.handle_action(Action::OpenUser);
+
+    assert_eq!(app.view, View::Stories);
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("user profiles are unavailable offline")
+    );
+}
+
+#[tokio::test]
+async fn a_profile_whose_activity_failed_shows_empty_lists_and_why() {
+    let mut profile = alice();
+    profile.submissions = Vec::new();
+    profile.comments = Vec::new();
+    profile.activity_error = Some("submissions: search is down".to_string());
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_user("alice", profile));
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+
+    app.handle_action(Action::OpenUser);
+    app.handle_app_event(rx.recv().await.expect("user event"));
+
+    assert_eq!(app.user_profile.as_ref().map(|p| p.karma), Some(1234));
+    assert_eq!(app.user_row_count(), 0);
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("submissions: search is down")
+    );
+}
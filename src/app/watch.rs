@@ -0,0 +1,169 @@
+use super::comment_tree::{info_for_comment, merge_children, merge_thread};
+use super::{App, AppEvent, TaskTarget, View};
+use crate::api::{CommentNode, StoryThread};
+use std::time::{Duration, Instant};
+
+/// The thread `w` is watching, and when it was last polled. Polls pause
+/// while another view is in front and resume on coming back.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Watch {
+    story_id: u64,
+    /// `None` until the first poll, which goes out on the next tick.
+    last_poll: Option<Instant>,
+}
+
+impl App {
+    pub fn is_watching(&self) -> bool {
+        self.watch.is_some()
+    }
+
+    pub(super) fn toggle_watch(&mut self) {
+        if self.watch.is_some() {
+            self.stop_watch();
+            return;
+        }
+        if self.offline {
+            self.last_error = Some("watching is unavailable offline".to_string());
+            return;
+        }
+        let Some(story_id) = self.current_story.as_ref().map(|story| story.id) else {
+            return;
+        };
+        self.watch = Some(Watch {
+            story_id,
+            last_poll: None,
+        });
+    }
+
+    /// Arrivals already marked stay highlighted until they are seen.
+    pub(super) fn stop_watch(&mut self) {
+        self.watch = None;
+        self.tasks.cancel(TaskTarget::WatchPoll);
+    }
+
+    pub(super) fn maybe_poll_watched_thread(&mut self) {
+        let Some(watch) = self.watch else {
+            return;
+        };
+        if self.view != View::Comments
+            || self.offline
+            || self.comment_loading
+            || self.tasks.is_running(TaskTarget::WatchPoll)
+        {
+            return;
+        }
+        let interval = Duration::from_secs(self.cli.watch_interval_secs.get());
+        if watch
+            .last_poll
+            .is_some_and(|polled| polled.elapsed() < interval)
+        {
+            return;
+        }
+        let Some(story) = self
+            .current_story
+            .clone()
+            .filter(|story| story.id == watch.story_id)
+        else {
+            self.stop_watch();
+            return;
+        };
+
+        self.watch = Some(Watch {
+            last_poll: Some(Instant::now()),
+            ..watch
+        });
+        let story_id = story.id;
+        let source = self.sources.stories.clone();
+        self.tasks.spawn(
+            TaskTarget::WatchPoll,
+            async move { source.watch_thread(story).await },
+            move |task, thread| AppEvent::WatchPolled {
+                task,
+                story_id,
+                thread,
+            },
+        );
+    }
+
+    /// Merge a poll into the open thread, keeping the selection on the
+    /// comment it was on.
+    pub(super) fn handle_watch_polled(&mut self, story_id: u64, thread: StoryThread) {
+        if self
+            .current_story
+            .as_ref()
+            .is_none_or(|story| story.id != story_id)
+        {
+            return;
+        }
+        let selected_id = self
+            .comment_list_state
+            .selected()
+            .and_then(|selected| self.comment_list.get(selected))
+            .map(|comment| comment.id);
+        if !thread.poll.is_empty() {
+            self.comment_poll = thread.poll;
+        }
+        let known = self.loaded_comment_ids();
+        let levels = self.visible_comment_levels();
+        let grown = merge_thread(
+            &mut self.comment_tree,
+            thread.comments,
+            levels,
+            &mut self.new_comment_ids,
+        );
+        self.note_loaded_comments(&known);
+        self.rebuild_comment_list(selected_id);
+        for parent_id in grown {
+            self.spawn_watch_children(parent_id);
+        }
+    }
+
+    /// Re-fetch the replies of an expanded comment that gained some below
+    /// the levels a poll reads, to merge rather than replace them.
+    fn spawn_watch_children(&mut self, parent_id: u64) {
+        if self
+            .tasks
+            .is_running(TaskTarget::CommentChildren(parent_id))
+        {
+            return;
+        }
+        let Some((depth, kids, _, _)) = info_for_comment(&self.comment_tree, parent_id) else {
+            return;
+        };
+        let depth = depth.saturating_add(1);
+        let source = self.sources.stories.clone();
+        self.tasks.spawn(
+            TaskTarget::CommentChildren(parent_id),
+            async move { source.comment_children(kids, depth).await },
+            move |task, children| AppEvent::WatchChildrenPolled { task, children },
+        );
+    }
+
+    pub(super) fn handle_watch_children_polled(
+        &mut self,
+        parent_id: u64,
+        children: Vec<CommentNode>,
+    ) {
+        let selected_id = self
+            .comment_list_state
+            .selected()
+            .and_then(|selected| self.comment_list.get(selected))
+            .map(|comment| comment.id);
+        let known = self.loaded_comment_ids();
+        let levels = self.visible_comment_levels();
+        let Some(grown) = merge_children(
+            &mut self.comment_tree,
+            parent_id,
+            children,
+            levels,
+            &mut self.new_comment_ids,
+        ) else {
+            return;
+        };
+        self.note_loaded_comments(&known);
+        self.rebuild_comment_list(selected_id);
+        for parent_id in grown {
+            self.spawn_watch_children(parent_id);
+        }
+    }
+}
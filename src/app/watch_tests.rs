@@ -0,0 +1,101 @@
+use super::tests::{app_with_source, comment, comment_ids, story};
+use super::*;
+use crate::api::InMemorySource;
+use crate::input::Action;
+
+fn with_replies(id: u64, replies: &[u64]) -> CommentNode {
+    let mut node = comment(id);
+    node.comment.kids = replies.to_vec();
+    node.children = replies
+        .iter()
+        .map(|&reply| {
+            let mut child = comment(reply);
+            child.comment.depth = 1;
+            child
+        })
+        .collect();
+    node
+}
+
+#[tokio::test]
+async fn a_watch_poll_merges_new_comments_and_keeps_collapse_and_selection() {
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(1, vec![with_replies(10, &[11]), comment(20)])
+            .with_watched_comments(
+                1,
+                vec![with_replies(10, &[11, 12]), comment(20), comment(30)],
+            ),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+    app.handle_action(Action::SelectComment(0));
+    app.handle_action(Action::Collapse);
+    app.handle_action(Action::GoBottom);
+    assert_eq!(comment_ids(&app), vec![10, 20]);
+
+    app.handle_action(Action::ToggleWatch);
+    assert!(app.is_watching());
+    app.tick();
+    app.handle_app_event(rx.recv().await.expect("watch event"));
+
+    assert_eq!(comment_ids(&app), vec![10, 20, 30]);
+    assert_eq!(app.selected_comment_id(), Some(20));
+    assert_eq!(app.new_comment_ids, HashSet::from([12, 30]));
+
+    app.handle_action(Action::MoveDown);
+    app.prepare_frame(Rect::new(0, 0, 80, 24));
+    assert_eq!(app.new_comment_ids, HashSet::from([12]));
+
+    app.handle_action(Action::ToggleWatch);
+    assert!(!app.is_watching());
+}
+
+#[tokio::test]
+async fn a_watch_poll_fetches_new_replies_below_an_expanded_comment() {
+    let mut polled = with_replies(10, &[11]);
+    polled.children[0].comment.kids = vec![12];
+    polled.children[0].comment.children_loaded = false;
+    let mut deep = comment(12);
+    deep.comment.depth = 2;
+    let (mut app, mut rx) = app_with_source(
+        InMemorySource::new(vec![story(1)])
+            .with_comments(1, vec![with_replies(10, &[11])])
+            .with_watched_comments(1, vec![polled])
+            .with_children(vec![deep]),
+    );
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+    assert_eq!(comment_ids(&app), vec![10, 11]);
+
+    app.handle_action(Action::ToggleWatch);
+    app.tick();
+    app.handle_app_event(rx.recv().await.expect("watch event"));
+    app.handle_app_event(rx.recv().await.expect("watch children event"));
+
+    assert_eq!(comment_ids(&app), vec![10, 11, 12]);
+    assert_eq!(app.new_comment_ids, HashSet::from([12]));
+}
+
+#[tokio::test]
+async fn watching_is_refused_offline() {
+    let (mut app, mut rx) =
+        app_with_source(InMemorySource::new(vec![story(1)]).with_comments(1, vec![comment(10)]));
+    app.handle_action(Action::Refresh);
+    app.handle_app_event(rx.recv().await.expect("stories event"));
+    app.handle_action(Action::Enter);
+    app.handle_app_event(rx.recv().await.expect("comments event"));
+    app.offline = true;
+
+    app.handle_action(Action::ToggleWatch);
+
+    assert!(!app.is_watching());
+    assert_eq!(
+        app.last_error.as_deref(),
+        Some("watching is unavailable offline")
+    );
+}
@@ -5,8 +5,8 @@
 //! `docs/adr/20260725-article-fetch-via-localwebrs-subprocess.md`.
 
 use crate::api::Story;
-use crate::text::hn_html_to_article_markdown;
-use serde::Deserialize;
+use crate::text::{hn_html_to_article_markdown, stable_hash};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::time::Duration;
@@ -28,7 +28,7 @@ const FETCH_TIMEOUT: Duration = Duration::from_secs(90);
 const INSTALL_HINT: &str =
     "install it with: cargo install --git https://github.com/rocrp/localwebrs";
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Article {
     pub title: Option<String>,
     pub content: String,
@@ -58,6 +58,8 @@ pub enum ArticleError {
     /// localwebrs ran but produced no usable text (paywall, captcha, PDF
     /// without pdfium, …). Never a crash — the JSON contract is informal.
     NoContent,
+    /// Offline, and the page was never fetched while online.
+    NotDownloaded,
 }
 
 impl std::fmt::Display for ArticleError {
@@ -70,6 +72,10 @@ impl std::fmt::Display for ArticleError {
             Self::TimedOut { seconds } => write!(formatter, "timed out after {seconds}s"),
             Self::Failed { message } => write!(formatter, "{message}"),
             Self::NoContent => write!(formatter, "no readable content extracted"),
+            Self::NotDownloaded => write!(
+                formatter,
+                "not downloaded for offline reading (press D while online)"
+            ),
         }
     }
 }
@@ -168,7 +174,58 @@ impl ArticleFetcher {
             });
         }
 
-        parse_visit_output(&output.stdout, &url)
+        let article = parse_visit_output(&output.stdout, &url)?;
+        self.store(&url, &article).await;
+        Ok(article)
+    }
+
+    /// The Article an earlier `fetch` of `url` saved, without running
+    /// localwebrs: offline mode must not touch the network.
+    pub async fn fetch_offline(&self, url: String) -> Result<Article, ArticleError> {
+        let path = self.stored_path(&url).ok_or(ArticleError::NotDownloaded)?;
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(ArticleError::NotDownloaded)
+            }
+            Err(error) => {
+                return Err(ArticleError::Failed {
+                    message: format!("read {}: {error}", path.display()),
+                })
+            }
+        };
+        serde_json::from_slice(&bytes).map_err(|error| ArticleError::Failed {
+            message: format!("decode {}: {error}", path.display()),
+        })
+    }
+
+    fn stored_path(&self, url: &str) -> Option<PathBuf> {
+        let dir = self.working_dir.as_ref()?;
+        Some(
+            dir.join("articles")
+                .join(format!("{:016x}.json", stable_hash(url))),
+        )
+    }
+
+    /// Best effort: a failed write only costs the offline copy.
+    async fn store(&self, url: &str, article: &Article) {
+        let Some(path) = self.stored_path(url) else {
+            return;
+        };
+        let result = async {
+            let bytes = serde_json::to_vec(article)?;
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&path, bytes).await?;
+            anyhow::Ok(())
+        };
+        if let Err(error) = result.await {
+            crate::logging::log_error(format!(
+                "failed to store article {}: {error:#}",
+                path.display()
+            ));
+        }
     }
 }
 
@@ -274,6 +331,8 @@ mod tests {
             time: 1,
             comment_count: 0,
             kids: vec![],
+            kind: None,
+            stale_secs: None,
         }
     }
 
@@ -442,6 +501,37 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn a_fetched_page_is_served_offline_without_the_binary() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let bin = fake_bin(
+            dir.path(),
+            "succeeding",
+            "#!/bin/sh\nprintf '{\"title\":\"T\",\"content\":\"body\"}'\n",
+        );
+        let working_dir = dir.path().join("cache");
+        std::fs::create_dir_all(&working_dir).expect("create working dir");
+        let online = ArticleFetcher::new(bin, Some(working_dir.clone()));
+        let offline =
+            ArticleFetcher::new("hntui-no-such-localwebrs".to_string(), Some(working_dir));
+
+        let fetched = online
+            .fetch("https://example.com/a".to_string())
+            .await
+            .expect("fake visitor succeeds");
+        let stored = offline
+            .fetch_offline("https://example.com/a".to_string())
+            .await
+            .expect("stored article");
+        let missing = offline
+            .fetch_offline("https://example.com/b".to_string())
+            .await
+            .expect_err("never fetched");
+
+        assert_eq!(stored, fetched);
+        assert!(matches!(missing, ArticleError::NotDownloaded));
+    }
+
     #[test]
     fn a_self_post_body_preserves_links_as_markdown() {
         let article = self_post_article(&story_with_text(Some(
@@ -1,17 +1,62 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct Config {
     stored: StoredConfig,
     path: PathBuf,
+    /// Keys the file still sets that hntui no longer reads.
+    retired_keys: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 struct StoredConfig {
     summarize: Option<SummarizeConfig>,
     article: Option<ArticleConfig>,
+    keys: Option<KeyBindings>,
+    theme: Option<ThemeConfig>,
+    filters: Option<BTreeMap<String, String>>,
+    feed_filters: Option<BTreeMap<String, String>>,
+    mute: Option<MuteConfig>,
+}
+
+/// `[keys.<layer>]` tables mapping a key (or `"g g"` sequence) to an
+/// action name; resolved by `input::Keymap::from_config`.
+pub type KeyBindings = BTreeMap<String, BTreeMap<String, String>>;
+
+/// `[theme]`: a built-in palette and optional per-role overrides; resolved
+/// by `ui::theme::Theme::from_config`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ThemeConfig {
+    /// frappe (default), latte / light, macchiato, mocha, or ansi.
+    pub name: Option<String>,
+    /// Colors the comment depth bars and story gradient cycle through.
+    pub rainbow: Option<Vec<String>>,
+    pub score_scale: Option<Vec<ScaleStepConfig>>,
+    pub comment_scale: Option<Vec<ScaleStepConfig>>,
+}
+
+/// One heat-map step: values at or above `min` take `color`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScaleStepConfig {
+    pub min: i64,
+    pub color: String,
+}
+
+/// `[mute]`: the killfile. Stories from these domains (subdomains
+/// included) or authors, or with a title matching one of the keywords, are
+/// hidden everywhere; comments by these users fold to a stub.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MuteConfig {
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub users: Vec<String>,
+    /// Case-insensitive regexes, like the `F` filter's bare words.
+    #[serde(default)]
+    pub keywords: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -28,16 +73,74 @@ pub struct SummarizeConfig {
     pub api_key: Option<String>,
     #[serde(default)]
     pub base_url: Option<String>,
-    #[serde(default = "default_max_comments")]
-    pub max_comments: usize,
+    /// Rough size of the story prompt, article and comments together.
+    /// Set it to what the model's context window comfortably holds.
+    #[serde(default = "default_max_prompt_tokens")]
+    pub max_prompt_tokens: usize,
+    /// `[summarize.model_prompt_tokens]`: the budget for a model, keyed by
+    /// its name as written in `model`, in place of `max_prompt_tokens`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_prompt_tokens: BTreeMap<String, usize>,
     /// Feed the story's Article to the model alongside the discussion.
     #[serde(default = "default_include_article")]
     pub include_article: bool,
-    /// Head-truncation ceiling for the Article section of the prompt.
-    #[serde(default = "default_max_article_chars")]
-    pub max_article_chars: usize,
     #[serde(default = "default_system_prompt")]
     pub system_prompt: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<SummaryProfile>,
+}
+
+/// `[[summarize.profiles]]`: a named variation on `[summarize]` the summary
+/// overlay can re-run a story with. Unset fields keep the `[summarize]` value.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SummaryProfile {
+    pub name: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub max_prompt_tokens: Option<usize>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+}
+
+impl SummarizeConfig {
+    /// The settings a summary runs with under `profile`; `None` is
+    /// `[summarize]` itself.
+    pub fn for_profile(&self, profile: Option<&str>) -> Result<Self> {
+        let Some(name) = profile else {
+            return Ok(self.clone());
+        };
+        let Some(profile) = self.profiles.iter().find(|profile| profile.name == name) else {
+            anyhow::bail!("no summary profile named {name:?} in [summarize]");
+        };
+        let mut config = self.clone();
+        if let Some(model) = &profile.model {
+            config.model = model.clone();
+        }
+        if let Some(max_prompt_tokens) = profile.max_prompt_tokens {
+            config.max_prompt_tokens = max_prompt_tokens;
+        }
+        if let Some(system_prompt) = &profile.system_prompt {
+            config.system_prompt = system_prompt.clone();
+        }
+        Ok(config)
+    }
+
+    /// The legs of the `model` fallback list, in the order they are tried.
+    pub fn models(&self) -> impl Iterator<Item = &str> {
+        self.model
+            .split(',')
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+    }
+
+    /// The prompt budget for one leg of `model`.
+    pub fn prompt_tokens_for(&self, model: &str) -> usize {
+        self.model_prompt_tokens
+            .get(model)
+            .copied()
+            .unwrap_or(self.max_prompt_tokens)
+    }
 }
 
 pub struct ConfigEdits {
@@ -73,8 +176,54 @@ impl EffectiveValue {
     }
 }
 
-pub(crate) fn default_max_comments() -> usize {
-    200
+/// The `[summarize]` budgets `max_prompt_tokens` replaced. They are read
+/// only to tell the user, since the old counts do not translate to tokens.
+#[derive(Default, Deserialize)]
+struct RetiredKeys {
+    summarize: Option<RetiredSummarizeKeys>,
+}
+
+#[derive(Default, Deserialize)]
+struct RetiredSummarizeKeys {
+    max_comments: Option<toml::Value>,
+    max_article_chars: Option<toml::Value>,
+    #[serde(default)]
+    profiles: Vec<RetiredProfileKeys>,
+}
+
+#[derive(Deserialize)]
+struct RetiredProfileKeys {
+    name: Option<String>,
+    max_comments: Option<toml::Value>,
+}
+
+impl RetiredKeys {
+    fn find(contents: &str) -> Vec<String> {
+        let Some(summarize) = toml::from_str::<Self>(contents)
+            .ok()
+            .and_then(|keys| keys.summarize)
+        else {
+            return Vec::new();
+        };
+        let mut found = Vec::new();
+        if summarize.max_comments.is_some() {
+            found.push("[summarize] max_comments".to_string());
+        }
+        if summarize.max_article_chars.is_some() {
+            found.push("[summarize] max_article_chars".to_string());
+        }
+        for profile in summarize.profiles {
+            if profile.max_comments.is_some() {
+                let name = profile.name.unwrap_or_default();
+                found.push(format!("profile {name:?} max_comments"));
+            }
+        }
+        found
+    }
+}
+
+pub(crate) fn default_max_prompt_tokens() -> usize {
+    32_000
 }
 
 fn default_article_bin() -> String {
@@ -85,10 +234,6 @@ pub(crate) fn default_include_article() -> bool {
     true
 }
 
-pub(crate) fn default_max_article_chars() -> usize {
-    20_000
-}
-
 pub(crate) fn default_system_prompt() -> String {
     "Summarize this Hacker News discussion concisely. \
      Highlight key arguments, disagreements, and consensus points. \
@@ -103,6 +248,7 @@ impl Config {
         Self {
             stored: StoredConfig::default(),
             path,
+            retired_keys: Vec::new(),
         }
     }
 
@@ -112,8 +258,14 @@ impl Config {
             stored: StoredConfig {
                 summarize: Some(summarize),
                 article: None,
+                keys: None,
+                theme: None,
+                filters: None,
+                feed_filters: None,
+                mute: None,
             },
             path,
+            retired_keys: Vec::new(),
         }
     }
 
@@ -131,14 +283,20 @@ impl Config {
             .find(|candidate| candidate.exists())
             .cloned()
             .unwrap_or(default_path);
-        let stored = if path.exists() {
+        let (stored, retired_keys) = if path.exists() {
             let contents = std::fs::read_to_string(&path)
                 .with_context(|| format!("read config {}", path.display()))?;
-            toml::from_str(&contents).with_context(|| format!("parse config {}", path.display()))?
+            let stored = toml::from_str(&contents)
+                .with_context(|| format!("parse config {}", path.display()))?;
+            (stored, RetiredKeys::find(&contents))
         } else {
-            StoredConfig::default()
+            (StoredConfig::default(), Vec::new())
         };
-        Ok(Self { stored, path })
+        Ok(Self {
+            stored,
+            path,
+            retired_keys,
+        })
     }
 
     #[cfg(test)]
@@ -150,6 +308,18 @@ impl Config {
         self.stored.summarize.as_ref()
     }
 
+    /// What to tell a user whose file still sizes summaries the old way.
+    /// Writing the file back drops the retired keys.
+    pub fn retired_keys_notice(&self) -> Option<String> {
+        (!self.retired_keys.is_empty()).then(|| {
+            format!(
+                "config: {} no longer apply; summaries are sized by max_prompt_tokens (default {})",
+                self.retired_keys.join(", "),
+                default_max_prompt_tokens()
+            )
+        })
+    }
+
     pub fn article_bin(&self) -> String {
         self.stored
             .article
@@ -158,6 +328,29 @@ impl Config {
             .unwrap_or_else(default_article_bin)
     }
 
+    pub fn keys(&self) -> Option<&KeyBindings> {
+        self.stored.keys.as_ref()
+    }
+
+    pub fn theme(&self) -> Option<&ThemeConfig> {
+        self.stored.theme.as_ref()
+    }
+
+    /// `[filters]`: story filters by name, e.g. `quality = "points>=50 -is:seen"`.
+    pub fn filters(&self) -> Option<&BTreeMap<String, String>> {
+        self.stored.filters.as_ref()
+    }
+
+    pub fn mute(&self) -> Option<&MuteConfig> {
+        self.stored.mute.as_ref()
+    }
+
+    /// `[feed_filters]`: feed name to the `[filters]` entry applied to it,
+    /// e.g. `top = "quality"`.
+    pub fn feed_filters(&self) -> Option<&BTreeMap<String, String>> {
+        self.stored.feed_filters.as_ref()
+    }
+
     pub fn effective_api_key(&self) -> EffectiveValue {
         let file_value = self
             .summarize()
@@ -216,14 +409,34 @@ impl Config {
         let next = Self {
             stored: StoredConfig {
                 summarize: Some(edits.summarize),
-                // The settings popup never edits [article]; carry it through
-                // so saving from the popup does not drop it.
+                // The settings popup edits only [summarize]; carry the other
+                // sections through so saving does not drop them.
                 article: self.stored.article.clone(),
+                keys: self.stored.keys.clone(),
+                theme: self.stored.theme.clone(),
+                filters: self.stored.filters.clone(),
+                feed_filters: self.stored.feed_filters.clone(),
+                mute: self.stored.mute.clone(),
             },
             path: self.path.clone(),
+            retired_keys: Vec::new(),
         };
-        let contents = toml::to_string_pretty(&next.stored).context("serialize config")?;
-        if let Some(parent) = next
+        next.write().await?;
+        Ok(next)
+    }
+
+    /// This config with `[mute]` replaced, for [`write`](Self::write).
+    pub fn with_mute(&self, mute: MuteConfig) -> Self {
+        let mut next = self.clone();
+        next.stored.mute = Some(mute);
+        next.retired_keys.clear();
+        next
+    }
+
+    /// Replace the file with this config, atomically.
+    pub async fn write(&self) -> Result<()> {
+        let contents = toml::to_string_pretty(&self.stored).context("serialize config")?;
+        if let Some(parent) = self
             .path
             .parent()
             .filter(|parent| !parent.as_os_str().is_empty())
@@ -236,22 +449,22 @@ impl Config {
             .duration_since(std::time::UNIX_EPOCH)
             .context("system time")?
             .as_nanos();
-        let temporary = next
+        let temporary = self
             .path
             .with_extension(format!("toml.tmp.{}.{unique}", std::process::id()));
         tokio::fs::write(&temporary, contents)
             .await
             .with_context(|| format!("write temp config {}", temporary.display()))?;
-        tokio::fs::rename(&temporary, &next.path)
+        tokio::fs::rename(&temporary, &self.path)
             .await
             .with_context(|| {
                 format!(
                     "rename config {} -> {}",
                     temporary.display(),
-                    next.path.display()
+                    self.path.display()
                 )
             })?;
-        Ok(next)
+        Ok(())
     }
 }
 
@@ -408,10 +621,11 @@ mod tests {
                     model: "openai/new".to_string(),
                     api_key: None,
                     base_url: None,
-                    max_comments: 50,
+                    max_prompt_tokens: 32_000,
+                    model_prompt_tokens: BTreeMap::new(),
                     include_article: true,
-                    max_article_chars: 20_000,
                     system_prompt: "Be terse".to_string(),
+                    profiles: Vec::new(),
                 },
             })
             .await
@@ -498,12 +712,12 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn saving_settings_keeps_the_article_section() {
+    async fn saving_settings_keeps_the_sections_it_does_not_edit() {
         let dir = tempfile::tempdir().expect("temp dir");
         let path = dir.path().join("config.toml");
         std::fs::write(
             &path,
-            "[summarize]\nmodel = \"openai/old\"\n\n[article]\nbin = \"/opt/bin/localwebrs\"\n",
+            "[summarize]\nmodel = \"openai/old\"\n\n[article]\nbin = \"/opt/bin/localwebrs\"\n\n[keys.view]\nJ = \"move-down\"\n\n[theme]\nname = \"latte\"\n\n[filters]\nquality = \"points>=50\"\n\n[feed_filters]\ntop = \"quality\"\n",
         )
         .expect("write config");
         let config = Config::load_from(vec![path.clone()], path.clone()).expect("load config");
@@ -514,10 +728,11 @@ mod tests {
                     model: "openai/new".to_string(),
                     api_key: None,
                     base_url: None,
-                    max_comments: 50,
+                    max_prompt_tokens: 32_000,
+                    model_prompt_tokens: BTreeMap::new(),
                     include_article: true,
-                    max_article_chars: 20_000,
                     system_prompt: "Be terse".to_string(),
+                    profiles: Vec::new(),
                 },
             })
             .await
@@ -526,12 +741,120 @@ mod tests {
 
         assert_eq!(saved.article_bin(), "/opt/bin/localwebrs");
         assert_eq!(reloaded.article_bin(), "/opt/bin/localwebrs");
+        assert_eq!(
+            reloaded.keys().and_then(|keys| keys.get("view")),
+            Some(&BTreeMap::from([(
+                "J".to_string(),
+                "move-down".to_string()
+            )]))
+        );
+        assert_eq!(
+            reloaded.theme().and_then(|theme| theme.name.as_deref()),
+            Some("latte")
+        );
+        assert_eq!(
+            reloaded
+                .filters()
+                .and_then(|filters| filters.get("quality"))
+                .map(String::as_str),
+            Some("points>=50")
+        );
+        assert_eq!(
+            reloaded
+                .feed_filters()
+                .and_then(|feeds| feeds.get("top"))
+                .map(String::as_str),
+            Some("quality")
+        );
         assert_eq!(
             reloaded.summarize().expect("summarize config").model,
             "openai/new"
         );
     }
 
+    #[test]
+    fn summary_profiles_override_only_the_fields_they_set() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[summarize]\nmodel = \"gemini/base\"\nmax_prompt_tokens = 80000\n\n\
+             [[summarize.profiles]]\nname = \"tl;dr\"\nmax_prompt_tokens = 8000\n\
+             system_prompt = \"One paragraph.\"\n",
+        )
+        .expect("write config");
+        let config = Config::load(Some(&path)).expect("load config");
+        let summarize = config.summarize().expect("summarize config");
+
+        let tldr = summarize.for_profile(Some("tl;dr")).expect("tl;dr profile");
+        assert_eq!(tldr.model, "gemini/base");
+        assert_eq!(tldr.max_prompt_tokens, 8000);
+        assert_eq!(tldr.system_prompt, "One paragraph.");
+        assert_eq!(
+            summarize.for_profile(None).expect("base").max_prompt_tokens,
+            80_000
+        );
+        assert_eq!(
+            summarize
+                .for_profile(Some("missing"))
+                .expect_err("unknown profile")
+                .to_string(),
+            "no summary profile named \"missing\" in [summarize]"
+        );
+    }
+
+    #[test]
+    fn a_model_budget_applies_to_that_leg_of_the_fallback_list() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[summarize]\nmodel = \"openai/gpt-4o, gemini/base\"\nmax_prompt_tokens = 8000\n\n\
+             [summarize.model_prompt_tokens]\n\"gemini/base\" = 200000\n",
+        )
+        .expect("write config");
+        let config = Config::load(Some(&path)).expect("load config");
+        let summarize = config.summarize().expect("summarize config");
+
+        assert_eq!(
+            summarize.models().collect::<Vec<_>>(),
+            vec!["openai/gpt-4o", "gemini/base"]
+        );
+        assert_eq!(summarize.prompt_tokens_for("openai/gpt-4o"), 8000);
+        assert_eq!(summarize.prompt_tokens_for("gemini/base"), 200_000);
+    }
+
+    #[test]
+    fn retired_budget_keys_load_but_are_reported() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[summarize]\nmodel = \"gemini/base\"\nmax_comments = 200\n\
+             max_article_chars = 20000\n\n\
+             [[summarize.profiles]]\nname = \"tl;dr\"\nmax_comments = 50\n",
+        )
+        .expect("write config");
+        let config = Config::load(Some(&path)).expect("load config");
+
+        assert_eq!(
+            config
+                .summarize()
+                .expect("summarize config")
+                .max_prompt_tokens,
+            32_000
+        );
+        assert_eq!(
+            config.retired_keys_notice().as_deref(),
+            Some(
+                "config: [summarize] max_comments, [summarize] max_article_chars, \
+                 profile \"tl;dr\" max_comments no longer apply; summaries are sized by \
+                 max_prompt_tokens (default 32000)"
+            )
+        );
+        assert_eq!(Config::for_test(path).retired_keys_notice(), None);
+    }
+
     #[tokio::test]
     async fn save_without_an_existing_file_uses_the_canonical_path() {
         let dir = tempfile::tempdir().expect("temp dir");
@@ -545,10 +868,11 @@ mod tests {
                     model: "gemini/test".to_string(),
                     api_key: None,
                     base_url: None,
-                    max_comments: 200,
+                    max_prompt_tokens: 32_000,
+                    model_prompt_tokens: BTreeMap::new(),
                     include_article: true,
-                    max_article_chars: 20_000,
                     system_prompt: "Summarize".to_string(),
+                    profiles: Vec::new(),
                 },
             })
             .await
@@ -4,12 +4,16 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 pub enum InputLayer {
     Help,
     Summary,
+    SummaryChat,
+    ProfilePicker,
     Article,
+    ArticleFind,
     SettingsEditor,
     Settings,
     FeedFilter,
     FilterText,
     SearchText,
+    FindText,
     View,
 }
 
@@ -32,7 +36,23 @@ pub enum SummaryAction {
     GoTop,
     GoBottom,
     Copy,
+    Regenerate,
+    PickProfile,
     OpenHelp,
+    StartQuestion,
+    Question(TextAction),
+    NextCitation,
+    PreviousCitation,
+    OpenCitation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfilePickerAction {
+    Dismiss,
+    Select,
+    SelectIndex(usize),
+    MoveDown,
+    MoveUp,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -50,6 +70,10 @@ pub enum ArticleAction {
     SelectPreviousLink,
     OpenSelectedLink,
     OpenHelp,
+    StartFind,
+    Find(TextAction),
+    NextMatch,
+    PreviousMatch,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -102,19 +126,26 @@ pub(crate) fn step_bounded(cursor: &mut usize, step: CursorStep, count: usize) {
     };
 }
 
+mod keymap;
 #[cfg(test)]
 mod routing_tests;
 
+use keymap::KeyPress;
+pub use keymap::Keymap;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Noop,
     Help(HelpAction),
     Summary(SummaryAction),
+    ProfilePicker(ProfilePickerAction),
     Article(ArticleAction),
     FeedFilter(FeedFilterAction),
     Settings(SettingsAction),
     FilterInput(TextAction),
     SearchInput(TextAction),
+    FindInput(TextAction),
+    ToggleSearchScope,
     MoveDown,
     MoveUp,
     PageDown,
@@ -138,206 +169,118 @@ pub enum Action {
     OpenFilter,
     OpenSettings,
     CopyComment,
+    ToggleBookmark,
+    ToggleOffline,
+    DownloadOffline,
+    OpenUser,
+    ToggleWatch,
+    NextUnread,
+    PreviousUnread,
+    ParentComment,
+    NextSibling,
+    PreviousSibling,
+    NextRoot,
+    PreviousRoot,
+    CollapseThread,
+    CollapseAll,
+    ExpandAll,
+    NextMatch,
+    PreviousMatch,
+    MuteDomain,
+    MuteAuthor,
+    /// Fold the thread to exactly this many levels.
+    ShowLevels(usize),
     SelectStory(usize),
     SelectComment(usize),
+    SelectUserRow(usize),
 }
 
+/// Turns key presses into actions through the active layer's bindings,
+/// holding the first key of a sequence like `gg` until the next press.
 #[derive(Debug, Default)]
 pub struct KeyState {
-    pending_g_layer: Option<InputLayer>,
+    keymap: Keymap,
+    pending: Option<(InputLayer, KeyPress)>,
 }
 
 impl KeyState {
-    pub fn on_key(&mut self, layer: InputLayer, key: KeyEvent) -> Action {
-        if !matches!(
-            layer,
-            InputLayer::Summary | InputLayer::Article | InputLayer::View
-        ) {
-            self.pending_g_layer = None;
-        }
-        match layer {
-            InputLayer::Help => match (key.code, key.modifiers) {
-                (KeyCode::Char('?'), _)
-                | (KeyCode::Esc, _)
-                | (KeyCode::Char('q'), KeyModifiers::NONE)
-                | (KeyCode::Char('c'), KeyModifiers::CONTROL) => Action::Help(HelpAction::Dismiss),
-                (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => {
-                    Action::Help(HelpAction::ScrollDown(1))
-                }
-                (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => {
-                    Action::Help(HelpAction::ScrollUp(1))
-                }
-                (KeyCode::Char('d'), KeyModifiers::CONTROL) => Action::Help(HelpAction::PageDown),
-                (KeyCode::Char('u'), KeyModifiers::CONTROL) => Action::Help(HelpAction::PageUp),
-                _ => Action::Noop,
-            },
-            InputLayer::Summary => self.summary_action(key),
-            InputLayer::Article => self.article_action(key),
-            InputLayer::FeedFilter => match (key.code, key.modifiers) {
-                (KeyCode::Esc, _) => Action::FeedFilter(FeedFilterAction::Dismiss),
-                (KeyCode::Enter, _) => Action::FeedFilter(FeedFilterAction::Select),
-                (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => {
-                    Action::FeedFilter(FeedFilterAction::MoveDown)
-                }
-                (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => {
-                    Action::FeedFilter(FeedFilterAction::MoveUp)
-                }
-                _ => Action::Noop,
-            },
-            InputLayer::Settings => match (key.code, key.modifiers) {
-                (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => {
-                    Action::Settings(SettingsAction::MoveDown)
-                }
-                (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => {
-                    Action::Settings(SettingsAction::MoveUp)
-                }
-                (KeyCode::Enter, _) => Action::Settings(SettingsAction::Activate),
-                (KeyCode::Esc, _) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
-                    Action::Settings(SettingsAction::CloseAndSave)
-                }
-                _ => Action::Noop,
-            },
-            InputLayer::SettingsEditor => settings_text_action(key)
-                .map(|action| Action::Settings(SettingsAction::Edit(action)))
-                .unwrap_or(Action::Noop),
-            InputLayer::FilterText => text_action(key)
-                .map(Action::FilterInput)
-                .unwrap_or(Action::Noop),
-            InputLayer::SearchText => text_action(key)
-                .map(Action::SearchInput)
-                .unwrap_or(Action::Noop),
-            InputLayer::View => self.view_action(key),
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            keymap,
+            pending: None,
         }
     }
 
-    fn summary_action(&mut self, key: KeyEvent) -> Action {
-        if let Some(action) = self.g_sequence_action(
-            InputLayer::Summary,
-            key,
-            Action::Summary(SummaryAction::GoTop),
-        ) {
-            return action;
-        }
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
 
-        match (key.code, key.modifiers) {
-            (KeyCode::Esc, _)
-            | (KeyCode::Char('q'), KeyModifiers::NONE)
-            | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                Action::Summary(SummaryAction::Dismiss)
-            }
-            (KeyCode::Char('?'), _) => Action::Summary(SummaryAction::OpenHelp),
-            (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => {
-                Action::Summary(SummaryAction::ScrollDown(1))
+    pub fn on_key(&mut self, layer: InputLayer, key: KeyEvent) -> Action {
+        match layer {
+            InputLayer::SettingsEditor => {
+                self.pending = None;
+                settings_text_action(key)
+                    .map(|action| Action::Settings(SettingsAction::Edit(action)))
+                    .unwrap_or(Action::Noop)
             }
-            (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => {
-                Action::Summary(SummaryAction::ScrollUp(1))
+            InputLayer::FilterText => {
+                self.pending = None;
+                text_action(key)
+                    .map(Action::FilterInput)
+                    .unwrap_or(Action::Noop)
             }
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => Action::Summary(SummaryAction::PageDown),
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => Action::Summary(SummaryAction::PageUp),
-            (KeyCode::Char('G'), KeyModifiers::SHIFT)
-            | (KeyCode::Char('G'), KeyModifiers::NONE) => Action::Summary(SummaryAction::GoBottom),
-            (KeyCode::Char('c'), KeyModifiers::NONE) => Action::Summary(SummaryAction::Copy),
-            _ => Action::Noop,
-        }
-    }
-
-    fn article_action(&mut self, key: KeyEvent) -> Action {
-        if let Some(action) = self.g_sequence_action(
-            InputLayer::Article,
-            key,
-            Action::Article(ArticleAction::GoTop),
-        ) {
-            return action;
-        }
-
-        match (key.code, key.modifiers) {
-            (KeyCode::Esc, _)
-            | (KeyCode::Char('q'), KeyModifiers::NONE)
-            | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                Action::Article(ArticleAction::Dismiss)
+            InputLayer::SearchText => {
+                self.pending = None;
+                match key.code {
+                    KeyCode::Tab | KeyCode::BackTab => Action::ToggleSearchScope,
+                    _ => text_action(key)
+                        .map(Action::SearchInput)
+                        .unwrap_or(Action::Noop),
+                }
             }
-            (KeyCode::Char('?'), _) => Action::Article(ArticleAction::OpenHelp),
-            (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => {
-                Action::Article(ArticleAction::ScrollDown(1))
+            InputLayer::FindText => {
+                self.pending = None;
+                text_action(key)
+                    .map(Action::FindInput)
+                    .unwrap_or(Action::Noop)
             }
-            (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => {
-                Action::Article(ArticleAction::ScrollUp(1))
+            InputLayer::SummaryChat => {
+                self.pending = None;
+                text_action(key)
+                    .map(|action| Action::Summary(SummaryAction::Question(action)))
+                    .unwrap_or(Action::Noop)
             }
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => Action::Article(ArticleAction::PageDown),
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => Action::Article(ArticleAction::PageUp),
-            (KeyCode::PageDown, _) => Action::Article(ArticleAction::PageDown),
-            (KeyCode::PageUp, _) => Action::Article(ArticleAction::PageUp),
-            (KeyCode::Char('G'), KeyModifiers::SHIFT)
-            | (KeyCode::Char('G'), KeyModifiers::NONE) => Action::Article(ArticleAction::GoBottom),
-            (KeyCode::Char('c'), KeyModifiers::NONE) => Action::Article(ArticleAction::Copy),
-            (KeyCode::Char('o'), KeyModifiers::NONE) => Action::Article(ArticleAction::OpenBrowser),
-            (KeyCode::BackTab, _) | (KeyCode::Tab, KeyModifiers::SHIFT) => {
-                Action::Article(ArticleAction::SelectPreviousLink)
+            InputLayer::ArticleFind => {
+                self.pending = None;
+                text_action(key)
+                    .map(|action| Action::Article(ArticleAction::Find(action)))
+                    .unwrap_or(Action::Noop)
             }
-            (KeyCode::Tab, KeyModifiers::NONE) => Action::Article(ArticleAction::SelectNextLink),
-            (KeyCode::Enter, _) => Action::Article(ArticleAction::OpenSelectedLink),
-            _ => Action::Noop,
+            InputLayer::Help
+            | InputLayer::Summary
+            | InputLayer::ProfilePicker
+            | InputLayer::Article
+            | InputLayer::FeedFilter
+            | InputLayer::Settings
+            | InputLayer::View => self.bound_action(layer, KeyPress::from_event(key)),
         }
     }
 
-    fn view_action(&mut self, key: KeyEvent) -> Action {
-        if let Some(action) = self.g_sequence_action(InputLayer::View, key, Action::GoTop) {
-            return action;
-        }
-
-        match (key.code, key.modifiers) {
-            (KeyCode::Char('?'), _) => Action::OpenHelp,
-            (KeyCode::Char('j'), KeyModifiers::NONE) | (KeyCode::Down, _) => Action::MoveDown,
-            (KeyCode::Char('k'), KeyModifiers::NONE) | (KeyCode::Up, _) => Action::MoveUp,
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => Action::PageDown,
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => Action::PageUp,
-            (KeyCode::Char('G'), KeyModifiers::SHIFT)
-            | (KeyCode::Char('G'), KeyModifiers::NONE) => Action::GoBottom,
-            (KeyCode::Enter, _) => Action::Enter,
-            (KeyCode::Char(' '), KeyModifiers::NONE) => Action::OpenComments,
-            (KeyCode::Char('o'), KeyModifiers::NONE) => Action::OpenPrimaryBrowser,
-            (KeyCode::Char('o'), KeyModifiers::SHIFT) | (KeyCode::Char('O'), _) => {
-                Action::OpenSecondaryBrowser
+    /// A press that does not finish the pending sequence is taken on its
+    /// own, so `g` then `j` still moves down.
+    fn bound_action(&mut self, layer: InputLayer, press: KeyPress) -> Action {
+        if let Some((pending_layer, first)) = self.pending.take() {
+            if pending_layer == layer {
+                if let Some(action) = self.keymap.action(layer, &[first, press]) {
+                    return action;
+                }
             }
-            (KeyCode::Char('q'), KeyModifiers::NONE)
-            | (KeyCode::Esc, _)
-            | (KeyCode::Char('c'), KeyModifiers::CONTROL) => Action::BackOrQuit,
-            (KeyCode::Char('h'), KeyModifiers::NONE) | (KeyCode::Left, _) => Action::Collapse,
-            (KeyCode::Char('l'), KeyModifiers::NONE) | (KeyCode::Right, _) => Action::Expand,
-            (KeyCode::Char('c'), KeyModifiers::NONE) => Action::ToggleCollapse,
-            (KeyCode::Char('r'), KeyModifiers::NONE) => Action::Refresh,
-            (KeyCode::Char('s'), KeyModifiers::NONE) => Action::Summarize,
-            (KeyCode::Char('v'), KeyModifiers::NONE) => Action::ViewArticle,
-            (KeyCode::Char('/'), _) => Action::StartSearch,
-            (KeyCode::Char('f'), KeyModifiers::NONE) => Action::OpenFeedFilter,
-            (KeyCode::Char('F'), KeyModifiers::SHIFT)
-            | (KeyCode::Char('F'), KeyModifiers::NONE) => Action::OpenFilter,
-            (KeyCode::Char('y'), KeyModifiers::NONE) => Action::CopyComment,
-            (KeyCode::Char(','), KeyModifiers::NONE) => Action::OpenSettings,
-            _ => Action::Noop,
         }
-    }
-
-    fn g_sequence_action(
-        &mut self,
-        layer: InputLayer,
-        key: KeyEvent,
-        go_top: Action,
-    ) -> Option<Action> {
-        if !matches!(
-            (key.code, key.modifiers),
-            (KeyCode::Char('g'), KeyModifiers::NONE)
-        ) {
-            self.pending_g_layer = None;
-            return None;
-        }
-
-        if self.pending_g_layer == Some(layer) {
-            self.pending_g_layer = None;
-            return Some(go_top);
+        if self.keymap.starts_sequence(layer, press) {
+            self.pending = Some((layer, press));
+            return Action::Noop;
         }
-        self.pending_g_layer = Some(layer);
-        Some(Action::Noop)
+        self.keymap.action(layer, &[press]).unwrap_or(Action::Noop)
     }
 }
 
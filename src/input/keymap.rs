@@ -0,0 +1,1083 @@
+use super::{
+    Action, ArticleAction, FeedFilterAction, HelpAction, InputLayer, ProfilePickerAction,
+    SettingsAction, SummaryAction,
+};
+use crate::config::KeyBindings;
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// One key press as bindings see it. Terminals disagree on whether a
+/// shifted letter carries SHIFT, so it is folded into the character, and
+/// Shift+Tab always reads as BackTab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn from_event(key: KeyEvent) -> Self {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let modifiers = key.modifiers.difference(KeyModifiers::SHIFT);
+        let code = match key.code {
+            KeyCode::Char(character) if shift => {
+                KeyCode::Char(character.to_uppercase().next().unwrap_or(character))
+            }
+            KeyCode::Tab if shift => KeyCode::BackTab,
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// `j`, `G`, `?`, `Ctrl+d`, `Shift+Tab`, `Down`, `PgUp`, `Space`, …
+    fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        let mut rest = text;
+        while let Some((prefix, tail)) = rest.split_once('+').filter(|(_, tail)| !tail.is_empty()) {
+            match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => bail!("unknown modifier {prefix:?} in {text:?}"),
+            }
+            rest = tail;
+        }
+
+        let mut characters = rest.chars();
+        let code = match (characters.next(), characters.next()) {
+            (Some(character), None) => KeyCode::Char(character),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                _ => bail!("unknown key {text:?}"),
+            },
+        };
+        let shifted = if shift {
+            modifiers | KeyModifiers::SHIFT
+        } else {
+            modifiers
+        };
+        Ok(Self::from_event(KeyEvent::new(code, shifted)))
+    }
+
+    fn label(self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(character) => character.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            code => format!("{code:?}"),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label.push_str(&key);
+        label
+    }
+}
+
+/// What a binding is pressed as: one key, or a two-key sequence like `gg`,
+/// written with a space in config (`"g g"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyChord(Vec<KeyPress>);
+
+impl KeyChord {
+    fn parse(text: &str) -> Result<Self> {
+        let presses = text
+            .split_whitespace()
+            .map(KeyPress::parse)
+            .collect::<Result<Vec<_>>>()?;
+        match presses.len() {
+            1 | 2 => Ok(Self(presses)),
+            _ => bail!("{text:?} must be one key or a two-key sequence"),
+        }
+    }
+
+    /// One chord is the other, or begins it: they cannot share a layer.
+    fn overlaps(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .all(|(left, right)| left == right)
+    }
+
+    fn label(&self) -> String {
+        self.0.iter().map(|press| press.label()).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Binding {
+    layer: InputLayer,
+    chord: KeyChord,
+    action: Action,
+}
+
+/// Every layer's bindings: the defaults below, with `[keys]` applied.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .map(|&(layer, chord, action)| Binding {
+                layer,
+                chord: KeyChord::parse(chord).expect("default bindings parse"),
+                action,
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// The defaults with each `[keys.<layer>]` entry applied in turn: a
+    /// chord bound to an action replaces whatever that chord (or a sequence
+    /// it starts) did; bound to `"none"` it does nothing.
+    pub fn from_config(keys: &KeyBindings) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (section, entries) in keys {
+            let layer = LAYER_NAMES
+                .iter()
+                .find(|(name, _)| *name == section.as_str())
+                .map(|&(_, layer)| layer)
+                .with_context(|| format!("unknown section [keys.{section}]"))?;
+            for (chord, action) in entries {
+                let chord = KeyChord::parse(chord).with_context(|| format!("[keys.{section}]"))?;
+                let action = if action == "none" {
+                    None
+                } else {
+                    let action = ACTION_NAMES
+                        .iter()
+                        .find(|&&(candidate, name, _)| {
+                            candidate == layer && name == action.as_str()
+                        })
+                        .map(|&(_, _, action)| action)
+                        .with_context(|| format!("[keys.{section}]: unknown action {action:?}"))?;
+                    Some(action)
+                };
+                keymap.bind(layer, chord, action);
+            }
+        }
+        Ok(keymap)
+    }
+
+    fn bind(&mut self, layer: InputLayer, chord: KeyChord, action: Option<Action>) {
+        self.bindings
+            .retain(|binding| binding.layer != layer || !binding.chord.overlaps(&chord));
+        if let Some(action) = action {
+            self.bindings.push(Binding {
+                layer,
+                chord,
+                action,
+            });
+        }
+    }
+
+    /// The action bound to exactly these presses. Named keys bound without
+    /// modifiers also answer with modifiers held, the way arrows always have.
+    pub(super) fn action(&self, layer: InputLayer, presses: &[KeyPress]) -> Option<Action> {
+        let exact = |binding: &&Binding| binding.layer == layer && binding.chord.0 == presses;
+        if let Some(binding) = self.bindings.iter().find(exact) {
+            return Some(binding.action);
+        }
+        match presses {
+            [press]
+                if press.modifiers != KeyModifiers::NONE
+                    && !matches!(press.code, KeyCode::Char(_)) =>
+            {
+                let bare = KeyPress {
+                    code: press.code,
+                    modifiers: KeyModifiers::NONE,
+                };
+                self.action(layer, &[bare])
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `press` begins a two-key sequence in `layer`.
+    pub(super) fn starts_sequence(&self, layer: InputLayer, press: KeyPress) -> bool {
+        self.bindings.iter().any(|binding| {
+            binding.layer == layer && binding.chord.0.len() == 2 && binding.chord.0[0] == press
+        })
+    }
+
+    /// Labels of the keys bound to `action`, in binding order, for help.
+    pub fn labels(&self, layer: InputLayer, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.layer == layer && binding.action == action)
+            .map(|binding| binding.chord.label())
+            .collect()
+    }
+}
+
+/// `[keys.<name>]` sections. The text inputs (filter, search, settings
+/// editor) keep their editing keys.
+const LAYER_NAMES: [(&str, InputLayer); 7] = [
+    ("view", InputLayer::View),
+    ("help", InputLayer::Help),
+    ("summary", InputLayer::Summary),
+    ("profile-picker", InputLayer::ProfilePicker),
+    ("article", InputLayer::Article),
+    ("feed-filter", InputLayer::FeedFilter),
+    ("settings", InputLayer::Settings),
+];
+
+/// Config names of the bindable actions: the `Action` variant in
+/// kebab-case, per layer.
+const ACTION_NAMES: &[(InputLayer, &str, Action)] = &[
+    (InputLayer::View, "open-help", Action::OpenHelp),
+    (InputLayer::View, "move-down", Action::MoveDown),
+    (InputLayer::View, "move-up", Action::MoveUp),
+    (InputLayer::View, "page-down", Action::PageDown),
+    (InputLayer::View, "page-up", Action::PageUp),
+    (InputLayer::View, "go-top", Action::GoTop),
+    (InputLayer::View, "go-bottom", Action::GoBottom),
+    (InputLayer::View, "enter", Action::Enter),
+    (InputLayer::View, "open-comments", Action::OpenComments),
+    (
+        InputLayer::View,
+        "open-primary-browser",
+        Action::OpenPrimaryBrowser,
+    ),
+    (
+        InputLayer::View,
+        "open-secondary-browser",
+        Action::OpenSecondaryBrowser,
+    ),
+    (InputLayer::View, "back-or-quit", Action::BackOrQuit),
+    (InputLayer::View, "collapse", Action::Collapse),
+    (InputLayer::View, "expand", Action::Expand),
+    (InputLayer::View, "toggle-collapse", Action::ToggleCollapse),
+    (InputLayer::View, "refresh", Action::Refresh),
+    (InputLayer::View, "summarize", Action::Summarize),
+    (InputLayer::View, "view-article", Action::ViewArticle),
+    (InputLayer::View, "start-search", Action::StartSearch),
+    (InputLayer::View, "open-feed-filter", Action::OpenFeedFilter),
+    (InputLayer::View, "open-filter", Action::OpenFilter),
+    (InputLayer::View, "open-settings", Action::OpenSettings),
+    (InputLayer::View, "copy-comment", Action::CopyComment),
+    (InputLayer::View, "toggle-bookmark", Action::ToggleBookmark),
+    (InputLayer::View, "toggle-offline", Action::ToggleOffline),
+    (
+        InputLayer::View,
+        "download-offline",
+        Action::DownloadOffline,
+    ),
+    (InputLayer::View, "open-user", Action::OpenUser),
+    (InputLayer::View, "toggle-watch", Action::ToggleWatch),
+    (InputLayer::View, "next-unread", Action::NextUnread),
+    (InputLayer::View, "previous-unread", Action::PreviousUnread),
+    (InputLayer::View, "parent-comment", Action::ParentComment),
+    (InputLayer::View, "next-sibling", Action::NextSibling),
+    (
+        InputLayer::View,
+        "previous-sibling",
+        Action::PreviousSibling,
+    ),
+    (InputLayer::View, "next-root", Action::NextRoot),
+    (InputLayer::View, "previous-root", Action::PreviousRoot),
+    (InputLayer::View, "collapse-thread", Action::CollapseThread),
+    (InputLayer::View, "next-match", Action::NextMatch),
+    (InputLayer::View, "previous-match", Action::PreviousMatch),
+    (InputLayer::View, "collapse-all", Action::CollapseAll),
+    (InputLayer::View, "expand-all", Action::ExpandAll),
+    (InputLayer::View, "mute-domain", Action::MuteDomain),
+    (InputLayer::View, "mute-author", Action::MuteAuthor),
+    (InputLayer::View, "show-levels-1", Action::ShowLevels(1)),
+    (InputLayer::View, "show-levels-2", Action::ShowLevels(2)),
+    (InputLayer::View, "show-levels-3", Action::ShowLevels(3)),
+    (InputLayer::View, "show-levels-4", Action::ShowLevels(4)),
+    (InputLayer::View, "show-levels-5", Action::ShowLevels(5)),
+    (InputLayer::View, "show-levels-6", Action::ShowLevels(6)),
+    (InputLayer::View, "show-levels-7", Action::ShowLevels(7)),
+    (InputLayer::View, "show-levels-8", Action::ShowLevels(8)),
+    (InputLayer::View, "show-levels-9", Action::ShowLevels(9)),
+    (
+        InputLayer::Help,
+        "dismiss",
+        Action::Help(HelpAction::Dismiss),
+    ),
+    (
+        InputLayer::Help,
+        "scroll-down",
+        Action::Help(HelpAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Help,
+        "scroll-up",
+        Action::Help(HelpAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Help,
+        "page-down",
+        Action::Help(HelpAction::PageDown),
+    ),
+    (
+        InputLayer::Help,
+        "page-up",
+        Action::Help(HelpAction::PageUp),
+    ),
+    (
+        InputLayer::Summary,
+        "dismiss",
+        Action::Summary(SummaryAction::Dismiss),
+    ),
+    (
+        InputLayer::Summary,
+        "scroll-down",
+        Action::Summary(SummaryAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "scroll-up",
+        Action::Summary(SummaryAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "page-down",
+        Action::Summary(SummaryAction::PageDown),
+    ),
+    (
+        InputLayer::Summary,
+        "page-up",
+        Action::Summary(SummaryAction::PageUp),
+    ),
+    (
+        InputLayer::Summary,
+        "go-top",
+        Action::Summary(SummaryAction::GoTop),
+    ),
+    (
+        InputLayer::Summary,
+        "go-bottom",
+        Action::Summary(SummaryAction::GoBottom),
+    ),
+    (
+        InputLayer::Summary,
+        "copy",
+        Action::Summary(SummaryAction::Copy),
+    ),
+    (
+        InputLayer::Summary,
+        "regenerate",
+        Action::Summary(SummaryAction::Regenerate),
+    ),
+    (
+        InputLayer::Summary,
+        "pick-profile",
+        Action::Summary(SummaryAction::PickProfile),
+    ),
+    (
+        InputLayer::Summary,
+        "open-help",
+        Action::Summary(SummaryAction::OpenHelp),
+    ),
+    (
+        InputLayer::Summary,
+        "ask",
+        Action::Summary(SummaryAction::StartQuestion),
+    ),
+    (
+        InputLayer::Summary,
+        "next-citation",
+        Action::Summary(SummaryAction::NextCitation),
+    ),
+    (
+        InputLayer::Summary,
+        "previous-citation",
+        Action::Summary(SummaryAction::PreviousCitation),
+    ),
+    (
+        InputLayer::Summary,
+        "open-citation",
+        Action::Summary(SummaryAction::OpenCitation),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "dismiss",
+        Action::ProfilePicker(ProfilePickerAction::Dismiss),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "select",
+        Action::ProfilePicker(ProfilePickerAction::Select),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "move-down",
+        Action::ProfilePicker(ProfilePickerAction::MoveDown),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "move-up",
+        Action::ProfilePicker(ProfilePickerAction::MoveUp),
+    ),
+    (
+        InputLayer::Article,
+        "dismiss",
+        Action::Article(ArticleAction::Dismiss),
+    ),
+    (
+        InputLayer::Article,
+        "scroll-down",
+        Action::Article(ArticleAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Article,
+        "scroll-up",
+        Action::Article(ArticleAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Article,
+        "page-down",
+        Action::Article(ArticleAction::PageDown),
+    ),
+    (
+        InputLayer::Article,
+        "page-up",
+        Action::Article(ArticleAction::PageUp),
+    ),
+    (
+        InputLayer::Article,
+        "go-top",
+        Action::Article(ArticleAction::GoTop),
+    ),
+    (
+        InputLayer::Article,
+        "go-bottom",
+        Action::Article(ArticleAction::GoBottom),
+    ),
+    (
+        InputLayer::Article,
+        "copy",
+        Action::Article(ArticleAction::Copy),
+    ),
+    (
+        InputLayer::Article,
+        "open-browser",
+        Action::Article(ArticleAction::OpenBrowser),
+    ),
+    (
+        InputLayer::Article,
+        "select-next-link",
+        Action::Article(ArticleAction::SelectNextLink),
+    ),
+    (
+        InputLayer::Article,
+        "select-previous-link",
+        Action::Article(ArticleAction::SelectPreviousLink),
+    ),
+    (
+        InputLayer::Article,
+        "open-selected-link",
+        Action::Article(ArticleAction::OpenSelectedLink),
+    ),
+    (
+        InputLayer::Article,
+        "open-help",
+        Action::Article(ArticleAction::OpenHelp),
+    ),
+    (
+        InputLayer::Article,
+        "start-find",
+        Action::Article(ArticleAction::StartFind),
+    ),
+    (
+        InputLayer::Article,
+        "next-match",
+        Action::Article(ArticleAction::NextMatch),
+    ),
+    (
+        InputLayer::Article,
+        "previous-match",
+        Action::Article(ArticleAction::PreviousMatch),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "dismiss",
+        Action::FeedFilter(FeedFilterAction::Dismiss),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "select",
+        Action::FeedFilter(FeedFilterAction::Select),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "move-down",
+        Action::FeedFilter(FeedFilterAction::MoveDown),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "move-up",
+        Action::FeedFilter(FeedFilterAction::MoveUp),
+    ),
+    (
+        InputLayer::Settings,
+        "move-down",
+        Action::Settings(SettingsAction::MoveDown),
+    ),
+    (
+        InputLayer::Settings,
+        "move-up",
+        Action::Settings(SettingsAction::MoveUp),
+    ),
+    (
+        InputLayer::Settings,
+        "activate",
+        Action::Settings(SettingsAction::Activate),
+    ),
+    (
+        InputLayer::Settings,
+        "close-and-save",
+        Action::Settings(SettingsAction::CloseAndSave),
+    ),
+];
+
+/// The built-in bindings, in the order help lists their keys.
+const DEFAULT_BINDINGS: &[(InputLayer, &str, Action)] = &[
+    (InputLayer::View, "?", Action::OpenHelp),
+    (InputLayer::View, "j", Action::MoveDown),
+    (InputLayer::View, "Down", Action::MoveDown),
+    (InputLayer::View, "k", Action::MoveUp),
+    (InputLayer::View, "Up", Action::MoveUp),
+    (InputLayer::View, "Ctrl+d", Action::PageDown),
+    (InputLayer::View, "Ctrl+u", Action::PageUp),
+    (InputLayer::View, "g g", Action::GoTop),
+    (InputLayer::View, "G", Action::GoBottom),
+    (InputLayer::View, "Enter", Action::Enter),
+    (InputLayer::View, "Space", Action::OpenComments),
+    (InputLayer::View, "o", Action::OpenPrimaryBrowser),
+    (InputLayer::View, "O", Action::OpenSecondaryBrowser),
+    (InputLayer::View, "q", Action::BackOrQuit),
+    (InputLayer::View, "Esc", Action::BackOrQuit),
+    (InputLayer::View, "Ctrl+c", Action::BackOrQuit),
+    (InputLayer::View, "h", Action::Collapse),
+    (InputLayer::View, "Left", Action::Collapse),
+    (InputLayer::View, "l", Action::Expand),
+    (InputLayer::View, "Right", Action::Expand),
+    (InputLayer::View, "c", Action::ToggleCollapse),
+    (InputLayer::View, "r", Action::Refresh),
+    (InputLayer::View, "s", Action::Summarize),
+    (InputLayer::View, "v", Action::ViewArticle),
+    (InputLayer::View, "/", Action::StartSearch),
+    (InputLayer::View, "f", Action::OpenFeedFilter),
+    (InputLayer::View, "F", Action::OpenFilter),
+    (InputLayer::View, "y", Action::CopyComment),
+    (InputLayer::View, "b", Action::ToggleBookmark),
+    (InputLayer::View, "u", Action::OpenUser),
+    (InputLayer::View, "w", Action::ToggleWatch),
+    (InputLayer::View, "Tab", Action::NextUnread),
+    (InputLayer::View, "Shift+Tab", Action::PreviousUnread),
+    (InputLayer::View, "p", Action::ParentComment),
+    (InputLayer::View, "]", Action::NextSibling),
+    (InputLayer::View, "[", Action::PreviousSibling),
+    (InputLayer::View, "}", Action::NextRoot),
+    (InputLayer::View, "{", Action::PreviousRoot),
+    (InputLayer::View, "C", Action::CollapseThread),
+    (InputLayer::View, "n", Action::NextMatch),
+    (InputLayer::View, "N", Action::PreviousMatch),
+    (InputLayer::View, "z M", Action::CollapseAll),
+    (InputLayer::View, "z R", Action::ExpandAll),
+    (InputLayer::View, "m d", Action::MuteDomain),
+    (InputLayer::View, "m a", Action::MuteAuthor),
+    (InputLayer::View, "z 1", Action::ShowLevels(1)),
+    (InputLayer::View, "z 2", Action::ShowLevels(2)),
+    (InputLayer::View, "z 3", Action::ShowLevels(3)),
+    (InputLayer::View, "z 4", Action::ShowLevels(4)),
+    (InputLayer::View, "z 5", Action::ShowLevels(5)),
+    (InputLayer::View, "z 6", Action::ShowLevels(6)),
+    (InputLayer::View, "z 7", Action::ShowLevels(7)),
+    (InputLayer::View, "z 8", Action::ShowLevels(8)),
+    (InputLayer::View, "z 9", Action::ShowLevels(9)),
+    (InputLayer::View, "Ctrl+o", Action::ToggleOffline),
+    (InputLayer::View, "D", Action::DownloadOffline),
+    (InputLayer::View, ",", Action::OpenSettings),
+    (InputLayer::Help, "?", Action::Help(HelpAction::Dismiss)),
+    (InputLayer::Help, "Esc", Action::Help(HelpAction::Dismiss)),
+    (InputLayer::Help, "q", Action::Help(HelpAction::Dismiss)),
+    (
+        InputLayer::Help,
+        "Ctrl+c",
+        Action::Help(HelpAction::Dismiss),
+    ),
+    (
+        InputLayer::Help,
+        "j",
+        Action::Help(HelpAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Help,
+        "Down",
+        Action::Help(HelpAction::ScrollDown(1)),
+    ),
+    (InputLayer::Help, "k", Action::Help(HelpAction::ScrollUp(1))),
+    (
+        InputLayer::Help,
+        "Up",
+        Action::Help(HelpAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Help,
+        "Ctrl+d",
+        Action::Help(HelpAction::PageDown),
+    ),
+    (InputLayer::Help, "Ctrl+u", Action::Help(HelpAction::PageUp)),
+    (
+        InputLayer::Summary,
+        "Esc",
+        Action::Summary(SummaryAction::Dismiss),
+    ),
+    (
+        InputLayer::Summary,
+        "q",
+        Action::Summary(SummaryAction::Dismiss),
+    ),
+    (
+        InputLayer::Summary,
+        "Ctrl+c",
+        Action::Summary(SummaryAction::Dismiss),
+    ),
+    (
+        InputLayer::Summary,
+        "?",
+        Action::Summary(SummaryAction::OpenHelp),
+    ),
+    (
+        InputLayer::Summary,
+        "j",
+        Action::Summary(SummaryAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "Down",
+        Action::Summary(SummaryAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "k",
+        Action::Summary(SummaryAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "Up",
+        Action::Summary(SummaryAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Summary,
+        "Ctrl+d",
+        Action::Summary(SummaryAction::PageDown),
+    ),
+    (
+        InputLayer::Summary,
+        "Ctrl+u",
+        Action::Summary(SummaryAction::PageUp),
+    ),
+    (
+        InputLayer::Summary,
+        "g g",
+        Action::Summary(SummaryAction::GoTop),
+    ),
+    (
+        InputLayer::Summary,
+        "G",
+        Action::Summary(SummaryAction::GoBottom),
+    ),
+    (
+        InputLayer::Summary,
+        "c",
+        Action::Summary(SummaryAction::Copy),
+    ),
+    (
+        InputLayer::Summary,
+        "r",
+        Action::Summary(SummaryAction::Regenerate),
+    ),
+    (
+        InputLayer::Summary,
+        "p",
+        Action::Summary(SummaryAction::PickProfile),
+    ),
+    (
+        InputLayer::Summary,
+        "a",
+        Action::Summary(SummaryAction::StartQuestion),
+    ),
+    (
+        InputLayer::Summary,
+        "Tab",
+        Action::Summary(SummaryAction::NextCitation),
+    ),
+    (
+        InputLayer::Summary,
+        "Shift+Tab",
+        Action::Summary(SummaryAction::PreviousCitation),
+    ),
+    (
+        InputLayer::Summary,
+        "Enter",
+        Action::Summary(SummaryAction::OpenCitation),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "Esc",
+        Action::ProfilePicker(ProfilePickerAction::Dismiss),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "q",
+        Action::ProfilePicker(ProfilePickerAction::Dismiss),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "Enter",
+        Action::ProfilePicker(ProfilePickerAction::Select),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "j",
+        Action::ProfilePicker(ProfilePickerAction::MoveDown),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "Down",
+        Action::ProfilePicker(ProfilePickerAction::MoveDown),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "k",
+        Action::ProfilePicker(ProfilePickerAction::MoveUp),
+    ),
+    (
+        InputLayer::ProfilePicker,
+        "Up",
+        Action::ProfilePicker(ProfilePickerAction::MoveUp),
+    ),
+    (
+        InputLayer::Article,
+        "Esc",
+        Action::Article(ArticleAction::Dismiss),
+    ),
+    (
+        InputLayer::Article,
+        "q",
+        Action::Article(ArticleAction::Dismiss),
+    ),
+    (
+        InputLayer::Article,
+        "Ctrl+c",
+        Action::Article(ArticleAction::Dismiss),
+    ),
+    (
+        InputLayer::Article,
+        "?",
+        Action::Article(ArticleAction::OpenHelp),
+    ),
+    (
+        InputLayer::Article,
+        "j",
+        Action::Article(ArticleAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Article,
+        "Down",
+        Action::Article(ArticleAction::ScrollDown(1)),
+    ),
+    (
+        InputLayer::Article,
+        "k",
+        Action::Article(ArticleAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Article,
+        "Up",
+        Action::Article(ArticleAction::ScrollUp(1)),
+    ),
+    (
+        InputLayer::Article,
+        "Ctrl+d",
+        Action::Article(ArticleAction::PageDown),
+    ),
+    (
+        InputLayer::Article,
+        "PgDn",
+        Action::Article(ArticleAction::PageDown),
+    ),
+    (
+        InputLayer::Article,
+        "Ctrl+u",
+        Action::Article(ArticleAction::PageUp),
+    ),
+    (
+        InputLayer::Article,
+        "PgUp",
+        Action::Article(ArticleAction::PageUp),
+    ),
+    (
+        InputLayer::Article,
+        "g g",
+        Action::Article(ArticleAction::GoTop),
+    ),
+    (
+        InputLayer::Article,
+        "G",
+        Action::Article(ArticleAction::GoBottom),
+    ),
+    (
+        InputLayer::Article,
+        "c",
+        Action::Article(ArticleAction::Copy),
+    ),
+    (
+        InputLayer::Article,
+        "o",
+        Action::Article(ArticleAction::OpenBrowser),
+    ),
+    (
+        InputLayer::Article,
+        "Tab",
+        Action::Article(ArticleAction::SelectNextLink),
+    ),
+    (
+        InputLayer::Article,
+        "Shift+Tab",
+        Action::Article(ArticleAction::SelectPreviousLink),
+    ),
+    (
+        InputLayer::Article,
+        "Enter",
+        Action::Article(ArticleAction::OpenSelectedLink),
+    ),
+    (
+        InputLayer::Article,
+        "/",
+        Action::Article(ArticleAction::StartFind),
+    ),
+    (
+        InputLayer::Article,
+        "n",
+        Action::Article(ArticleAction::NextMatch),
+    ),
+    (
+        InputLayer::Article,
+        "N",
+        Action::Article(ArticleAction::PreviousMatch),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "Esc",
+        Action::FeedFilter(FeedFilterAction::Dismiss),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "Enter",
+        Action::FeedFilter(FeedFilterAction::Select),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "j",
+        Action::FeedFilter(FeedFilterAction::MoveDown),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "Down",
+        Action::FeedFilter(FeedFilterAction::MoveDown),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "k",
+        Action::FeedFilter(FeedFilterAction::MoveUp),
+    ),
+    (
+        InputLayer::FeedFilter,
+        "Up",
+        Action::FeedFilter(FeedFilterAction::MoveUp),
+    ),
+    (
+        InputLayer::Settings,
+        "j",
+        Action::Settings(SettingsAction::MoveDown),
+    ),
+    (
+        InputLayer::Settings,
+        "Down",
+        Action::Settings(SettingsAction::MoveDown),
+    ),
+    (
+        InputLayer::Settings,
+        "k",
+        Action::Settings(SettingsAction::MoveUp),
+    ),
+    (
+        InputLayer::Settings,
+        "Up",
+        Action::Settings(SettingsAction::MoveUp),
+    ),
+    (
+        InputLayer::Settings,
+        "Enter",
+        Action::Settings(SettingsAction::Activate),
+    ),
+    (
+        InputLayer::Settings,
+        "Esc",
+        Action::Settings(SettingsAction::CloseAndSave),
+    ),
+    (
+        InputLayer::Settings,
+        "q",
+        Action::Settings(SettingsAction::CloseAndSave),
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyPress {
+        KeyPress::from_event(KeyEvent::new(code, modifiers))
+    }
+
+    fn keys(section: &str, entries: &[(&str, &str)]) -> KeyBindings {
+        BTreeMap::from([(
+            section.to_string(),
+            entries
+                .iter()
+                .map(|&(chord, action)| (chord.to_string(), action.to_string()))
+                .collect(),
+        )])
+    }
+
+    #[test]
+    fn every_default_binding_names_an_action_config_can_rebind() {
+        for &(layer, chord, action) in DEFAULT_BINDINGS {
+            assert!(
+                ACTION_NAMES
+                    .iter()
+                    .any(|&(named_layer, _, named)| named_layer == layer && named == action),
+                "{chord:?} in {layer:?} is bound to {action:?}, which has no name in ACTION_NAMES"
+            );
+        }
+    }
+
+    #[test]
+    fn shifted_letters_match_however_the_terminal_reports_them() {
+        let keymap = Keymap::default();
+
+        for reported in [
+            press(KeyCode::Char('G'), KeyModifiers::NONE),
+            press(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            press(KeyCode::Char('g'), KeyModifiers::SHIFT),
+        ] {
+            assert_eq!(
+                keymap.action(InputLayer::View, &[reported]),
+                Some(Action::GoBottom)
+            );
+        }
+    }
+
+    #[test]
+    fn overrides_swap_keys_and_unbind_without_touching_other_bindings() {
+        let keymap = Keymap::from_config(&keys(
+            "view",
+            &[
+                ("o", "open-secondary-browser"),
+                ("O", "open-primary-browser"),
+                ("J", "move-down"),
+                ("D", "none"),
+            ],
+        ))
+        .expect("valid keys");
+        let view = |code| keymap.action(InputLayer::View, &[press(code, KeyModifiers::NONE)]);
+
+        assert_eq!(view(KeyCode::Char('o')), Some(Action::OpenSecondaryBrowser));
+        assert_eq!(view(KeyCode::Char('O')), Some(Action::OpenPrimaryBrowser));
+        assert_eq!(view(KeyCode::Char('J')), Some(Action::MoveDown));
+        assert_eq!(view(KeyCode::Char('j')), Some(Action::MoveDown));
+        assert_eq!(view(KeyCode::Char('D')), None);
+        assert_eq!(
+            keymap.labels(InputLayer::View, Action::MoveDown),
+            vec!["j", "↓", "J"]
+        );
+    }
+
+    #[test]
+    fn a_single_key_binding_replaces_the_sequence_it_starts() {
+        let keymap = Keymap::from_config(&keys("view", &[("g", "go-top")])).expect("valid keys");
+        let g = press(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        assert!(!keymap.starts_sequence(InputLayer::View, g));
+        assert_eq!(keymap.action(InputLayer::View, &[g]), Some(Action::GoTop));
+    }
+
+    #[test]
+    fn none_unbinds_a_sequence_and_frees_its_first_key() {
+        let keymap = Keymap::from_config(&keys("view", &[("g g", "none")])).expect("valid keys");
+        let g = press(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        assert!(!keymap.starts_sequence(InputLayer::View, g));
+        assert_eq!(keymap.action(InputLayer::View, &[g, g]), None);
+        assert!(keymap.labels(InputLayer::View, Action::GoTop).is_empty());
+    }
+
+    #[test]
+    fn unknown_sections_keys_and_actions_are_reported() {
+        let errors = [
+            keys("stories", &[("j", "move-down")]),
+            keys("view", &[("Hyper+j", "move-down")]),
+            keys("view", &[("j", "fly")]),
+            keys("help", &[("j", "open-user")]),
+        ]
+        .map(|keys| {
+            format!(
+                "{:#}",
+                Keymap::from_config(&keys).expect_err("invalid keys")
+            )
+        });
+
+        assert_eq!(errors[0], "unknown section [keys.stories]");
+        assert_eq!(
+            errors[1],
+            "[keys.view]: unknown modifier \"Hyper\" in \"Hyper+j\""
+        );
+        assert_eq!(errors[2], "[keys.view]: unknown action \"fly\"");
+        assert_eq!(errors[3], "[keys.help]: unknown action \"open-user\"");
+    }
+}
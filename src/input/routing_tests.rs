@@ -28,6 +28,11 @@ fn same_key_routes_by_the_single_active_layer() {
             key(KeyCode::Esc),
             Action::FeedFilter(FeedFilterAction::Dismiss),
         ),
+        (
+            InputLayer::ProfilePicker,
+            key(KeyCode::Esc),
+            Action::ProfilePicker(ProfilePickerAction::Dismiss),
+        ),
         (
             InputLayer::Settings,
             key(KeyCode::Esc),
@@ -43,6 +48,16 @@ fn same_key_routes_by_the_single_active_layer() {
             key(KeyCode::Esc),
             Action::SearchInput(TextAction::Cancel),
         ),
+        (
+            InputLayer::FindText,
+            key(KeyCode::Esc),
+            Action::FindInput(TextAction::Cancel),
+        ),
+        (
+            InputLayer::ArticleFind,
+            key(KeyCode::Esc),
+            Action::Article(ArticleAction::Find(TextAction::Cancel)),
+        ),
     ];
 
     for (layer, key, expected) in cases {
@@ -64,6 +79,14 @@ fn question_mark_is_help_in_view_but_text_in_an_input() {
     );
 }
 
+#[test]
+fn tab_switches_what_a_search_looks_for() {
+    assert_eq!(
+        KeyState::default().on_key(InputLayer::SearchText, key(KeyCode::Tab)),
+        Action::ToggleSearchScope
+    );
+}
+
 #[test]
 fn unicode_text_editing_is_an_action() {
     assert_eq!(
@@ -281,3 +304,39 @@ fn help_scroll_keys_route_to_help_actions() {
         assert_eq!(KeyState::default().on_key(InputLayer::Help, key), expected);
     }
 }
+
+#[test]
+fn configured_keys_route_sequences_and_fall_back_to_single_keys() {
+    let config = std::collections::BTreeMap::from([(
+        "view".to_string(),
+        std::collections::BTreeMap::from([
+            ("g r".to_string(), "refresh".to_string()),
+            ("K".to_string(), "move-up".to_string()),
+        ]),
+    )]);
+    let mut keys = KeyState::new(Keymap::from_config(&config).expect("valid keys"));
+
+    assert_eq!(
+        keys.on_key(InputLayer::View, key(KeyCode::Char('g'))),
+        Action::Noop
+    );
+    assert_eq!(
+        keys.on_key(InputLayer::View, key(KeyCode::Char('r'))),
+        Action::Refresh
+    );
+    assert_eq!(
+        keys.on_key(InputLayer::View, key(KeyCode::Char('g'))),
+        Action::Noop
+    );
+    assert_eq!(
+        keys.on_key(InputLayer::View, key(KeyCode::Char('g'))),
+        Action::GoTop
+    );
+    assert_eq!(
+        keys.on_key(
+            InputLayer::View,
+            KeyEvent::new(KeyCode::Char('k'), KeyModifiers::SHIFT)
+        ),
+        Action::MoveUp
+    );
+}
@@ -13,6 +13,7 @@ mod tui;
 mod ui;
 
 use crate::api::ApiBackend;
+use crate::ui::color_depth::ColorDepth;
 use anyhow::Context;
 use clap::builder::{NonEmptyStringValueParser, TypedValueParser};
 use clap::Parser;
@@ -50,6 +51,10 @@ pub struct Cli {
     #[arg(long, default_value_t = false)]
     pub no_file_cache: bool,
 
+    /// Read only from the on-disk cache; never touch the network.
+    #[arg(long, default_value_t = false, conflicts_with = "no_file_cache")]
+    pub offline: bool,
+
     /// Directory for the on-disk item cache (defaults to OS cache dir).
     #[arg(long)]
     pub file_cache_dir: Option<PathBuf>,
@@ -62,7 +67,8 @@ pub struct Cli {
     #[arg(long, default_value = "3600")]
     pub file_cache_ttl_secs: NonZeroU64,
 
-    /// API backend: "hackerweb" (default, faster) or "firebase" (official).
+    /// API backend: "hackerweb" (default, faster), "firebase" (official) or
+    /// "algolia" (hn.algolia.com).
     #[arg(long, value_enum, default_value = "hackerweb")]
     pub api_backend: ApiBackend,
 
@@ -70,6 +76,24 @@ pub struct Cli {
     #[arg(long, value_parser = parse_nonblank)]
     pub base_url: Option<String>,
 
+    /// Do not retry failed HackerWeb requests against Firebase.
+    #[arg(long, default_value_t = false)]
+    pub no_failover: bool,
+
+    /// How long to stay on Firebase after a HackerWeb failure (seconds).
+    #[arg(long, default_value = "300")]
+    pub failover_cooldown_secs: NonZeroU64,
+
+    /// How often a watched thread (`w` in the comments view) is re-polled
+    /// (seconds).
+    #[arg(long, default_value = "30")]
+    pub watch_interval_secs: NonZeroU64,
+
+    /// Terminal colors: "truecolor", "256" or "16" (detected from COLORTERM
+    /// and TERM when omitted).
+    #[arg(long, value_enum)]
+    pub color_depth: Option<ColorDepth>,
+
     /// Config file path (searches default locations when omitted).
     #[arg(long, value_parser = NonEmptyStringValueParser::new().map(PathBuf::from))]
     pub config: Option<PathBuf>,
@@ -92,10 +116,7 @@ impl Cli {
         if let Some(url) = &self.base_url {
             return url.clone();
         }
-        match self.api_backend {
-            ApiBackend::HackerWeb => "https://api.hackerwebapp.com".to_string(),
-            ApiBackend::Firebase => "https://hacker-news.firebaseio.com/v0".to_string(),
-        }
+        self.api_backend.default_base_url().to_string()
     }
 }
 
@@ -1,6 +1,7 @@
 use crate::api::Story;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs;
@@ -8,12 +9,21 @@ use tokio::fs;
 #[derive(Debug, Clone)]
 pub(crate) struct StateStore {
     path: PathBuf,
+    bookmarks_path: PathBuf,
+    summaries_path: PathBuf,
 }
 
 /// Cap on persisted seen story IDs. HN IDs are monotonic, so we keep the
 /// highest N — older items naturally fall off as the user reads more.
 pub(crate) const SEEN_STORY_CAPACITY: usize = 10_000;
 
+/// Cap on persisted comment read marks, pruned the same way: the most
+/// recent stories keep theirs.
+pub(crate) const READ_MARK_CAPACITY: usize = 2_000;
+
+/// Cap on cached summaries; the most recently generated are kept.
+pub(crate) const SUMMARY_CACHE_CAPACITY: usize = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct StoryListState {
     pub saved_at: i64,
@@ -23,12 +33,51 @@ pub(crate) struct StoryListState {
     pub feed: Option<String>,
     #[serde(default)]
     pub seen_story_ids: Vec<u64>,
+    /// Story ID → highest comment ID loaded on the last visit. HN IDs are
+    /// monotonic, so anything above it arrived since.
+    #[serde(default)]
+    pub comment_read_marks: BTreeMap<u64, u64>,
+}
+
+/// Starred stories, most recently starred first. Whole `Story` values so the
+/// Saved feed renders without fetching anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BookmarkState {
+    stories: Vec<Story>,
+}
+
+/// What a summary was generated from. A cached summary is shown again only
+/// for the same story, model, prompt settings and number of comments loaded
+/// in the thread, however many of them are folded away.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SummaryKey {
+    pub story_id: u64,
+    pub model: String,
+    pub prompt_hash: u64,
+    pub comment_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CachedSummary {
+    pub key: SummaryKey,
+    /// The model that answered; a fallback list can make it differ from
+    /// `key.model`.
+    pub model_name: String,
+    pub summary: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SummaryCacheState {
+    summaries: Vec<CachedSummary>,
 }
 
 impl StateStore {
     pub(crate) fn new(cache_dir: PathBuf) -> Self {
         Self {
             path: cache_dir.join("state.json"),
+            bookmarks_path: cache_dir.join("bookmarks.json"),
+            summaries_path: cache_dir.join("summaries.json"),
         }
     }
 
@@ -50,6 +99,7 @@ impl StateStore {
         stories: Vec<Story>,
         feed: String,
         mut seen_story_ids: Vec<u64>,
+        mut comment_read_marks: BTreeMap<u64, u64>,
     ) -> Result<()> {
         anyhow::ensure!(
             !story_ids.is_empty(),
@@ -57,25 +107,96 @@ impl StateStore {
         );
         anyhow::ensure!(!stories.is_empty(), "refusing to save empty stories state");
 
-        // Prune the seen list: dedupe, then keep only the highest N IDs.
-        seen_story_ids.sort_unstable();
-        seen_story_ids.dedup();
-        if seen_story_ids.len() > SEEN_STORY_CAPACITY {
-            let drop = seen_story_ids.len() - SEEN_STORY_CAPACITY;
-            seen_story_ids.drain(0..drop);
-        }
-
+        prune_reading_marks(&mut seen_story_ids, &mut comment_read_marks);
         let state = StoryListState {
             saved_at: now_unix()?,
             story_ids,
             stories,
             feed: Some(feed),
             seen_story_ids,
+            comment_read_marks,
+        };
+        let bytes = serde_json::to_vec(&state).context("encode story list state")?;
+        atomic_write(&self.path, &bytes).await?;
+        Ok(())
+    }
+
+    /// Replace only the seen stories and comment read marks, keeping the
+    /// saved feed listing. Before any listing is saved there is no state to
+    /// restore them with, so nothing is written.
+    pub(crate) async fn save_reading_marks(
+        &self,
+        mut seen_story_ids: Vec<u64>,
+        mut comment_read_marks: BTreeMap<u64, u64>,
+    ) -> Result<()> {
+        let Some(mut state) = self.load_story_list_state().await? else {
+            return Ok(());
         };
+        prune_reading_marks(&mut seen_story_ids, &mut comment_read_marks);
+        state.seen_story_ids = seen_story_ids;
+        state.comment_read_marks = comment_read_marks;
         let bytes = serde_json::to_vec(&state).context("encode story list state")?;
         atomic_write(&self.path, &bytes).await?;
         Ok(())
     }
+
+    pub(crate) async fn load_bookmarks(&self) -> Result<Vec<Story>> {
+        let bytes = match fs::read(&self.bookmarks_path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => {
+                return Err(err).with_context(|| format!("read {}", self.bookmarks_path.display()))
+            }
+        };
+
+        let state: BookmarkState = serde_json::from_slice(&bytes)
+            .with_context(|| format!("decode {}", self.bookmarks_path.display()))?;
+        Ok(state.stories)
+    }
+
+    /// Unlike the story list, an empty set is a legitimate state: it is what
+    /// removing the last bookmark leaves behind.
+    pub(crate) async fn save_bookmarks(&self, stories: Vec<Story>) -> Result<()> {
+        let bytes = serde_json::to_vec(&BookmarkState { stories }).context("encode bookmarks")?;
+        atomic_write(&self.bookmarks_path, &bytes).await
+    }
+
+    pub(crate) async fn load_summaries(&self) -> Result<Vec<CachedSummary>> {
+        let bytes = match fs::read(&self.summaries_path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => {
+                return Err(err).with_context(|| format!("read {}", self.summaries_path.display()))
+            }
+        };
+
+        let state: SummaryCacheState = serde_json::from_slice(&bytes)
+            .with_context(|| format!("decode {}", self.summaries_path.display()))?;
+        Ok(state.summaries)
+    }
+
+    /// Keeps the newest `SUMMARY_CACHE_CAPACITY` summaries.
+    pub(crate) async fn save_summaries(&self, mut summaries: Vec<CachedSummary>) -> Result<()> {
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.created_at));
+        summaries.truncate(SUMMARY_CACHE_CAPACITY);
+        let bytes =
+            serde_json::to_vec(&SummaryCacheState { summaries }).context("encode summaries")?;
+        atomic_write(&self.summaries_path, &bytes).await
+    }
+}
+
+/// Dedupe the seen list and keep only its highest IDs, and the read marks
+/// of the most recent stories.
+fn prune_reading_marks(seen_story_ids: &mut Vec<u64>, comment_read_marks: &mut BTreeMap<u64, u64>) {
+    seen_story_ids.sort_unstable();
+    seen_story_ids.dedup();
+    if seen_story_ids.len() > SEEN_STORY_CAPACITY {
+        let drop = seen_story_ids.len() - SEEN_STORY_CAPACITY;
+        seen_story_ids.drain(0..drop);
+    }
+    while comment_read_marks.len() > READ_MARK_CAPACITY {
+        comment_read_marks.pop_first();
+    }
 }
 
 fn now_unix() -> Result<i64> {
@@ -160,6 +281,43 @@ mod tests {
 
         assert_eq!(state.stories[0].title, "old");
         assert_eq!(state.stories[0].text, None);
+        assert!(state.comment_read_marks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn comment_read_marks_round_trip_and_keep_the_newest_stories() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = StateStore::new(dir.path().to_path_buf());
+        let story = Story {
+            id: 1,
+            title: "marked".to_string(),
+            url: None,
+            text: None,
+            score: 1,
+            by: "alice".to_string(),
+            time: 1,
+            comment_count: 0,
+            kids: vec![],
+            kind: None,
+            stale_secs: None,
+        };
+        let marks: BTreeMap<u64, u64> = (1..=READ_MARK_CAPACITY as u64 + 1)
+            .map(|story_id| (story_id, story_id * 10))
+            .collect();
+
+        store
+            .save_story_list_state(vec![1], vec![story], "top".to_string(), vec![], marks)
+            .await
+            .expect("save state");
+        let reloaded = store
+            .load_story_list_state()
+            .await
+            .expect("load state")
+            .expect("state present");
+
+        assert_eq!(reloaded.comment_read_marks.len(), READ_MARK_CAPACITY);
+        assert_eq!(reloaded.comment_read_marks.get(&1), None);
+        assert_eq!(reloaded.comment_read_marks.get(&2), Some(&20));
     }
 
     #[tokio::test]
@@ -176,10 +334,18 @@ mod tests {
             time: 1,
             comment_count: 0,
             kids: vec![],
+            kind: None,
+            stale_secs: None,
         };
 
         store
-            .save_story_list_state(vec![1], vec![story], "ask".to_string(), vec![])
+            .save_story_list_state(
+                vec![1],
+                vec![story],
+                "ask".to_string(),
+                vec![],
+                BTreeMap::new(),
+            )
             .await
             .expect("save state");
         let reloaded = store
@@ -190,4 +356,77 @@ mod tests {
 
         assert_eq!(reloaded.stories[0].text.as_deref(), Some("<p>body"));
     }
+
+    #[tokio::test]
+    async fn bookmarks_default_to_empty_and_survive_removing_the_last_one() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = StateStore::new(dir.path().to_path_buf());
+        let story = Story {
+            id: 7,
+            title: "keep me".to_string(),
+            url: Some("https://example.com".to_string()),
+            text: None,
+            score: 3,
+            by: "carol".to_string(),
+            time: 1,
+            comment_count: 2,
+            kids: vec![8],
+            kind: None,
+            stale_secs: None,
+        };
+
+        assert!(store
+            .load_bookmarks()
+            .await
+            .expect("load missing")
+            .is_empty());
+
+        store
+            .save_bookmarks(vec![story])
+            .await
+            .expect("save bookmarks");
+        let reloaded = store.load_bookmarks().await.expect("load bookmarks");
+        assert_eq!(reloaded[0].title, "keep me");
+        assert_eq!(reloaded[0].kids, vec![8]);
+
+        store.save_bookmarks(vec![]).await.expect("save empty");
+        assert!(store.load_bookmarks().await.expect("load empty").is_empty());
+    }
+
+    #[tokio::test]
+    async fn summaries_round_trip_keeping_the_newest() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = StateStore::new(dir.path().to_path_buf());
+        let summaries: Vec<CachedSummary> = (0..=SUMMARY_CACHE_CAPACITY as i64)
+            .map(|created_at| CachedSummary {
+                key: SummaryKey {
+                    story_id: 1,
+                    model: "gemini/test".to_string(),
+                    prompt_hash: 7,
+                    comment_count: 3,
+                },
+                model_name: "gemini-test".to_string(),
+                summary: format!("summary {created_at}"),
+                created_at,
+            })
+            .collect();
+
+        assert!(store
+            .load_summaries()
+            .await
+            .expect("load missing")
+            .is_empty());
+        store
+            .save_summaries(summaries)
+            .await
+            .expect("save summaries");
+        let reloaded = store.load_summaries().await.expect("load summaries");
+
+        assert_eq!(reloaded.len(), SUMMARY_CACHE_CAPACITY);
+        assert_eq!(
+            reloaded[0].summary,
+            format!("summary {SUMMARY_CACHE_CAPACITY}")
+        );
+        assert!(reloaded.iter().all(|summary| summary.created_at > 0));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::api::types::{Comment, Story};
+use crate::api::types::{Comment, PollOption, Story};
 use crate::config::SummarizeConfig;
 use crate::text::hn_html_to_plain;
 use anyhow::Result;
@@ -39,15 +39,30 @@ impl LlmSession {
 pub(crate) struct SummaryRequest {
     model: String,
     system_prompt: String,
-    user_prompt: String,
+    /// The story context first, then any follow-up turns, ending on the
+    /// user's latest question.
+    messages: Vec<ChatMessage>,
     api_key: Option<String>,
     base_url: Option<String>,
 }
 
 #[cfg(test)]
 impl SummaryRequest {
+    /// The story context the conversation opens with.
     pub(crate) fn user_prompt(&self) -> &str {
-        &self.user_prompt
+        &self.messages[0].content
+    }
+
+    pub(crate) fn messages(&self) -> &[ChatMessage] {
+        &self.messages
+    }
+
+    pub(crate) fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub(crate) fn system_prompt(&self) -> &str {
+        &self.system_prompt
     }
 }
 
@@ -64,6 +79,38 @@ pub struct SummaryInput {
     /// Article text to ground the summary in. `None` when the toggle is off,
     /// the story has nothing to fetch, or the fetch failed.
     pub article: Option<String>,
+    /// The options and votes when the story is a poll.
+    pub poll: Vec<PollOption>,
+    /// `[[summarize.profiles]]` entry to run with; `None` is `[summarize]`.
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -115,7 +162,31 @@ impl Summarizer {
         self.api_key_override = api_key_override;
     }
 
+    /// The settings a summary under `profile` would run with; `None` when
+    /// no LLM is configured or the profile does not exist.
+    pub fn resolved_config(&self, profile: Option<&str>) -> Option<SummarizeConfig> {
+        self.config.as_ref()?.for_profile(profile).ok()
+    }
+
     pub fn summarize(&self, input: SummaryInput) -> BoxStream<'static, Result<SummaryEvent>> {
+        self.converse(input, Vec::new())
+    }
+
+    /// Continue the conversation a summary of `input` started. `transcript`
+    /// is the summary and any earlier exchanges, ending on the new question.
+    pub fn follow_up(
+        &self,
+        input: SummaryInput,
+        transcript: Vec<ChatMessage>,
+    ) -> BoxStream<'static, Result<SummaryEvent>> {
+        self.converse(input, transcript)
+    }
+
+    fn converse(
+        &self,
+        input: SummaryInput,
+        transcript: Vec<ChatMessage>,
+    ) -> BoxStream<'static, Result<SummaryEvent>> {
         let config = self.config.clone();
         let api_key_override = self.api_key_override.clone();
         let llm = self.stream.clone();
@@ -126,31 +197,62 @@ impl Summarizer {
                 ));
                 return;
             };
+            let config = match config.for_profile(input.profile.as_deref()) {
+                Ok(config) => config,
+                Err(error) => {
+                    yield Err(error);
+                    return;
+                }
+            };
             let article = input.article.as_deref().filter(|text| !text.trim().is_empty());
-            if input.comments.is_empty() && article.is_none() {
+            if input.comments.is_empty() && article.is_none() && input.poll.is_empty() {
                 yield Err(anyhow::anyhow!("No comments to summarize"));
                 return;
             }
 
-            let request = SummaryRequest {
-                model: config.model,
-                system_prompt: config.system_prompt,
-                user_prompt: build_prompt(
+            // Each leg of a fallback list gets a prompt sized by its own
+            // budget, so the legs are tried here rather than inside smolllm.
+            let models: Vec<String> = config.models().map(str::to_string).collect();
+            let mut started = None;
+            let mut last_error = None;
+            for model in models {
+                let context = build_prompt(
                     &input.story,
                     &input.comments,
                     article,
-                    config.max_comments,
-                    config.max_article_chars,
-                ),
-                api_key: api_key_override,
-                base_url: config.base_url,
-            };
-            let mut session = match llm.start(request).await {
-                Ok(session) => session,
-                Err(error) => {
+                    &input.poll,
+                    config.prompt_tokens_for(&model),
+                );
+                let request = SummaryRequest {
+                    model: model.clone(),
+                    system_prompt: config.system_prompt.clone(),
+                    messages: std::iter::once(ChatMessage::user(context))
+                        .chain(transcript.iter().cloned())
+                        .collect(),
+                    api_key: api_key_override.clone(),
+                    base_url: config.base_url.clone(),
+                };
+                match llm.start(request).await {
+                    Ok(session) => {
+                        started = Some(session);
+                        break;
+                    }
+                    Err(error) => {
+                        crate::logging::log_error(format!("summary model {model} failed: {error}"));
+                        last_error = Some(error);
+                    }
+                }
+            }
+            let mut session = match (started, last_error) {
+                (Some(session), _) => session,
+                (None, Some(error)) => {
                     yield Err(summary_llm_error(error));
                     return;
                 }
+                (None, None) => {
+                    yield Err(anyhow::anyhow!("no model set in [summarize]"));
+                    return;
+                }
             };
             yield Ok(SummaryEvent::Started {
                 model: session.model,
@@ -187,7 +289,7 @@ impl LlmStream for SmolLlmStream {
     fn start(&self, request: SummaryRequest) -> LlmFuture {
         let http = self.http.clone();
         Box::pin(async move {
-            let mut builder = smolllm::stream(request.user_prompt)
+            let mut builder = smolllm::stream(flatten_messages(request.messages))
                 .model(&request.model)
                 .system_prompt(&request.system_prompt)
                 .http_client(http);
@@ -214,39 +316,181 @@ impl LlmStream for SmolLlmStream {
     }
 }
 
+/// smolllm takes a single user prompt, so a follow-up goes out as the story
+/// context with the exchange so far replayed beneath it.
+fn flatten_messages(messages: Vec<ChatMessage>) -> String {
+    let mut messages = messages.into_iter();
+    let mut prompt = messages
+        .next()
+        .map(|message| message.content)
+        .unwrap_or_default();
+    let mut turns = messages.peekable();
+    if turns.peek().is_none() {
+        return prompt;
+    }
+    prompt.push_str("## Conversation\n\n");
+    for message in turns {
+        let speaker = match message.role {
+            ChatRole::User => "User",
+            ChatRole::Assistant => "Assistant",
+        };
+        prompt.push_str(&format!("{speaker}: {}\n\n", message.content.trim()));
+    }
+    prompt.push_str("Answer the user's last message, using the story above.");
+    prompt
+}
+
+/// A rough average for English prose: close enough to keep a prompt inside
+/// a context window, not to bill by.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// How much of the budget the article may take when there are comments too.
+/// Whatever a shorter article leaves over goes to the comments.
+const ARTICLE_SHARE_PERCENT: usize = 40;
+
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
 fn build_prompt(
     story: &Story,
     comments: &[Comment],
     article: Option<&str>,
-    max_comments: usize,
-    max_article_chars: usize,
+    poll: &[PollOption],
+    max_tokens: usize,
 ) -> String {
     let mut prompt = format!("# {}\n\n", story.title);
 
+    let mut poll_section = String::new();
+    if !poll.is_empty() {
+        poll_section.push_str("## Poll\n\n");
+        for option in poll {
+            let text = hn_html_to_plain(&option.text);
+            poll_section.push_str(&format!("- {text}: {} votes\n", option.score));
+        }
+        poll_section.push('\n');
+    }
+
     if let Some(article) = article {
+        let available =
+            max_tokens.saturating_sub(estimate_tokens(&prompt) + estimate_tokens(&poll_section));
+        let article_tokens = if comments.is_empty() {
+            available
+        } else {
+            available * ARTICLE_SHARE_PERCENT / 100
+        };
         prompt.push_str("## Article\n\n");
-        prompt.push_str(&truncate_article(article, max_article_chars));
+        prompt.push_str(&truncate_article(article, article_tokens * CHARS_PER_TOKEN));
         prompt.push_str("\n\n");
     }
+    prompt.push_str(&poll_section);
 
     if comments.is_empty() {
         return prompt;
     }
 
-    // Only label the comments once there is another section to tell them from;
-    // an article-less prompt stays byte-identical to the pre-Article shape.
-    if article.is_some() {
-        prompt.push_str("## Comments\n\n");
+    // Only label the comments once there is another section to tell them from.
+    let heading = if article.is_some() || !poll.is_empty() {
+        "## Comments\n\n"
+    } else {
+        ""
+    };
+    // IDs let the summary cite the comments it draws on, and the overlay
+    // turn those citations into jumps.
+    let lines: Vec<String> = comments
+        .iter()
+        .map(|comment| {
+            let author = comment.by.as_deref().unwrap_or("[anon]");
+            let indent = "  ".repeat(comment.depth);
+            let text = hn_html_to_plain(&comment.text);
+            format!("{indent}[{}] {author}: {text}\n\n", comment.id)
+        })
+        .collect();
+    let cite =
+        |id: u64| format!("Cite the comments you draw on by their bracketed ID, like [{id}].\n\n");
+    let used =
+        estimate_tokens(&prompt) + estimate_tokens(heading) + estimate_tokens(&cite(u64::MAX));
+    let kept = select_comments(comments, &lines, max_tokens.saturating_sub(used));
+    let Some(first) = kept.iter().position(|&keep| keep) else {
+        return prompt;
+    };
+
+    prompt.push_str(heading);
+    for (line, &keep) in lines.iter().zip(&kept) {
+        if keep {
+            prompt.push_str(line);
+        }
     }
-    for comment in comments.iter().take(max_comments) {
-        let author = comment.by.as_deref().unwrap_or("[anon]");
-        let indent = "  ".repeat(comment.depth);
-        let text = hn_html_to_plain(&comment.text);
-        prompt.push_str(&format!("{indent}{author}: {text}\n\n"));
+    let left_out = kept.iter().filter(|&&keep| !keep).count();
+    if left_out > 0 {
+        prompt.push_str(&format!(
+            "…[{left_out} of {} comments left out]\n\n",
+            comments.len()
+        ));
     }
+    prompt.push_str(&cite(comments[first].id));
     prompt
 }
 
+/// How many replies a top-level comment counts as heading beyond its own,
+/// when ranked against the replies of other threads.
+const TOP_LEVEL_WEIGHT: usize = 3;
+
+/// Which of `comments` (flattened in thread order, rendered as `lines`) fit
+/// in `budget` tokens. Comments heading the biggest subthreads go first, then
+/// shallower ones, with top-level comments weighted up, so a tight budget
+/// keeps the top-level takes and the threads people argued in rather than
+/// whatever came first or a long back-and-forth deep in one thread. A reply
+/// is only kept under its parent, so everything kept still reads in context.
+fn select_comments(comments: &[Comment], lines: &[String], budget: usize) -> Vec<bool> {
+    let mut parents = vec![None; comments.len()];
+    let mut descendants = vec![0_usize; comments.len()];
+    let mut ancestors: Vec<usize> = Vec::new();
+    for (index, comment) in comments.iter().enumerate() {
+        while ancestors
+            .last()
+            .is_some_and(|&ancestor| comments[ancestor].depth >= comment.depth)
+        {
+            ancestors.pop();
+        }
+        parents[index] = ancestors.last().copied();
+        for &ancestor in &ancestors {
+            descendants[ancestor] += 1;
+        }
+        ancestors.push(index);
+    }
+
+    // A parent always heads a bigger subthread than its replies, and only
+    // top-level comments are weighted up, so it is considered before them.
+    let weight = |index: usize| {
+        let top_level = if comments[index].depth == 0 {
+            TOP_LEVEL_WEIGHT
+        } else {
+            0
+        };
+        descendants[index] + top_level
+    };
+    let mut order: Vec<usize> = (0..comments.len()).collect();
+    order.sort_by_key(|&index| {
+        (
+            std::cmp::Reverse(weight(index)),
+            comments[index].depth,
+            index,
+        )
+    });
+
+    let mut kept = vec![false; comments.len()];
+    let mut remaining = budget;
+    for index in order {
+        let cost = estimate_tokens(&lines[index]);
+        if cost <= remaining && parents[index].is_none_or(|parent| kept[parent]) {
+            kept[index] = true;
+            remaining -= cost;
+        }
+    }
+    kept
+}
+
 /// Head-truncate on a char boundary; the lead of an article carries the thesis.
 fn truncate_article(article: &str, max_chars: usize) -> String {
     let mut truncated: String = article.chars().take(max_chars).collect();
@@ -1,4 +1,4 @@
-use super::{LlmSession, Summarizer, SummaryRequest};
+use super::{ChatMessage, LlmSession, Summarizer, SummaryRequest};
 use futures::future::BoxFuture;
 use futures::StreamExt;
 use std::time::{Duration, Instant};
@@ -54,7 +54,7 @@ impl Summarizer {
                 let request = SummaryRequest {
                     model: draft.model,
                     system_prompt: draft.system_prompt,
-                    user_prompt: "hi".to_string(),
+                    messages: vec![ChatMessage::user("hi")],
                     api_key: draft.api_key,
                     base_url: draft.base_url,
                 };
@@ -111,7 +111,7 @@ async fn connection_test_uses_the_draft_request_and_drops_after_first_content()
         .expect("captured request");
     assert_eq!(captured.model, "missing/model, fallback/model");
     assert_eq!(captured.system_prompt, "Draft instructions");
-    assert_eq!(captured.user_prompt, "hi");
+    assert_eq!(captured.user_prompt(), "hi");
     assert_eq!(captured.api_key.as_deref(), Some("draft-key"));
     assert_eq!(
         captured.base_url.as_deref(),
@@ -1,7 +1,8 @@
 use super::*;
-use crate::api::types::{Comment, Story};
+use crate::api::types::{Comment, PollOption, Story};
 use crate::config::SummarizeConfig;
 use futures::{stream, FutureExt};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -67,8 +68,12 @@ fn input() -> SummaryInput {
             time: 1,
             comment_count: 1,
             kids: vec![2],
+            kind: None,
+            stale_secs: None,
         },
         article: None,
+        poll: vec![],
+        profile: None,
         comments: vec![Comment {
             id: 2,
             by: Some("bob".to_string()),
@@ -79,6 +84,7 @@ fn input() -> SummaryInput {
             collapsed: false,
             children_loaded: true,
             children_loading: false,
+            muted: false,
         }],
     }
 }
@@ -88,10 +94,11 @@ fn config() -> SummarizeConfig {
         model: "fake/model".to_string(),
         api_key: None,
         base_url: None,
-        max_comments: 20,
+        max_prompt_tokens: 32_000,
+        model_prompt_tokens: BTreeMap::new(),
         include_article: true,
-        max_article_chars: 20_000,
         system_prompt: "Summarize".to_string(),
+        profiles: Vec::new(),
     }
 }
 
@@ -106,6 +113,7 @@ fn comment(author: &str, text: &str, depth: usize) -> Comment {
         collapsed: false,
         children_loaded: true,
         children_loading: false,
+        muted: false,
     }
 }
 
@@ -114,25 +122,29 @@ fn story() -> Story {
 }
 
 #[test]
-fn a_prompt_without_an_article_keeps_the_pre_article_shape() {
+fn a_prompt_without_an_article_leaves_the_comments_unlabelled() {
     let comments = [comment("bob", "hello", 0), comment("carol", "reply", 1)];
-    let prompt = build_prompt(&story(), &comments, None, 20, 20_000);
-    assert_eq!(prompt, "# Story\n\nbob: hello\n\n  carol: reply\n\n");
+    let prompt = build_prompt(&story(), &comments, None, &[], 32_000);
+    assert_eq!(
+        prompt,
+        "# Story\n\n[2] bob: hello\n\n  [2] carol: reply\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n"
+    );
 }
 
 #[test]
 fn an_article_prompt_labels_all_three_sections() {
     let comments = [comment("bob", "hello", 0)];
-    let prompt = build_prompt(&story(), &comments, Some("the body"), 20, 20_000);
+    let prompt = build_prompt(&story(), &comments, Some("the body"), &[], 32_000);
     assert_eq!(
         prompt,
-        "# Story\n\n## Article\n\nthe body\n\n## Comments\n\nbob: hello\n\n"
+        "# Story\n\n## Article\n\nthe body\n\n## Comments\n\n[2] bob: hello\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n"
     );
 }
 
 #[test]
-fn an_article_longer_than_the_cap_is_head_truncated_with_a_marker() {
-    let prompt = build_prompt(&story(), &[], Some("abcdefghij"), 20, 4);
+fn an_article_over_the_budget_is_head_truncated_with_a_marker() {
+    // The title takes three of the four tokens, leaving four characters.
+    let prompt = build_prompt(&story(), &[], Some("abcdefghij"), &[], 4);
     assert_eq!(prompt, "# Story\n\n## Article\n\nabcd\n\n…[truncated]\n\n");
 }
 
@@ -145,21 +157,93 @@ fn truncation_counts_characters_not_bytes() {
 
 #[test]
 fn an_article_that_fits_carries_no_truncation_marker() {
-    let prompt = build_prompt(&story(), &[], Some("short"), 20, 20_000);
+    let prompt = build_prompt(&story(), &[], Some("short"), &[], 32_000);
     assert_eq!(prompt, "# Story\n\n## Article\n\nshort\n\n");
 }
 
 #[test]
-fn max_comments_still_caps_the_comment_section() {
+fn a_tight_budget_keeps_big_subthreads_and_top_level_comments_first() {
+    let comments = [
+        comment("a", "first", 0),
+        comment("b", "second", 0),
+        comment("c", "third", 0),
+        comment("d", "reply", 1),
+        comment("e", "deeper", 2),
+    ];
+    // Title (3) and citation line (21), then room for three 4-token comments.
+    let prompt = build_prompt(&story(), &comments, None, &[], 36);
+    assert_eq!(
+        prompt,
+        "# Story\n\n[2] a: first\n\n[2] b: second\n\n[2] c: third\n\n…[2 of 5 comments left out]\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n"
+    );
+}
+
+#[test]
+fn a_tight_budget_keeps_a_top_level_comment_over_a_deep_reply() {
     let comments = [
-        comment("a", "one", 0),
-        comment("b", "two", 0),
-        comment("c", "three", 0),
+        comment("a", "first", 0),
+        comment("b", "reply", 1),
+        comment("c", "deeper", 2),
+        comment("d", "deepest", 3),
+        comment("e", "late", 0),
     ];
-    let prompt = build_prompt(&story(), &comments, None, 2, 20_000);
-    assert!(prompt.contains("a: one"));
-    assert!(prompt.contains("b: two"));
-    assert!(!prompt.contains("c: three"));
+    // Title (3) and citation line (21), then 13 tokens: a, b and e at 4
+    // each, or a, b and the 5-token c.
+    let prompt = build_prompt(&story(), &comments, None, &[], 37);
+    assert_eq!(
+        prompt,
+        "# Story\n\n[2] a: first\n\n  [2] b: reply\n\n[2] e: late\n\n…[2 of 5 comments left out]\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n"
+    );
+}
+
+#[test]
+fn the_article_leaves_most_of_the_budget_to_the_comments() {
+    let article = "x".repeat(1_000);
+    let comments = [comment("bob", "hello", 0)];
+    let prompt = build_prompt(&story(), &comments, Some(&article), &[], 100);
+    // 40% of the 97 tokens after the title: 38 tokens, 152 characters.
+    let kept = format!("## Article\n\n{}\n\n…[truncated]\n\n", "x".repeat(152));
+    assert!(prompt.contains(&kept), "{prompt}");
+    assert!(prompt.contains("[2] bob: hello"), "{prompt}");
+}
+
+#[test]
+fn a_poll_lists_its_options_between_the_article_and_the_comments() {
+    let comments = [comment("bob", "hello", 0)];
+    let poll = [
+        PollOption {
+            text: "Rust".to_string(),
+            score: 12,
+        },
+        PollOption {
+            text: "Go &amp; C".to_string(),
+            score: 3,
+        },
+    ];
+    let prompt = build_prompt(&story(), &comments, None, &poll, 32_000);
+    assert_eq!(
+        prompt,
+        "# Story\n\n## Poll\n\n- Rust: 12 votes\n- Go & C: 3 votes\n\n## Comments\n\n[2] bob: hello\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n"
+    );
+}
+
+#[test]
+fn a_follow_up_replays_the_exchange_under_the_story_context() {
+    let prompt = flatten_messages(vec![
+        ChatMessage::user("# Story\n\nbob: hello\n\n"),
+        ChatMessage::assistant("People liked it.\n"),
+        ChatMessage::user("What about pricing?"),
+    ]);
+    assert_eq!(
+        prompt,
+        "# Story\n\nbob: hello\n\n## Conversation\n\nAssistant: People liked it.\n\nUser: What about pricing?\n\nAnswer the user's last message, using the story above."
+    );
+}
+
+#[test]
+fn a_lone_summary_prompt_goes_out_unchanged() {
+    let prompt = flatten_messages(vec![ChatMessage::user("# Story\n\nbob: hello\n\n")]);
+    assert_eq!(prompt, "# Story\n\nbob: hello\n\n");
 }
 
 async fn first_error(input: SummaryInput) -> Option<String> {
@@ -241,6 +325,130 @@ async fn fake_stream_emits_started_chunks_and_complete_in_order() {
     );
 }
 
+#[derive(Clone, Default)]
+struct RecordingLlmStream {
+    requests: Arc<std::sync::Mutex<Vec<(String, String, String)>>>,
+}
+
+impl LlmStream for RecordingLlmStream {
+    fn start(&self, request: SummaryRequest) -> LlmFuture {
+        self.requests.lock().expect("recorder poisoned").push((
+            request.model().to_string(),
+            request.system_prompt().to_string(),
+            request.user_prompt().to_string(),
+        ));
+        FakeLlmStream.start(request)
+    }
+}
+
+#[tokio::test]
+async fn a_profile_swaps_the_model_prompt_and_token_budget() {
+    let mut config = config();
+    config.profiles = vec![crate::config::SummaryProfile {
+        name: "tl;dr".to_string(),
+        model: Some("fake/small".to_string()),
+        // Title, citation line and the first comment only.
+        max_prompt_tokens: Some(28),
+        system_prompt: Some("One line.".to_string()),
+    }];
+    let stream = RecordingLlmStream::default();
+    let summarizer = Summarizer::with_stream(Some(config), None, Arc::new(stream.clone()));
+    let mut input = input();
+    input.comments.push(comment("carol", "second", 0));
+    input.profile = Some("tl;dr".to_string());
+
+    summarizer.summarize(input).collect::<Vec<_>>().await;
+
+    let requests = stream.requests.lock().expect("recorder poisoned");
+    let (model, system_prompt, user_prompt) = &requests[0];
+    assert_eq!(model, "fake/small");
+    assert_eq!(system_prompt, "One line.");
+    assert!(!user_prompt.contains("second"), "{user_prompt}");
+}
+
+#[derive(Clone, Default)]
+struct UnreachableFirstLeg {
+    recorded: RecordingLlmStream,
+}
+
+impl LlmStream for UnreachableFirstLeg {
+    fn start(&self, request: SummaryRequest) -> LlmFuture {
+        let first_leg = request.model() == "fake/small";
+        let session = self.recorded.start(request);
+        if first_leg {
+            return async { Err(smolllm::Error::Other("unreachable".to_string())) }.boxed();
+        }
+        session
+    }
+}
+
+#[tokio::test]
+async fn each_fallback_leg_gets_a_prompt_sized_by_its_own_budget() {
+    let mut config = config();
+    config.model = "fake/small, fake/big".to_string();
+    config.max_prompt_tokens = 28;
+    config.model_prompt_tokens = BTreeMap::from([("fake/big".to_string(), 32_000)]);
+    let stream = UnreachableFirstLeg::default();
+    let summarizer = Summarizer::with_stream(Some(config), None, Arc::new(stream.clone()));
+    let mut input = input();
+    input.comments.push(comment("carol", "second", 0));
+
+    let events: Vec<_> = summarizer.summarize(input).collect().await;
+
+    assert!(matches!(events.last(), Some(Ok(SummaryEvent::Complete))));
+    let requests = stream.recorded.requests.lock().expect("recorder poisoned");
+    let prompts: Vec<_> = requests
+        .iter()
+        .map(|(model, _, prompt)| (model.as_str(), prompt.contains("second")))
+        .collect();
+    assert_eq!(prompts, vec![("fake/small", false), ("fake/big", true)]);
+}
+
+#[derive(Clone, Default)]
+struct TranscriptRecorder {
+    messages: Arc<std::sync::Mutex<Vec<ChatMessage>>>,
+}
+
+impl LlmStream for TranscriptRecorder {
+    fn start(&self, request: SummaryRequest) -> LlmFuture {
+        *self.messages.lock().expect("recorder poisoned") = request.messages().to_vec();
+        FakeLlmStream.start(request)
+    }
+}
+
+#[tokio::test]
+async fn a_follow_up_sends_the_story_context_before_the_transcript() {
+    let recorder = TranscriptRecorder::default();
+    let summarizer = Summarizer::with_stream(Some(config()), None, Arc::new(recorder.clone()));
+    let transcript = vec![
+        ChatMessage::assistant("People liked it."),
+        ChatMessage::user("What about pricing?"),
+    ];
+
+    let events: Vec<_> = summarizer
+        .follow_up(input(), transcript.clone())
+        .collect()
+        .await;
+
+    assert!(matches!(events.last(), Some(Ok(SummaryEvent::Complete))));
+    let messages = recorder.messages.lock().expect("recorder poisoned");
+    assert_eq!(
+        messages[0],
+        ChatMessage::user("# Story\n\n[2] bob: hello\n\nCite the comments you draw on by their bracketed ID, like [2].\n\n")
+    );
+    assert_eq!(messages[1..], transcript[..]);
+}
+
+#[tokio::test]
+async fn an_unknown_profile_is_an_error_before_any_request() {
+    let mut input = input();
+    input.profile = Some("missing".to_string());
+    assert_eq!(
+        first_error(input).await.as_deref(),
+        Some("no summary profile named \"missing\" in [summarize]")
+    );
+}
+
 #[tokio::test]
 async fn initialization_error_surfaces_without_complete() {
     let summarizer = Summarizer::with_stream(
@@ -11,13 +11,20 @@ struct Generation(u64);
 pub(crate) enum TaskTarget {
     Stories,
     Search,
+    User,
     CommentRoots(u64),
     CommentChildren(u64),
+    CommentParent(u64),
+    WatchPoll,
     Article(u64),
     Summary,
     ConnectionTest,
     SettingsSave,
     StoryStateSave,
+    BookmarksSave,
+    SummaryCacheSave,
+    MuteSave,
+    OfflineDownload,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -1,5 +1,6 @@
 use html_escape::{decode_html_entities, encode_text};
 use regex::{Captures, Regex};
+use std::ops::Range;
 use std::sync::LazyLock;
 
 static HN_ANCHOR_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -53,6 +54,41 @@ fn escape_markdown_destination(destination: &str) -> String {
     escaped
 }
 
+/// Byte ranges of each occurrence of `needle` in `haystack`, ignoring case,
+/// left to right without overlaps. An empty needle matches nothing.
+pub(crate) fn find_ignore_case(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let folded: Vec<(char, Range<usize>)> = haystack
+        .char_indices()
+        .flat_map(|(start, character)| {
+            let end = start + character.len_utf8();
+            character
+                .to_lowercase()
+                .map(move |lower| (lower, start..end))
+        })
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut index = 0;
+    while index + needle.len() <= folded.len() {
+        let window = &folded[index..index + needle.len()];
+        if window
+            .iter()
+            .map(|(lower, _)| *lower)
+            .eq(needle.iter().copied())
+        {
+            matches.push(window[0].1.start..window[needle.len() - 1].1.end);
+            index += needle.len();
+        } else {
+            index += 1;
+        }
+    }
+    matches
+}
+
 pub(crate) fn hn_html_to_plain(html: &str) -> String {
     let html = html
         .replace("<p>", "\n\n")
@@ -110,6 +146,14 @@ fn collapse_spaces(value: &str) -> String {
     output
 }
 
+/// FNV-1a: unlike `DefaultHasher`, stable across builds, so keys written to
+/// disk by one release still match in the next.
+pub(crate) fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +164,19 @@ mod tests {
         assert_eq!(hn_html_to_plain(html), "Hello world\n\nline\nnext & more");
     }
 
+    #[test]
+    fn finds_every_match_regardless_of_case() {
+        let text = "Rust and RUST, then ßrust";
+
+        let found: Vec<&str> = find_ignore_case(text, "rust")
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+
+        assert_eq!(found, vec!["Rust", "RUST", "rust"]);
+        assert!(find_ignore_case(text, "").is_empty());
+    }
+
     #[test]
     fn keeps_link_text_and_strips_tags() {
         let html = r#"Read <a href="https://example.com">this</a> &gt; that"#;
@@ -25,6 +25,21 @@ enum LinkDirection {
     Previous,
 }
 
+/// `/` in the overlay: a query over the rendered Article, with `n`/`N`
+/// stepping through its matches.
+#[derive(Debug, Default)]
+struct ArticleFind {
+    query: String,
+    /// Still typing the query; keys go to the query, not the bindings.
+    typing: bool,
+    /// The match last jumped to, counted through the whole document.
+    current: Option<usize>,
+    count: usize,
+    /// The scroll offset when the find began: typing searches from here
+    /// and Esc returns here.
+    origin: usize,
+}
+
 #[derive(Default)]
 pub struct ArticleOverlay {
     state: ArticleState,
@@ -46,6 +61,7 @@ pub struct ArticleOverlay {
     story_time: i64,
     links: Vec<String>,
     selected_link: Option<usize>,
+    find: Option<ArticleFind>,
 }
 
 impl ArticleOverlay {
@@ -75,6 +91,7 @@ impl ArticleOverlay {
             markdown::render_markdown_document(&self.content, self.link_base_url.as_deref(), None)
                 .links;
         self.selected_link = None;
+        self.find = None;
         self.error = None;
         self.started_at = None;
         self.scroll.go_top();
@@ -86,6 +103,7 @@ impl ArticleOverlay {
         self.error = Some(message);
         self.links.clear();
         self.selected_link = None;
+        self.find = None;
         self.started_at = None;
         self.scroll.go_top();
         self.reflow();
@@ -161,6 +179,177 @@ impl ArticleOverlay {
         self.selected_link = None;
     }
 
+    /// Begin typing a find query. Only a fetched Article can be searched.
+    pub fn start_find(&mut self) {
+        if self.state != ArticleState::Done {
+            return;
+        }
+        self.find = Some(ArticleFind {
+            typing: true,
+            origin: self.scroll.offset(),
+            ..ArticleFind::default()
+        });
+    }
+
+    pub fn is_typing_find(&self) -> bool {
+        self.find.as_ref().is_some_and(|find| find.typing)
+    }
+
+    pub fn has_find(&self) -> bool {
+        self.find.is_some()
+    }
+
+    pub fn push_find(&mut self, character: char) {
+        self.edit_find_query(|query| query.push(character));
+    }
+
+    pub fn pop_find(&mut self) {
+        self.edit_find_query(|query| {
+            query.pop();
+        });
+    }
+
+    /// Keep the query for `n`/`N`; an empty one ends the find.
+    pub fn submit_find(&mut self) {
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        find.typing = false;
+        if find.query.is_empty() {
+            self.find = None;
+        }
+    }
+
+    /// Drop the query and scroll back to where the find began.
+    pub fn cancel_find(&mut self) {
+        let Some(find) = self.find.take() else {
+            return;
+        };
+        self.scroll.go_top();
+        self.scroll.scroll_down(find.origin);
+    }
+
+    pub fn clear_find(&mut self) {
+        self.find = None;
+    }
+
+    pub fn next_match(&mut self) {
+        self.step_match(LinkDirection::Next);
+    }
+
+    pub fn previous_match(&mut self) {
+        self.step_match(LinkDirection::Previous);
+    }
+
+    /// Incremental: each edit jumps to the first match at or below where
+    /// the find began, or clears the jump when nothing matches.
+    fn edit_find_query(&mut self, edit: impl FnOnce(&mut String)) {
+        let lines = self.document_lines();
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        edit(&mut find.query);
+        find.count = markdown::count_matches(&lines, &find.query);
+        let origin = find.origin;
+        let target = self
+            .first_match_from(origin)
+            .or_else(|| (self.find.as_ref()?.count > 0).then_some(0));
+        match target {
+            Some(index) => self.select_match(index),
+            None => {
+                if let Some(find) = self.find.as_mut() {
+                    find.current = None;
+                }
+                self.scroll.go_top();
+                self.scroll.scroll_down(origin);
+            }
+        }
+    }
+
+    /// Wraps at either end of the Article.
+    fn step_match(&mut self, direction: LinkDirection) {
+        let Some(find) = self.find.as_ref() else {
+            return;
+        };
+        if find.count == 0 {
+            return;
+        }
+        let count = find.count;
+        let target = match (find.current, direction) {
+            (Some(current), LinkDirection::Next) => (current + 1) % count,
+            (Some(current), LinkDirection::Previous) => current.checked_sub(1).unwrap_or(count - 1),
+            (None, LinkDirection::Next) => self.first_match_from(self.scroll.offset()).unwrap_or(0),
+            (None, LinkDirection::Previous) => self
+                .first_match_from(self.scroll.offset())
+                .and_then(|first| first.checked_sub(1))
+                .unwrap_or(count - 1),
+        };
+        self.select_match(target);
+    }
+
+    fn select_match(&mut self, index: usize) {
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        find.current = Some(index);
+        self.selected_link = None;
+        self.reveal_current_match();
+    }
+
+    /// Index of the first match on a line that reaches `row` or below.
+    fn first_match_from(&self, row: usize) -> Option<usize> {
+        let find = self.find.as_ref()?;
+        let mut line_end = 0;
+        let mut seen = 0;
+        for line in self.document_lines() {
+            let matches = markdown::count_matches(std::slice::from_ref(&line), &find.query);
+            line_end += Paragraph::new(line)
+                .wrap(Wrap { trim: false })
+                .line_count(self.viewport_width);
+            if matches > 0 && line_end > row {
+                return Some(seen);
+            }
+            seen += matches;
+        }
+        None
+    }
+
+    fn reveal_current_match(&mut self) {
+        let Some(find) = self.find.as_ref() else {
+            return;
+        };
+        let Some(current) = find.current else {
+            return;
+        };
+        if let Some(rows) = markdown::match_row_range(
+            self.document_lines(),
+            &find.query,
+            current,
+            self.viewport_width,
+        ) {
+            self.scroll.reveal(*rows.start(), *rows.end());
+        }
+    }
+
+    /// `3/12` while a find is active, for the hint line.
+    fn find_status(&self) -> Option<String> {
+        let find = self.find.as_ref()?;
+        let position = match (find.current, find.count) {
+            (_, 0) if find.query.is_empty() => String::new(),
+            (_, 0) => "no matches".to_string(),
+            (Some(current), count) => format!("{}/{count}", current + 1),
+            (None, count) => format!("{count} matches"),
+        };
+        Some(if find.typing {
+            format!("/{}▏  {position}  Enter: done  Esc: cancel", find.query)
+        } else {
+            format!(
+                "/{}  {position}  n/N: next/previous  Esc: clear",
+                find.query
+            )
+        })
+    }
+
     pub fn selected_link(&self) -> Option<&str> {
         self.selected_link
             .and_then(|selected| self.links.get(selected))
@@ -179,6 +368,9 @@ impl ArticleOverlay {
             self.reflow();
         }
         self.reveal_selected_link();
+        if self.selected_link.is_none() {
+            self.reveal_current_match();
+        }
     }
 
     pub fn page_scroll_amount(&self) -> usize {
@@ -200,6 +392,11 @@ impl ArticleOverlay {
         self.state
     }
 
+    #[cfg(test)]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
     pub fn is_visible(&self) -> bool {
         self.state != ArticleState::Idle
     }
@@ -282,24 +479,31 @@ impl ArticleOverlay {
                     "fetching article… {}s {spinner} (Esc to cancel)",
                     self.elapsed_secs()
                 ),
-                theme::HINT,
+                theme::hint(),
             ))],
-            ArticleState::Done => {
-                markdown::render_markdown_document(
-                    &self.content,
-                    self.link_base_url.as_deref(),
-                    self.selected_link,
-                )
-                .lines
-            }
+            ArticleState::Done => match &self.find {
+                Some(find) => {
+                    markdown::highlight_matches(self.document_lines(), &find.query, find.current)
+                }
+                None => self.document_lines(),
+            },
             ArticleState::Error => vec![Line::from(Span::styled(
                 self.error.as_deref().unwrap_or("Unknown error").to_string(),
-                theme::ERROR,
+                theme::error(),
             ))],
             ArticleState::Idle => Vec::new(),
         }
     }
 
+    fn document_lines(&self) -> Vec<Line<'static>> {
+        markdown::render_markdown_document(
+            &self.content,
+            self.link_base_url.as_deref(),
+            self.selected_link,
+        )
+        .lines
+    }
+
     fn content_paragraph(&self, spinner: char) -> Paragraph<'static> {
         Paragraph::new(self.content_lines(spinner)).wrap(Wrap { trim: false })
     }
@@ -342,37 +546,42 @@ pub fn render(frame: &mut Frame, overlay: &ArticleOverlay, spinner: char) {
     };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled(title, theme::HEADER_ACCENT));
+        .title(Span::styled(title, theme::header_accent()));
     frame.render_widget(Clear, areas.popup);
-    frame.render_widget(block.style(theme::POPUP), areas.popup);
+    frame.render_widget(block.style(theme::popup()), areas.popup);
     frame.render_widget(
         overlay
             .content_paragraph(spinner)
             .scroll((overlay.render_scroll_offset(), 0))
-            .style(theme::POPUP),
+            .style(theme::popup()),
         areas.content,
     );
     overlay::render_scrollbar(frame, areas.scrollbar, &overlay.scroll);
 
     let hint = if overlay::copied_recently(overlay.copied_flash) {
-        Line::from(Span::styled("Copied!", theme::SUCCESS))
+        Line::from(Span::styled("Copied!", theme::success()))
     } else {
-        let text = if let Some(url) = overlay.selected_link() {
+        let find_status = overlay
+            .find_status()
+            .filter(|_| overlay.is_typing_find() || overlay.selected_link.is_none());
+        let text = if let Some(status) = find_status {
+            status
+        } else if let Some(url) = overlay.selected_link() {
             format!("{url}  Enter: open  Tab/Shift+Tab: links  o: original")
         } else {
             match overlay.state {
                 ArticleState::Done if !overlay.links.is_empty() => {
-                    "j/k: scroll  Tab/Shift+Tab: links  Enter: open  c: copy  o: original  q/Esc: close"
+                    "j/k: scroll  /: find  Tab/Shift+Tab: links  Enter: open  c: copy  o: original  q/Esc: close"
                 }
-                ArticleState::Done => "j/k: scroll  c: copy  o: original  q/Esc: close",
+                ArticleState::Done => "j/k: scroll  /: find  c: copy  o: original  q/Esc: close",
                 ArticleState::Error => "o: original  q/Esc: close",
                 _ => "q/Esc: cancel",
             }
             .to_string()
         };
-        Line::from(Span::styled(text, theme::HINT))
+        Line::from(Span::styled(text, theme::hint()))
     };
-    frame.render_widget(Paragraph::new(hint).style(theme::POPUP), areas.hint);
+    frame.render_widget(Paragraph::new(hint).style(theme::popup()), areas.hint);
 }
 
 pub(crate) fn popup_rect(area: Rect) -> Option<Rect> {
@@ -14,6 +14,8 @@ fn story() -> Story {
         time: 1_700_000_000,
         comment_count: 2,
         kids: vec![1, 2],
+        kind: None,
+        stale_secs: None,
     }
 }
 
@@ -190,6 +192,50 @@ fn manual_scrolling_clears_article_link_selection() {
     assert_eq!(overlay.selected_link(), None);
 }
 
+#[test]
+fn typing_a_find_query_jumps_to_the_first_match_and_n_wraps() {
+    let mut overlay = done_overlay(&format!(
+        "{}\n\nneedle one\n\n{}\n\nneedle two",
+        many_paragraphs(6),
+        many_paragraphs(6)
+    ));
+    let (_, areas) = render_overlay(&mut overlay, 80, 10);
+
+    overlay.start_find();
+    for character in "NEEDLE".chars() {
+        overlay.push_find(character);
+    }
+    let (buffer, _) = render_overlay(&mut overlay, 80, 10);
+    assert!(area_text(&buffer, areas.content).contains("needle one"));
+    assert!(area_text(&buffer, areas.hint).contains("1/2"));
+
+    overlay.submit_find();
+    overlay.next_match();
+    let (buffer, _) = render_overlay(&mut overlay, 80, 10);
+    assert!(area_text(&buffer, areas.content).contains("needle two"));
+
+    overlay.next_match();
+    let (buffer, _) = render_overlay(&mut overlay, 80, 10);
+    assert!(area_text(&buffer, areas.content).contains("needle one"));
+    assert!(area_text(&buffer, areas.hint).contains("n/N: next/previous"));
+}
+
+#[test]
+fn cancelling_a_find_returns_to_where_it_began() {
+    let mut overlay = done_overlay(&format!("{}\n\nneedle", many_paragraphs(12)));
+    overlay.set_viewport(40, 3);
+    overlay.scroll_down(2);
+
+    overlay.start_find();
+    overlay.push_find('n');
+    assert!(overlay.scroll_offset() > 2);
+
+    overlay.cancel_find();
+
+    assert_eq!(overlay.scroll_offset(), 2);
+    assert!(!overlay.has_find());
+}
+
 #[test]
 fn content_shorter_than_the_viewport_does_not_scroll() {
     let mut overlay = done_overlay("short");
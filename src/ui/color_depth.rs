@@ -0,0 +1,248 @@
+use clap::ValueEnum;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use std::sync::OnceLock;
+
+/// How many colors the terminal can show. The theme speaks truecolor; on
+/// anything less, each drawn frame is mapped to the nearest palette entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorDepth {
+    /// 24-bit RGB, drawn as-is.
+    Truecolor,
+    /// The xterm 256-color palette (its 6×6×6 cube and gray ramp).
+    #[value(name = "256")]
+    Indexed256,
+    /// The 16 ANSI colors, as the terminal's own scheme renders them.
+    #[value(name = "16")]
+    Ansi16,
+}
+
+static ACTIVE: OnceLock<ColorDepth> = OnceLock::new();
+
+pub(crate) fn install(depth: ColorDepth) {
+    let _ = ACTIVE.set(depth);
+}
+
+/// Map every cell of a finished frame down to the installed depth.
+pub(crate) fn downgrade_buffer(buffer: &mut Buffer) {
+    let depth = ACTIVE.get().copied().unwrap_or(ColorDepth::Truecolor);
+    if depth == ColorDepth::Truecolor {
+        return;
+    }
+    for cell in &mut buffer.content {
+        cell.fg = depth.downgrade(cell.fg);
+        cell.bg = depth.downgrade(cell.bg);
+    }
+}
+
+impl ColorDepth {
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+            std::env::var_os("TMUX").is_some(),
+        )
+    }
+
+    /// `COLORTERM=truecolor|24bit` or a `*-direct` TERM promise RGB and a
+    /// `*-256color` TERM, plain `xterm` or `screen` the palette. Only the
+    /// Linux console, VT-series terminals and `*-16color` TERMs get the 16;
+    /// an unknown TERM keeps truecolor, since most modern terminals take it
+    /// without saying so. Inside tmux, with its own `tmux-*` or `screen-*`
+    /// TERM, `COLORTERM` is the outer terminal's and says nothing of
+    /// whether tmux passes RGB on, so TERM alone decides.
+    fn from_env(colorterm: Option<&str>, term: Option<&str>, in_tmux: bool) -> Self {
+        let tmux_term = in_tmux
+            && term.is_some_and(|term| term.starts_with("tmux") || term.starts_with("screen"));
+        if !tmux_term && colorterm.is_some_and(|value| matches!(value, "truecolor" | "24bit")) {
+            return Self::Truecolor;
+        }
+        match term {
+            Some(term) if term.ends_with("-direct") => Self::Truecolor,
+            Some(term) if is_sixteen_color_term(term) => Self::Ansi16,
+            Some(term) if term.contains("256color") || matches!(term, "xterm" | "screen") => {
+                Self::Indexed256
+            }
+            _ => Self::Truecolor,
+        }
+    }
+
+    fn downgrade(self, color: Color) -> Color {
+        let rgb = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(index) if self == Self::Ansi16 && index >= 16 => indexed_rgb(index),
+            _ => return color,
+        };
+        match self {
+            Self::Truecolor => color,
+            Self::Indexed256 => Color::Indexed(nearest_indexed(rgb)),
+            Self::Ansi16 => nearest_ansi(rgb),
+        }
+    }
+}
+
+fn is_sixteen_color_term(term: &str) -> bool {
+    term == "linux" || term.starts_with("vt") || term.ends_with("-16color")
+}
+
+/// Channel values of the 256-color cube's six steps.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The closest of the cube (16–231) and gray ramp (232–255); the first 16
+/// are left out because terminal schemes redefine them.
+fn nearest_indexed((r, g, b): (u8, u8, u8)) -> u8 {
+    let level = |channel: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&step| CUBE_LEVELS[step].abs_diff(channel))
+            .expect("cube has levels")
+    };
+    let (cr, cg, cb) = (level(r), level(g), level(b));
+    let cube_index = 16 + 36 * cr + 6 * cg + cb;
+    let cube = (CUBE_LEVELS[cr], CUBE_LEVELS[cg], CUBE_LEVELS[cb]);
+
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// The RGB an index of the 256-color palette stands for.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[usize::from(index)].1,
+        16..=231 => {
+            let cube = usize::from(index - 16);
+            (
+                CUBE_LEVELS[cube / 36],
+                CUBE_LEVELS[(cube / 6) % 6],
+                CUBE_LEVELS[cube % 6],
+            )
+        }
+        _ => {
+            let gray = 8 + 10 * (index - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// xterm's defaults for the 16 ANSI colors; real schemes differ, but only
+/// the choice of entry matters, not the exact shade.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn nearest_ansi(rgb: (u8, u8, u8)) -> Color {
+    ANSI.iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map(|&(color, _)| color)
+        .expect("ANSI palette is non-empty")
+}
+
+/// Squared distance, weighted toward green the way eyes are.
+fn distance((ar, ag, ab): (u8, u8, u8), (br, bg, bb): (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    2 * channel(ar, br) + 4 * channel(ag, bg) + 3 * channel(ab, bb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detection_prefers_colorterm_then_term() {
+        let cases = [
+            (
+                Some("truecolor"),
+                Some("xterm-256color"),
+                ColorDepth::Truecolor,
+            ),
+            (Some("24bit"), None, ColorDepth::Truecolor),
+            (None, Some("xterm-direct"), ColorDepth::Truecolor),
+            (None, Some("tmux-256color"), ColorDepth::Indexed256),
+            (None, Some("screen-256color"), ColorDepth::Indexed256),
+            (None, Some("xterm"), ColorDepth::Indexed256),
+            (None, Some("screen"), ColorDepth::Indexed256),
+            (None, Some("linux"), ColorDepth::Ansi16),
+            (None, Some("vt100"), ColorDepth::Ansi16),
+            (None, Some("xterm-16color"), ColorDepth::Ansi16),
+            (None, Some("xterm-kitty"), ColorDepth::Truecolor),
+            (None, Some("alacritty"), ColorDepth::Truecolor),
+            (None, None, ColorDepth::Truecolor),
+        ];
+
+        for (colorterm, term, expected) in cases {
+            assert_eq!(
+                ColorDepth::from_env(colorterm, term, false),
+                expected,
+                "COLORTERM={colorterm:?} TERM={term:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn inside_tmux_its_own_term_outranks_a_passed_through_colorterm() {
+        let cases = [
+            (Some("tmux-256color"), ColorDepth::Indexed256),
+            (Some("screen-256color"), ColorDepth::Indexed256),
+            (Some("screen"), ColorDepth::Indexed256),
+            (Some("tmux-direct"), ColorDepth::Truecolor),
+            (Some("xterm-256color"), ColorDepth::Truecolor),
+        ];
+
+        for (term, expected) in cases {
+            assert_eq!(
+                ColorDepth::from_env(Some("truecolor"), term, true),
+                expected,
+                "TERM={term:?}"
+            );
+        }
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), Some("tmux-256color"), false),
+            ColorDepth::Truecolor
+        );
+    }
+
+    #[test]
+    fn rgb_maps_to_the_nearest_cube_or_gray_entry() {
+        let depth = ColorDepth::Indexed256;
+
+        assert_eq!(depth.downgrade(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(
+            depth.downgrade(Color::Rgb(0x30, 0x30, 0x30)),
+            Color::Indexed(236)
+        );
+        assert_eq!(depth.downgrade(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn heat_map_extremes_stay_apart_in_sixteen_colors() {
+        let depth = ColorDepth::Ansi16;
+        let hottest = depth.downgrade(Color::Rgb(0xEF, 0x9F, 0x76));
+        let coldest = depth.downgrade(Color::Rgb(0x3F, 0x2E, 0x2B));
+
+        assert_ne!(hottest, coldest);
+        assert_eq!(coldest, Color::Black);
+        assert_eq!(depth.downgrade(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(depth.downgrade(Color::Indexed(4)), Color::Indexed(4));
+    }
+}
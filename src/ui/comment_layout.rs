@@ -1,9 +1,10 @@
 use crate::api::types::Comment;
-use crate::text::hn_html_to_plain;
+use crate::text::{find_ignore_case, hn_html_to_plain};
 use crate::ui::theme;
 use crate::ui::{format_age, now_unix};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
+use std::collections::HashSet;
 use std::ops::Range;
 
 #[derive(Default)]
@@ -22,10 +23,12 @@ impl CommentLayout {
         width: usize,
         viewport_height: usize,
         spinner: char,
+        new_ids: &HashSet<u64>,
+        find: &str,
     ) {
         self.width = width.max(1);
         self.viewport_height = viewport_height.max(1);
-        self.comment_lines = build_comment_lines(comments, self.width, spinner);
+        self.comment_lines = build_comment_lines(comments, self.width, spinner, new_ids, find);
         self.line_ranges.clear();
 
         let mut start = 0;
@@ -152,7 +155,7 @@ impl CommentLayout {
             .line_range(selected)
             .unwrap_or_else(|| panic!("comment selection out of range: {selected}"));
         let half_viewport = self.viewport_height / 2;
-        let dim_target = theme::OVERLAY0;
+        let dim_target = theme::overlay0();
         let mut visible = Vec::with_capacity(end.saturating_sub(start));
 
         for (index, lines) in self.comment_lines.iter().enumerate() {
@@ -225,6 +228,8 @@ fn build_comment_lines(
     comments: &[Comment],
     content_width: usize,
     spinner: char,
+    new_ids: &HashSet<u64>,
+    find: &str,
 ) -> Vec<Vec<Line<'static>>> {
     let comment_max_lines = theme::COMMENT_MAX_LINES.unwrap_or(usize::MAX);
     let now = now_unix();
@@ -235,7 +240,8 @@ fn build_comment_lines(
             let indent_width = indent.chars().count();
             let indent_style = Style::default().fg(theme::comment_indent_color(comment.depth));
             let marker_style = indent_style.add_modifier(Modifier::BOLD);
-            let thread_marker = if comment.kids.is_empty() {
+            let stub = comment.muted && comment.collapsed;
+            let thread_marker = if comment.kids.is_empty() && !stub {
                 ' '
             } else if comment.collapsed {
                 '▸'
@@ -252,33 +258,38 @@ fn build_comment_lines(
             let author_style = Style::default()
                 .fg(theme::comment_indent_color(comment.depth))
                 .add_modifier(Modifier::BOLD);
-            let mut lines = vec![Line::from(vec![
+            let mut header = vec![
                 Span::styled(indent.clone(), indent_style),
                 Span::styled(format!("{thread_marker} "), marker_style),
-                Span::styled(by, author_style),
-                Span::styled(format!(" · {age}"), theme::META),
-            ])];
+            ];
+            header.extend(find_spans(by, author_style, find));
+            header.push(Span::styled(format!(" · {age}"), theme::meta()));
+            if new_ids.contains(&comment.id) {
+                header.push(Span::styled(" new", theme::success()));
+            }
+            if stub {
+                header.push(Span::styled(" · muted", theme::meta()));
+                return vec![Line::from(header)];
+            }
+            let mut lines = vec![Line::from(header)];
 
             let body_indent = format!("{indent}  ");
             let body_width = content_width.saturating_sub(indent_width + 2).max(1);
             let plain = hn_html_to_plain(&comment.text);
             if !plain.is_empty() {
                 for wrapped in wrap_content(&plain, body_width, comment_max_lines) {
-                    let line = match wrapped {
-                        ContentLine::Normal(text) => Line::from(vec![
-                            Span::styled(body_indent.clone(), indent_style),
-                            Span::styled(text, Style::default().fg(theme::TEXT)),
-                        ]),
-                        ContentLine::Quote(text) => Line::from(vec![
-                            Span::styled(body_indent.clone(), indent_style),
-                            Span::styled("▎ ", theme::QUOTE_BAR),
-                            Span::styled(text, theme::QUOTE),
-                        ]),
-                        ContentLine::Blank => {
-                            Line::from(Span::styled(body_indent.clone(), indent_style))
+                    let mut spans = vec![Span::styled(body_indent.clone(), indent_style)];
+                    match wrapped {
+                        ContentLine::Normal(text) => {
+                            spans.extend(find_spans(text, Style::default().fg(theme::text()), find))
+                        }
+                        ContentLine::Quote(text) => {
+                            spans.push(Span::styled("▎ ", theme::quote_bar()));
+                            spans.extend(find_spans(text, theme::quote(), find));
                         }
-                    };
-                    lines.push(line);
+                        ContentLine::Blank => {}
+                    }
+                    lines.push(Line::from(spans));
                 }
             }
             lines
@@ -286,6 +297,31 @@ fn build_comment_lines(
         .collect()
 }
 
+/// `text` in `style`, with each match of `find` picked out. A match cut by
+/// wrapping is not highlighted.
+fn find_spans(text: String, style: Style, find: &str) -> Vec<Span<'static>> {
+    let matches = find_ignore_case(&text, find);
+    if matches.is_empty() {
+        return vec![Span::styled(text, style)];
+    }
+    let mut spans = Vec::with_capacity(matches.len() * 2 + 1);
+    let mut end = 0;
+    for range in matches {
+        if range.start > end {
+            spans.push(Span::styled(text[end..range.start].to_string(), style));
+        }
+        spans.push(Span::styled(
+            text[range.clone()].to_string(),
+            theme::find_match(),
+        ));
+        end = range.end;
+    }
+    if end < text.len() {
+        spans.push(Span::styled(text[end..].to_string(), style));
+    }
+    spans
+}
+
 fn highlight_line_to_width(mut line: Line<'static>, width: usize) -> Line<'static> {
     let line_width = line
         .spans
@@ -295,10 +331,10 @@ fn highlight_line_to_width(mut line: Line<'static>, width: usize) -> Line<'stati
     if line_width < width {
         line.spans.push(Span::styled(
             " ".repeat(width - line_width),
-            theme::SELECTED,
+            theme::selected(),
         ));
     }
-    line.patch_style(theme::SELECTED)
+    line.patch_style(theme::selected())
 }
 
 enum ContentLine {
@@ -425,6 +461,7 @@ mod tests {
             collapsed: false,
             children_loaded: true,
             children_loading: false,
+            muted: false,
         }
     }
 
@@ -440,7 +477,7 @@ mod tests {
         let comments = vec![comment(1, "a"), comment(2, "aa bb cc"), comment(3, "d")];
         let mut layout = CommentLayout::default();
 
-        layout.relayout(&comments, 8, 4, '⠋');
+        layout.relayout(&comments, 8, 4, '⠋', &HashSet::new(), "");
 
         assert_eq!(layout.line_range(0), Some(0..2));
         assert_eq!(layout.line_range(1), Some(2..5));
@@ -456,7 +493,7 @@ mod tests {
     fn over_tall_comment_is_pinned_to_its_first_line() {
         let comments = vec![comment(1, "a"), comment(2, "aa bb cc dd ee ff")];
         let mut layout = CommentLayout::default();
-        layout.relayout(&comments, 8, 3, '⠋');
+        layout.relayout(&comments, 8, 3, '⠋', &HashSet::new(), "");
 
         layout.ensure_visible(1);
 
@@ -464,6 +501,39 @@ mod tests {
         assert!(line_text(&visible[0]).contains("user2"));
     }
 
+    #[test]
+    fn find_matches_are_picked_out_in_authors_and_text() {
+        let comments = vec![comment(1, "Rust or rust?")];
+        let mut layout = CommentLayout::default();
+        layout.relayout(&comments, 40, 4, '⠋', &HashSet::new(), "rust");
+
+        let visible = layout.visible_lines(0);
+        let highlighted: Vec<&str> = visible[1]
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == theme::find_match().bg)
+            .map(|span| span.content.as_ref())
+            .collect();
+
+        assert_eq!(highlighted, vec!["Rust", "rust"]);
+        assert_eq!(line_text(&visible[1]).trim_end(), "  Rust or rust?");
+    }
+
+    #[test]
+    fn a_folded_muted_comment_is_a_one_line_stub() {
+        let muted = Comment {
+            muted: true,
+            collapsed: true,
+            ..comment(1, "hidden text")
+        };
+        let mut layout = CommentLayout::default();
+        layout.relayout(&[muted], 40, 4, '⠋', &HashSet::new(), "");
+
+        assert_eq!(layout.line_range(0), Some(0..1));
+        let header = line_text(&layout.visible_lines(0)[0]);
+        assert!(header.contains("user1") && header.trim_end().ends_with("· muted"));
+    }
+
     #[test]
     fn paging_moves_by_visible_comment_heights() {
         let comments = vec![
@@ -473,7 +543,7 @@ mod tests {
             comment(4, "e"),
         ];
         let mut layout = CommentLayout::default();
-        layout.relayout(&comments, 8, 4, '⠋');
+        layout.relayout(&comments, 8, 4, '⠋', &HashSet::new(), "");
 
         let next = layout.page_down(0);
         assert_eq!(next, 1);
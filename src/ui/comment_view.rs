@@ -1,10 +1,15 @@
-use crate::app::App;
+use crate::api::PollOption;
+use crate::app::{App, CommentFind, CommentFold};
 use crate::ui::theme;
 use crate::ui::{format_age, format_error, now_unix};
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Poll rows above the thread; a longer poll ends in an "… N more" row.
+const MAX_POLL_ROWS: usize = 8;
 
 pub fn render(frame: &mut Frame, app: &App) {
     let area = frame.area();
@@ -15,29 +20,57 @@ pub fn render(frame: &mut Frame, app: &App) {
         .as_ref()
         .map(|s| s.title.as_str())
         .unwrap_or("Comments");
+    let star = if app
+        .current_story
+        .as_ref()
+        .is_some_and(|story| app.is_bookmarked(story.id))
+    {
+        "★ "
+    } else {
+        ""
+    };
+    let offline = match (app.offline, app.comment_stale_secs) {
+        (false, _) => String::new(),
+        (true, None) => " [offline]".to_string(),
+        (true, Some(secs)) => format!(" [offline · cached {} ago]", format_age(0, secs as i64)),
+    };
+    let watching = match (app.is_watching(), app.new_comment_ids.len()) {
+        (false, 0) => String::new(),
+        (false, unseen) => format!(" [{unseen} new]"),
+        (true, 0) => " [watching]".to_string(),
+        (true, unseen) => format!(" [watching · {unseen} new]"),
+    };
     let title = if app.comment_loading {
-        format!("{title} (loading {spinner})")
+        format!("{star}{title}{offline}{watching} (loading {spinner})")
     } else {
-        title.to_string()
+        format!("{star}{title}{offline}{watching}")
     };
     let block = Block::default().borders(Borders::ALL).title(title);
-    let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let (list_area, footer_area) = super::list_footer_areas(inner);
+    let (poll_area, list_area, footer_area) = areas(area, app.comment_poll.len());
+    if !app.comment_poll.is_empty() {
+        let poll_block = Block::default().borders(Borders::BOTTOM);
+        let poll_inner = poll_block.inner(poll_area);
+        frame.render_widget(poll_block, poll_area);
+        frame.render_widget(
+            Paragraph::new(poll_lines(&app.comment_poll, poll_inner.width as usize)),
+            poll_inner,
+        );
+    }
 
     if app.comment_loading && app.comment_list.is_empty() {
         let items = vec![ListItem::new(Line::from(format!("Loading {spinner}")))];
         let list = List::new(items)
             .highlight_symbol("")
-            .highlight_style(theme::SELECTED);
+            .highlight_style(theme::selected());
         let mut state = app.comment_list_state.clone();
         frame.render_stateful_widget(list, list_area, &mut state);
     } else if app.comment_list.is_empty() {
         let items = vec![ListItem::new(Line::from("No comments."))];
         let list = List::new(items)
             .highlight_symbol("")
-            .highlight_style(theme::SELECTED);
+            .highlight_style(theme::selected());
         let mut state = app.comment_list_state.clone();
         frame.render_stateful_widget(list, list_area, &mut state);
     } else {
@@ -57,13 +90,33 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     let now = now_unix();
     let show_copied = app.copied_flash.is_some_and(|t| t.elapsed().as_secs() < 2);
-    let meta = if show_copied {
-        Line::from(Span::styled("Copied!", theme::SUCCESS))
+    let muted = app
+        .muted_flash
+        .as_ref()
+        .filter(|(_, at)| at.elapsed().as_secs() < 2)
+        .map(|(muted, _)| muted);
+    let meta = if let Some(find) = app.comment_find.as_ref().filter(|_| app.find_input_active) {
+        Line::from(vec![
+            Span::styled(format!("Find: {}│", find.query), theme::key()),
+            Span::raw("  "),
+            Span::styled(
+                format!("{} matching  Enter:find  Esc:cancel", find.matches.len()),
+                theme::hint(),
+            ),
+        ])
+    } else if show_copied {
+        Line::from(Span::styled("Copied!", theme::success()))
+    } else if let Some(muted) = muted {
+        Line::from(Span::styled(format!("Muted {muted}"), theme::success()))
     } else if let Some(err) = app.last_error.as_deref() {
         Line::from(vec![Span::styled(
             format!("Error: {}", format_error(err)),
-            theme::ERROR,
+            theme::error(),
         )])
+    } else if let Some(find) = app.comment_find.as_ref() {
+        find_status_line(find)
+    } else if let Some(fold) = app.comment_fold.as_ref() {
+        fold_progress_line(fold)
     } else if let Some(story) = app.current_story.as_ref() {
         let age = format_age(story.time, now);
         Line::from(format!(
@@ -77,12 +130,136 @@ pub fn render(frame: &mut Frame, app: &App) {
     };
 
     let help = Line::from(format!(
-        "j/k:nav  h/←:collapse  l/→:expand  Enter/c:toggle  y:copy  s:summarize  o:comments  O:source  r:refresh  ?:help  q:back    {} comments",
+        "j/k:nav  h/←:collapse  l/→:expand  Enter/c:toggle  p/]/[:thread  y:copy  u:user  w:watch  Tab:new  b:save  s:summarize  o:comments  O:source  r:refresh  ?:help  q:back    {} comments",
         app.comment_list.len()
     ));
     frame.render_widget(Paragraph::new(vec![meta, help]), footer_inner);
 }
 
-pub(crate) fn content_areas(area: Rect) -> (Rect, Rect) {
-    super::bordered_list_footer_areas(area)
+fn find_status_line(find: &CommentFind) -> Line<'static> {
+    let count = match find.position() {
+        Some(position) => format!("{position}/{}", find.matches.len()),
+        None => format!("{} matching", find.matches.len()),
+    };
+    Line::from(vec![
+        Span::styled(format!("Find: {}", find.query), theme::key()),
+        Span::raw("  "),
+        Span::styled(count, theme::value()),
+        Span::raw("  "),
+        Span::styled("n/N:next/prev  Esc:clear", theme::hint()),
+    ])
+}
+
+fn fold_progress_line(fold: &CommentFold) -> Line<'static> {
+    const BAR_WIDTH: usize = 20;
+    let filled = (fold.done * BAR_WIDTH)
+        .checked_div(fold.total)
+        .unwrap_or(BAR_WIDTH);
+    Line::from(vec![
+        Span::styled("Loading replies ", theme::label()),
+        Span::styled("█".repeat(filled), theme::success()),
+        Span::styled("░".repeat(BAR_WIDTH - filled), theme::meta()),
+        Span::styled(format!(" {}/{}", fold.done, fold.total), theme::value()),
+    ])
+}
+
+/// One bar per option, scaled to the leader, with its votes and share.
+fn poll_lines(poll: &[PollOption], width: usize) -> Vec<Line<'static>> {
+    let total: i64 = poll.iter().map(|option| option.score.max(0)).sum();
+    let leader = poll.iter().map(|option| option.score).max().unwrap_or(0);
+    let labels: Vec<String> = poll
+        .iter()
+        .map(|option| {
+            crate::text::hn_html_to_plain(&option.text)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let label_width = labels
+        .iter()
+        .map(|label| label.width())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let shown = if poll.len() > MAX_POLL_ROWS {
+        MAX_POLL_ROWS - 1
+    } else {
+        poll.len()
+    };
+
+    let mut lines: Vec<Line<'static>> = poll
+        .iter()
+        .zip(labels)
+        .take(shown)
+        .map(|(option, label)| {
+            let votes = option.score.max(0);
+            let percent = (votes * 100).checked_div(total).unwrap_or(0);
+            let count = format!(" {} ({percent}%)", option.score);
+            let bar_width = width.saturating_sub(label_width + 1 + count.width());
+            let filled = (votes as usize * bar_width)
+                .checked_div(leader.max(0) as usize)
+                .unwrap_or(0);
+            let bar_style = if votes > 0 && option.score == leader {
+                theme::accent()
+            } else {
+                theme::value()
+            };
+            Line::from(vec![
+                Span::styled(fit_width(&label, label_width), theme::label()),
+                Span::raw(" "),
+                Span::styled("█".repeat(filled), bar_style),
+                Span::styled(count, theme::hint()),
+            ])
+        })
+        .collect();
+    if shown < poll.len() {
+        lines.push(Line::styled(
+            format!("… {} more options", poll.len() - shown),
+            theme::hint(),
+        ));
+    }
+    lines
+}
+
+/// Cut or pad to exactly `width` columns.
+fn fit_width(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for character in text.chars() {
+        let char_width = character.width().unwrap_or(0);
+        if used + char_width > width {
+            break;
+        }
+        out.push(character);
+        used += char_width;
+    }
+    out.push_str(&" ".repeat(width - used));
+    out
+}
+
+fn poll_height(options: usize) -> u16 {
+    match options {
+        0 => 0,
+        // The options plus the rule under them.
+        n => n.min(MAX_POLL_ROWS) as u16 + 1,
+    }
+}
+
+fn areas(area: Rect, poll_options: usize) -> (Rect, Rect, Rect) {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let [poll_area, rest] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(poll_height(poll_options)),
+            Constraint::Min(1),
+        ])
+        .areas(inner);
+    let (list_area, footer_area) = super::list_footer_areas(rest);
+    (poll_area, list_area, footer_area)
+}
+
+pub(crate) fn content_areas(area: Rect, poll_options: usize) -> (Rect, Rect) {
+    let (_, list_area, footer_area) = areas(area, poll_options);
+    (list_area, footer_area)
 }
@@ -1,5 +1,4 @@
-use crate::api::FeedKind;
-use crate::app::App;
+use crate::app::{App, FeedRow};
 use crate::ui::theme;
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span, Text};
@@ -16,45 +15,52 @@ pub fn render(frame: &mut Frame, app: &App) {
     };
 
     let mut lines: Vec<Line<'static>> = Vec::new();
-    lines.push(Line::from(Span::styled("Feed", theme::HEADER)));
+    lines.push(Line::from(Span::styled("Feed", theme::header())));
     lines.push(Line::raw(""));
 
-    for (i, &feed) in FeedKind::ALL.iter().enumerate() {
+    for (i, &row) in FeedRow::ALL.iter().enumerate() {
         let is_cursor = i == popup.feed_cursor;
-        let is_current = feed == app.current_feed;
+        let is_current = match row {
+            FeedRow::Feed(feed) => !app.saved_feed_active && feed == app.current_feed,
+            FeedRow::Saved => app.saved_feed_active,
+        };
+        let label = match row {
+            FeedRow::Saved => format!("{} ({})", row.label(), app.bookmarks.len()),
+            FeedRow::Feed(_) => row.label().to_string(),
+        };
         let marker = if is_cursor { "> " } else { "  " };
         let suffix = if is_current { " *" } else { "" };
         let style = if is_cursor {
-            theme::KEY
+            theme::key()
         } else if is_current {
-            theme::ACCENT
+            theme::accent()
         } else {
-            theme::LABEL
+            theme::label()
         };
         lines.push(Line::from(Span::styled(
-            format!("{marker}{}{suffix}", feed.label()),
+            format!("{marker}{label}{suffix}"),
             style,
         )));
     }
 
     lines.push(Line::raw(""));
     lines.push(Line::from(vec![
-        Span::styled("j/k", theme::KEY),
-        Span::styled(":nav  ", theme::HINT),
-        Span::styled("Enter", theme::KEY),
-        Span::styled(":select  ", theme::HINT),
-        Span::styled("Esc", theme::KEY),
-        Span::styled(":close", theme::HINT),
+        Span::styled("j/k", theme::key()),
+        Span::styled(":nav  ", theme::hint()),
+        Span::styled("Enter", theme::key()),
+        Span::styled(":select  ", theme::hint()),
+        Span::styled("Esc", theme::key()),
+        Span::styled(":close", theme::hint()),
     ]));
 
     frame.render_widget(Clear, popup_rect);
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled("f", theme::HEADER));
+        .title(Span::styled("f", theme::header()));
     let paragraph = Paragraph::new(Text::from(lines))
         .wrap(Wrap { trim: true })
         .block(block)
-        .style(theme::POPUP);
+        .style(theme::popup());
     frame.render_widget(paragraph, popup_rect);
 }
 
@@ -62,7 +68,7 @@ pub(crate) fn popup_rect(area: Rect) -> Option<Rect> {
     if area.width < 10 || area.height < 6 {
         return None;
     }
-    let line_count = FeedKind::ALL.len() + 4;
+    let line_count = FeedRow::ALL.len() + 4;
     let desired_width = area.width.min(40);
     let desired_height = (line_count as u16).saturating_add(2).min(area.height);
     Some(super::centered(area, desired_width, desired_height))
@@ -1,4 +1,5 @@
 use crate::app::{App, View};
+use crate::input::{Action, ArticleAction, HelpAction, InputLayer, Keymap, SummaryAction};
 use crate::ui::{clamped_scroll::ClampedScroll, theme};
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span, Text};
@@ -35,13 +36,13 @@ impl HelpOverlay {
         self.visible
     }
 
-    pub fn set_frame(&mut self, area: Rect, active: View, focus: HelpFocus) {
-        let Some(popup) = popup_rect(area, active, focus) else {
+    pub fn set_frame(&mut self, area: Rect, keymap: &Keymap, active: View, focus: HelpFocus) {
+        let Some(popup) = popup_rect(area, keymap, active, focus) else {
             self.scroll.set_extents(0, 0);
             return;
         };
         let inner = Block::default().borders(Borders::ALL).inner(popup);
-        let wrapped_line_count = content_paragraph(active, focus).line_count(inner.width);
+        let wrapped_line_count = content_paragraph(keymap, active, focus).line_count(inner.width);
         self.scroll
             .set_extents(wrapped_line_count, usize::from(inner.height));
     }
@@ -82,82 +83,332 @@ fn section_title(name: &str, active: bool) -> Line<'static> {
 
 fn kv(keys: &str, desc: &str) -> Line<'static> {
     Line::from(vec![
-        Span::styled(format!("  {keys}"), theme::KEY),
-        Span::styled(format!(": {desc}"), theme::LABEL),
+        Span::styled(format!("  {keys}"), theme::key()),
+        Span::styled(format!(": {desc}"), theme::label()),
     ])
 }
 
-fn content_lines(active: View, focus: HelpFocus) -> Vec<Line<'static>> {
+/// Help rows for one layer, labelled from whatever the keymap binds.
+/// Actions left with no key drop out rather than showing a blank.
+struct Section<'a> {
+    keymap: &'a Keymap,
+    layer: InputLayer,
+    lines: &'a mut Vec<Line<'static>>,
+}
+
+impl Section<'_> {
+    /// Every key that does any of `actions`: `Enter / Space / l / →`.
+    fn any(&mut self, actions: &[Action], desc: &str) {
+        let labels: Vec<String> = actions
+            .iter()
+            .flat_map(|&action| self.keymap.labels(self.layer, action))
+            .collect();
+        if !labels.is_empty() {
+            self.lines.push(kv(&labels.join(" / "), desc));
+        }
+    }
+
+    /// Keys for a pair of opposites, matched up where they line up:
+    /// `j/k, ↓/↑`.
+    fn pair(&mut self, first: Action, second: Action, desc: &str) {
+        let firsts = self.keymap.labels(self.layer, first);
+        let seconds = self.keymap.labels(self.layer, second);
+        let keys = if firsts.len() == seconds.len() {
+            firsts
+                .iter()
+                .zip(&seconds)
+                .map(|(first, second)| format!("{first}/{second}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            [firsts.join(", "), seconds.join(", ")]
+                .into_iter()
+                .filter(|keys| !keys.is_empty())
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+        if !keys.is_empty() {
+            self.lines.push(kv(&keys, desc));
+        }
+    }
+
+    fn text(&mut self, keys: &str, desc: &str) {
+        self.lines.push(kv(keys, desc));
+    }
+}
+
+fn content_lines(keymap: &Keymap, active: View, focus: HelpFocus) -> Vec<Line<'static>> {
+    let close = keymap
+        .labels(InputLayer::Help, Action::Help(HelpAction::Dismiss))
+        .into_iter()
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" or ");
     let mut lines = vec![
-        Line::from(Span::styled("Shortcuts", theme::HEADER)),
-        Line::from(Span::styled("Press ? or Esc to close.", theme::HINT)),
+        Line::from(Span::styled("Shortcuts", theme::header())),
+        Line::from(Span::styled(
+            format!("Press {close} to close."),
+            theme::hint(),
+        )),
         Line::raw(""),
     ];
 
     let in_view = focus == HelpFocus::View;
     let stories_active = in_view && active == View::Stories;
     lines.push(section_title("Stories", stories_active));
-    lines.push(kv("j/k, ↓/↑", "move"));
-    lines.push(kv("gg, G", "top / bottom"));
-    lines.push(kv("Ctrl+d / Ctrl+u", "page down / up"));
-    lines.push(kv("Enter / Space / l / →", "open comments"));
-    lines.push(kv("o", "open source link (browser)"));
-    lines.push(kv("O", "open comments page (browser)"));
-    lines.push(kv("s", "summarize (AI)"));
-    lines.push(kv("v", "view article"));
-    lines.push(kv("/", "search stories"));
-    lines.push(kv("f", "switch feed"));
-    lines.push(kv("F", "filter by title"));
-    lines.push(kv("r", "refresh"));
-    lines.push(kv(",", "settings"));
-    lines.push(kv("q / Esc", "quit"));
+    let mut view = Section {
+        keymap,
+        layer: InputLayer::View,
+        lines: &mut lines,
+    };
+    view.pair(Action::MoveDown, Action::MoveUp, "move");
+    view.pair(Action::GoTop, Action::GoBottom, "top / bottom");
+    view.pair(Action::PageDown, Action::PageUp, "page down / up");
+    view.any(
+        &[Action::Enter, Action::OpenComments, Action::Expand],
+        "open comments",
+    );
+    view.any(&[Action::OpenPrimaryBrowser], "open source link (browser)");
+    view.any(
+        &[Action::OpenSecondaryBrowser],
+        "open comments page (browser)",
+    );
+    view.any(&[Action::ToggleBookmark], "save / unsave story");
+    view.any(&[Action::OpenUser], "author's profile");
+    view.any(&[Action::Summarize], "summarize (AI)");
+    view.any(&[Action::ViewArticle], "view article");
+    view.any(
+        &[Action::StartSearch],
+        "search stories (author:, points>, sort:date …)",
+    );
+    view.text("Tab (while searching)", "search comments instead");
+    view.any(
+        &[Action::OpenFeedFilter],
+        "switch feed (Saved lists saved stories)",
+    );
+    view.any(&[Action::OpenFilter], "filter by title");
+    view.pair(
+        Action::MuteDomain,
+        Action::MuteAuthor,
+        "mute story's domain / author",
+    );
+    view.any(&[Action::Refresh], "refresh");
+    view.any(
+        &[Action::ToggleOffline],
+        "toggle offline mode (disk cache only)",
+    );
+    view.any(
+        &[Action::DownloadOffline],
+        "download loaded stories for offline",
+    );
+    view.any(&[Action::OpenSettings], "settings");
+    view.any(&[Action::BackOrQuit], "quit");
     lines.push(Line::raw(""));
 
     let comments_active = in_view && active == View::Comments;
     lines.push(section_title("Comments", comments_active));
-    lines.push(kv("j/k, ↓/↑", "move"));
-    lines.push(kv("gg, G", "top / bottom"));
-    lines.push(kv("Ctrl+d / Ctrl+u", "page down / up"));
-    lines.push(kv("h / ←", "collapse thread"));
-    lines.push(kv("l / →", "expand thread (loads children)"));
-    lines.push(kv("Enter / c", "toggle collapse/expand"));
-    lines.push(kv("y", "copy selected comment to clipboard"));
-    lines.push(kv("o", "open comments page (browser)"));
-    lines.push(kv("O", "open source link (browser)"));
-    lines.push(kv("s", "summarize (AI)"));
-    lines.push(kv("v", "view article"));
-    lines.push(kv("r", "refresh"));
-    lines.push(kv(",", "settings"));
-    lines.push(kv("q / Esc", "back"));
+    let mut view = Section {
+        keymap,
+        layer: InputLayer::View,
+        lines: &mut lines,
+    };
+    view.pair(Action::MoveDown, Action::MoveUp, "move");
+    view.pair(Action::GoTop, Action::GoBottom, "top / bottom");
+    view.pair(Action::PageDown, Action::PageUp, "page down / up");
+    view.any(&[Action::Collapse], "collapse thread");
+    view.any(&[Action::Expand], "expand thread (loads children)");
+    view.any(
+        &[Action::Enter, Action::ToggleCollapse],
+        "toggle collapse/expand",
+    );
+    view.any(&[Action::StartSearch], "find in thread");
+    view.pair(
+        Action::NextMatch,
+        Action::PreviousMatch,
+        "next / previous match",
+    );
+    view.any(&[Action::ParentComment], "parent comment");
+    view.pair(
+        Action::NextSibling,
+        Action::PreviousSibling,
+        "next / previous sibling",
+    );
+    view.pair(
+        Action::NextRoot,
+        Action::PreviousRoot,
+        "next / previous top-level thread",
+    );
+    view.any(&[Action::CollapseThread], "collapse whole top-level thread");
+    view.pair(
+        Action::CollapseAll,
+        Action::ExpandAll,
+        "collapse to roots / expand all",
+    );
+    let first = keymap.labels(InputLayer::View, Action::ShowLevels(1));
+    let last = keymap.labels(InputLayer::View, Action::ShowLevels(9));
+    if let (Some(first), Some(last)) = (first.first(), last.first()) {
+        view.text(&format!("{first}…{last}"), "show exactly 1–9 levels");
+    }
+    view.any(&[Action::CopyComment], "copy selected comment to clipboard");
+    view.any(&[Action::OpenUser], "comment author's profile");
+    view.any(&[Action::ToggleWatch], "watch for new comments");
+    view.pair(
+        Action::MuteDomain,
+        Action::MuteAuthor,
+        "mute story's domain / comment's author",
+    );
+    view.pair(
+        Action::NextUnread,
+        Action::PreviousUnread,
+        "next / previous new comment",
+    );
+    view.any(
+        &[Action::OpenPrimaryBrowser],
+        "open comments page (browser)",
+    );
+    view.any(
+        &[Action::OpenSecondaryBrowser],
+        "open source link (browser)",
+    );
+    view.any(&[Action::ToggleBookmark], "save / unsave story");
+    view.any(&[Action::Summarize], "summarize (AI)");
+    view.any(&[Action::ViewArticle], "view article");
+    view.any(&[Action::Refresh], "refresh");
+    view.any(
+        &[Action::ToggleOffline],
+        "toggle offline mode (disk cache only)",
+    );
+    view.any(&[Action::OpenSettings], "settings");
+    view.any(&[Action::BackOrQuit], "back");
+    lines.push(Line::raw(""));
+
+    lines.push(section_title("User", in_view && active == View::User));
+    let mut view = Section {
+        keymap,
+        layer: InputLayer::View,
+        lines: &mut lines,
+    };
+    view.pair(Action::MoveDown, Action::MoveUp, "move");
+    view.pair(Action::GoTop, Action::GoBottom, "top / bottom");
+    view.any(
+        &[Action::Enter, Action::OpenComments, Action::Expand],
+        "open thread (at the comment)",
+    );
+    view.pair(
+        Action::OpenPrimaryBrowser,
+        Action::OpenSecondaryBrowser,
+        "open source / HN link (browser)",
+    );
+    view.any(&[Action::ToggleBookmark], "save / unsave story");
+    view.any(&[Action::ViewArticle], "view article");
+    view.any(&[Action::Refresh], "refresh");
+    view.any(&[Action::BackOrQuit], "back");
     lines.push(Line::raw(""));
 
     lines.push(section_title("Summary", focus == HelpFocus::Summary));
-    lines.push(kv("gg, G", "top / bottom"));
+    let mut summary = Section {
+        keymap,
+        layer: InputLayer::Summary,
+        lines: &mut lines,
+    };
+    summary.pair(
+        Action::Summary(SummaryAction::GoTop),
+        Action::Summary(SummaryAction::GoBottom),
+        "top / bottom",
+    );
+    summary.any(
+        &[Action::Summary(SummaryAction::Regenerate)],
+        "regenerate (skip the cached summary)",
+    );
+    summary.any(
+        &[Action::Summary(SummaryAction::PickProfile)],
+        "re-run with another profile",
+    );
+    summary.any(
+        &[Action::Summary(SummaryAction::StartQuestion)],
+        "ask a follow-up about the story",
+    );
+    summary.pair(
+        Action::Summary(SummaryAction::NextCitation),
+        Action::Summary(SummaryAction::PreviousCitation),
+        "next / previous cited comment",
+    );
+    summary.any(
+        &[Action::Summary(SummaryAction::OpenCitation)],
+        "jump to the cited comment",
+    );
     lines.push(Line::raw(""));
 
     lines.push(section_title("Article", focus == HelpFocus::Article));
-    lines.push(kv("j/k, ↓/↑", "scroll"));
-    lines.push(kv("gg, G", "top / bottom"));
-    lines.push(kv("Ctrl+d / Ctrl+u", "page down / up"));
-    lines.push(kv("Tab / Shift+Tab", "next / previous article link"));
-    lines.push(kv("Enter", "open selected article link (browser)"));
-    lines.push(kv("c", "copy article to clipboard"));
-    lines.push(kv("o", "open Story's original URL (browser)"));
-    lines.push(kv("q / Esc", "close (cancels a running fetch)"));
+    let mut article = Section {
+        keymap,
+        layer: InputLayer::Article,
+        lines: &mut lines,
+    };
+    article.pair(
+        Action::Article(ArticleAction::ScrollDown(1)),
+        Action::Article(ArticleAction::ScrollUp(1)),
+        "scroll",
+    );
+    article.pair(
+        Action::Article(ArticleAction::GoTop),
+        Action::Article(ArticleAction::GoBottom),
+        "top / bottom",
+    );
+    article.pair(
+        Action::Article(ArticleAction::PageDown),
+        Action::Article(ArticleAction::PageUp),
+        "page down / up",
+    );
+    article.pair(
+        Action::Article(ArticleAction::SelectNextLink),
+        Action::Article(ArticleAction::SelectPreviousLink),
+        "next / previous article link",
+    );
+    article.any(
+        &[Action::Article(ArticleAction::OpenSelectedLink)],
+        "open selected article link (browser)",
+    );
+    article.any(
+        &[Action::Article(ArticleAction::StartFind)],
+        "find in article",
+    );
+    article.pair(
+        Action::Article(ArticleAction::NextMatch),
+        Action::Article(ArticleAction::PreviousMatch),
+        "next / previous match",
+    );
+    article.any(
+        &[Action::Article(ArticleAction::Copy)],
+        "copy article to clipboard",
+    );
+    article.any(
+        &[Action::Article(ArticleAction::OpenBrowser)],
+        "open Story's original URL (browser)",
+    );
+    article.any(
+        &[Action::Article(ArticleAction::Dismiss)],
+        "close (clears a find first; cancels a running fetch)",
+    );
     lines
 }
 
-fn content_paragraph(active: View, focus: HelpFocus) -> Paragraph<'static> {
-    Paragraph::new(Text::from(content_lines(active, focus))).wrap(Wrap { trim: true })
+fn content_paragraph(keymap: &Keymap, active: View, focus: HelpFocus) -> Paragraph<'static> {
+    Paragraph::new(Text::from(content_lines(keymap, active, focus))).wrap(Wrap { trim: true })
 }
 
-pub(crate) fn popup_rect(area: Rect, active: View, focus: HelpFocus) -> Option<Rect> {
+pub(crate) fn popup_rect(
+    area: Rect,
+    keymap: &Keymap,
+    active: View,
+    focus: HelpFocus,
+) -> Option<Rect> {
     if area.width < 3 || area.height < 3 {
         return None;
     }
     let desired_width = area.width.min(76);
     let inner_width = desired_width.saturating_sub(2);
-    let wrapped_height = content_paragraph(active, focus).line_count(inner_width);
+    let wrapped_height = content_paragraph(keymap, active, focus).line_count(inner_width);
     let desired_height = wrapped_height
         .saturating_add(2)
         .min(usize::from(area.height))
@@ -169,7 +420,7 @@ pub(crate) fn popup_rect(area: Rect, active: View, focus: HelpFocus) -> Option<R
 pub fn render(frame: &mut Frame, app: &App) {
     let area = frame.area();
     let focus = app.help_focus();
-    let Some(popup) = popup_rect(area, app.view, focus) else {
+    let Some(popup) = popup_rect(area, app.keymap(), app.view, focus) else {
         return;
     };
 
@@ -177,11 +428,11 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(Clear, popup);
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled("?", theme::HEADER));
-    let paragraph = content_paragraph(active, focus)
+        .title(Span::styled("?", theme::header()));
+    let paragraph = content_paragraph(app.keymap(), active, focus)
         .scroll((app.help_overlay.render_scroll_offset(), 0))
         .block(block)
-        .style(theme::POPUP);
+        .style(theme::popup());
     frame.render_widget(paragraph, popup);
 }
 
@@ -193,7 +444,12 @@ mod tests {
     #[test]
     fn scrolling_stops_when_the_last_help_line_reaches_the_viewport_bottom() {
         let mut overlay = HelpOverlay::default();
-        overlay.set_frame(Rect::new(0, 0, 80, 10), View::Stories, HelpFocus::View);
+        overlay.set_frame(
+            Rect::new(0, 0, 80, 10),
+            &Keymap::default(),
+            View::Stories,
+            HelpFocus::View,
+        );
 
         overlay.scroll_down(usize::MAX);
         let bottom = overlay.scroll_offset();
@@ -206,7 +462,12 @@ mod tests {
     #[test]
     fn help_that_fits_in_a_tall_terminal_does_not_scroll() {
         let mut overlay = HelpOverlay::default();
-        overlay.set_frame(Rect::new(0, 0, 80, 100), View::Stories, HelpFocus::View);
+        overlay.set_frame(
+            Rect::new(0, 0, 80, 100),
+            &Keymap::default(),
+            View::Stories,
+            HelpFocus::View,
+        );
 
         overlay.scroll_down(usize::MAX);
 
@@ -215,8 +476,21 @@ mod tests {
 
     #[test]
     fn narrow_terminal_sizes_the_popup_for_wrapped_help_lines() {
-        let wide = popup_rect(Rect::new(0, 0, 80, 100), View::Stories, HelpFocus::View).unwrap();
-        let narrow = popup_rect(Rect::new(0, 0, 30, 100), View::Stories, HelpFocus::View).unwrap();
+        let keymap = Keymap::default();
+        let wide = popup_rect(
+            Rect::new(0, 0, 80, 100),
+            &keymap,
+            View::Stories,
+            HelpFocus::View,
+        )
+        .unwrap();
+        let narrow = popup_rect(
+            Rect::new(0, 0, 30, 100),
+            &keymap,
+            View::Stories,
+            HelpFocus::View,
+        )
+        .unwrap();
 
         assert!(
             narrow.height > wide.height,
@@ -226,7 +500,12 @@ mod tests {
 
     #[test]
     fn small_terminal_still_gets_a_scrollable_help_viewport() {
-        let popup = popup_rect(Rect::new(0, 0, 9, 5), View::Stories, HelpFocus::View);
+        let popup = popup_rect(
+            Rect::new(0, 0, 9, 5),
+            &Keymap::default(),
+            View::Stories,
+            HelpFocus::View,
+        );
 
         assert_eq!(popup, Some(Rect::new(0, 0, 9, 5)));
     }
@@ -1,5 +1,7 @@
-use std::ops::RangeInclusive;
+use std::collections::HashSet;
+use std::ops::{Range, RangeInclusive};
 
+use crate::text::find_ignore_case;
 use crate::ui::theme;
 use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 use ratatui::buffer::Buffer;
@@ -31,7 +33,7 @@ pub fn render_markdown_document(
         input,
         base_url,
         selected_link,
-        theme::ARTICLE_LINK_SELECTED,
+        theme::article_link_selected(),
     )
 }
 
@@ -55,7 +57,7 @@ fn render_markdown_document_with_style(
     let mut links = Vec::new();
     let mut active_link = None;
 
-    let base_style = Style::default().fg(theme::TEXT);
+    let base_style = Style::default().fg(theme::text());
 
     for event in parser {
         match event {
@@ -66,7 +68,7 @@ fn render_markdown_document_with_style(
                         lines.push(Line::from(""));
                         need_paragraph_break = false;
                     }
-                    let mut style = theme::HEADER_ACCENT;
+                    let mut style = theme::header_accent();
                     if level == pulldown_cmark::HeadingLevel::H1 {
                         style = style.add_modifier(Modifier::UNDERLINED);
                     }
@@ -121,13 +123,13 @@ fn render_markdown_document_with_style(
                         }
                         _ => format!("{indent}- "),
                     };
-                    prefix_spans = vec![Span::styled(marker, theme::LIST_MARKER)];
+                    prefix_spans = vec![Span::styled(marker, theme::list_marker())];
                 }
                 Tag::BlockQuote(_) => {
                     flush_line(&mut lines, &mut current_spans, &prefix_spans);
                     prefix_spans = vec![Span::styled(
                         "> ".to_string(),
-                        Style::default().fg(theme::GREEN),
+                        Style::default().fg(theme::green()),
                     )];
                 }
                 Tag::Link { dest_url, .. } => {
@@ -139,7 +141,7 @@ fn render_markdown_document_with_style(
                     });
                     let link_style = match link_index {
                         Some(index) if selected_link == Some(index) => selected_link_style,
-                        Some(_) => top.fg(theme::BLUE).add_modifier(Modifier::UNDERLINED),
+                        Some(_) => top.fg(theme::blue()).add_modifier(Modifier::UNDERLINED),
                         None => top,
                     };
                     active_link = link_index;
@@ -192,7 +194,7 @@ fn render_markdown_document_with_style(
                         if !current_spans.is_empty() {
                             flush_line(&mut lines, &mut current_spans, &prefix_spans);
                         }
-                        current_spans.push(Span::styled(format!("  {line_str}"), theme::CODE));
+                        current_spans.push(Span::styled(format!("  {line_str}"), theme::code()));
                     }
                 } else {
                     let style = current_style(&style_stack, base_style);
@@ -211,7 +213,7 @@ fn render_markdown_document_with_style(
                 let style = if active_link.is_some() {
                     current_style(&style_stack, base_style)
                 } else {
-                    theme::CODE
+                    theme::code()
                 };
                 current_spans.push(Span::styled(format!("`{code}`"), style));
             }
@@ -225,7 +227,7 @@ fn render_markdown_document_with_style(
                 flush_line(&mut lines, &mut current_spans, &prefix_spans);
                 lines.push(Line::from(Span::styled(
                     "───────────────────────",
-                    theme::META,
+                    theme::meta(),
                 )));
                 need_paragraph_break = true;
             }
@@ -266,7 +268,219 @@ pub fn link_row_range(
     let document =
         render_markdown_document_with_style(input, base_url, Some(link_index), LINK_PROBE_STYLE);
     document.links.get(link_index)?;
-    let paragraph = Paragraph::new(document.lines).wrap(Wrap { trim: false });
+    probe_rows(document.lines, width)
+}
+
+/// `lines` with every match of `query` picked out, ignoring case, and the
+/// `current` one (counted through the whole document) set apart. Matches
+/// are found per rendered line, so one split across lines is missed.
+pub fn highlight_matches(
+    lines: Vec<Line<'static>>,
+    query: &str,
+    current: Option<usize>,
+) -> Vec<Line<'static>> {
+    highlight_matches_with_style(lines, query, current, theme::find_match_current())
+}
+
+/// How many matches [`highlight_matches`] would pick out.
+pub fn count_matches(lines: &[Line<'_>], query: &str) -> usize {
+    lines
+        .iter()
+        .map(|line| find_ignore_case(&line_text(line), query).len())
+        .sum()
+}
+
+/// Rows the `match_index`th match of `query` occupies once `lines` wrap to
+/// `width`.
+pub fn match_row_range(
+    lines: Vec<Line<'static>>,
+    query: &str,
+    match_index: usize,
+    width: u16,
+) -> Option<RangeInclusive<usize>> {
+    if width == 0 || match_index >= count_matches(&lines, query) {
+        return None;
+    }
+    let lines = highlight_matches_with_style(lines, query, Some(match_index), LINK_PROBE_STYLE);
+    probe_rows(lines, width)
+}
+
+fn highlight_matches_with_style(
+    lines: Vec<Line<'static>>,
+    query: &str,
+    current: Option<usize>,
+    current_style: Style,
+) -> Vec<Line<'static>> {
+    highlight_ranges(
+        lines,
+        |text| find_ignore_case(text, query),
+        current,
+        current_style,
+        theme::find_match(),
+    )
+}
+
+/// Comment IDs cited in `lines`, in reading order: bracketed numbers, alone
+/// or in a list like `[123, 456]`, that are among `known`.
+pub fn cited_comments(lines: &[Line<'_>], known: &HashSet<u64>) -> Vec<u64> {
+    lines
+        .iter()
+        .flat_map(|line| {
+            citation_ranges(&line_text(line), known)
+                .into_iter()
+                .map(|(_, id)| id)
+        })
+        .collect()
+}
+
+/// `lines` with each citation [`cited_comments`] finds styled as a link,
+/// the `selected` one set apart.
+pub fn highlight_citations(
+    lines: Vec<Line<'static>>,
+    known: &HashSet<u64>,
+    selected: Option<usize>,
+) -> Vec<Line<'static>> {
+    highlight_citations_with_style(lines, known, selected, theme::article_link_selected())
+}
+
+/// Rows the `index`th citation occupies once `lines` wrap to `width`.
+pub fn citation_row_range(
+    lines: Vec<Line<'static>>,
+    known: &HashSet<u64>,
+    index: usize,
+    width: u16,
+) -> Option<RangeInclusive<usize>> {
+    if width == 0 || index >= cited_comments(&lines, known).len() {
+        return None;
+    }
+    let lines = highlight_citations_with_style(lines, known, Some(index), LINK_PROBE_STYLE);
+    probe_rows(lines, width)
+}
+
+fn highlight_citations_with_style(
+    lines: Vec<Line<'static>>,
+    known: &HashSet<u64>,
+    selected: Option<usize>,
+    selected_style: Style,
+) -> Vec<Line<'static>> {
+    if known.is_empty() {
+        return lines;
+    }
+    highlight_ranges(
+        lines,
+        |text| {
+            citation_ranges(text, known)
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect()
+        },
+        selected,
+        selected_style,
+        theme::citation(),
+    )
+}
+
+/// The digits of each known ID inside `[...]` groups that hold nothing but
+/// IDs, commas and spaces. A group followed by `(` is a markdown link the
+/// renderer already consumed, so it never reaches here.
+fn citation_ranges(text: &str, known: &HashSet<u64>) -> Vec<(Range<usize>, u64)> {
+    let mut citations = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = text[rest..].find('[').map(|offset| rest + offset) {
+        let Some(close) = text[open..].find(']').map(|offset| open + offset) else {
+            break;
+        };
+        let inner = &text[open + 1..close];
+        let only_ids = !inner.trim().is_empty()
+            && inner
+                .chars()
+                .all(|character| character.is_ascii_digit() || matches!(character, ',' | ' '));
+        if only_ids {
+            let mut start = open + 1;
+            for part in inner.split(',') {
+                let digits = part.trim();
+                let offset = start + (part.len() - part.trim_start().len());
+                if let Some(id) = digits.parse().ok().filter(|id| known.contains(id)) {
+                    citations.push((offset..offset + digits.len(), id));
+                }
+                start += part.len() + 1;
+            }
+        }
+        rest = open + 1;
+    }
+    citations
+}
+
+/// `lines` with each byte range `ranges_for` finds in a line's text
+/// restyled, the `current` one (counted through all lines) set apart.
+fn highlight_ranges(
+    lines: Vec<Line<'static>>,
+    ranges_for: impl Fn(&str) -> Vec<Range<usize>>,
+    current: Option<usize>,
+    current_style: Style,
+    other_style: Style,
+) -> Vec<Line<'static>> {
+    let mut seen = 0;
+    lines
+        .into_iter()
+        .map(|line| {
+            let text = line_text(&line);
+            let matches = ranges_for(&text);
+            if matches.is_empty() {
+                return line;
+            }
+            let style_for = |index: usize| {
+                if current == Some(seen + index) {
+                    current_style
+                } else {
+                    other_style
+                }
+            };
+            let mut spans = Vec::with_capacity(line.spans.len() + matches.len() * 2);
+            let mut span_start = 0;
+            for span in &line.spans {
+                let span_end = span_start + span.content.len();
+                let mut cursor = span_start;
+                while cursor < span_end {
+                    let inside = matches
+                        .iter()
+                        .position(|range| range.start <= cursor && cursor < range.end);
+                    let (end, style) = match inside {
+                        Some(index) => (
+                            matches[index].end.min(span_end),
+                            span.style.patch(style_for(index)),
+                        ),
+                        None => (
+                            matches
+                                .iter()
+                                .map(|range| range.start)
+                                .find(|&start| start > cursor)
+                                .map_or(span_end, |start| start.min(span_end)),
+                            span.style,
+                        ),
+                    };
+                    spans.push(Span::styled(text[cursor..end].to_string(), style));
+                    cursor = end;
+                }
+                span_start = span_end;
+            }
+            seen += matches.len();
+            Line { spans, ..line }
+        })
+        .collect()
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// First and last rows holding visible text in the probe background once
+/// `lines` wrap to `width`.
+fn probe_rows(lines: Vec<Line<'static>>, width: u16) -> Option<RangeInclusive<usize>> {
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
     let height = u16::try_from(paragraph.line_count(width))
         .expect("article rendered height exceeds ratatui's u16 limit");
     if height == 0 {
@@ -280,7 +494,7 @@ pub fn link_row_range(
     let mut first = None;
     let mut last = None;
     for row in 0..height {
-        let contains_link = (0..width).any(|column| {
+        let contains_probe = (0..width).any(|column| {
             let cell = &buffer[(column, row)];
             cell.bg == LINK_PROBE_BACKGROUND
                 && UnicodeWidthStr::width(cell.symbol()) > 0
@@ -289,12 +503,12 @@ pub fn link_row_range(
                     .chars()
                     .any(|character| !character.is_control())
         });
-        if contains_link {
+        if contains_probe {
             first.get_or_insert(usize::from(row));
             last = Some(usize::from(row));
         }
     }
-    Some(first?..=last.expect("first selected-link row has a last row"))
+    Some(first?..=last.expect("first probed row has a last row"))
 }
 
 fn current_style(stack: &[Style], base: Style) -> Style {
@@ -365,4 +579,49 @@ mod tests {
             .add_modifier
             .contains(Modifier::UNDERLINED));
     }
+
+    #[test]
+    fn highlights_matches_across_span_boundaries_and_counts_through_the_document() {
+        let lines = render_markdown("Some **bold**ly text\n\nbOLDly again");
+
+        assert_eq!(count_matches(&lines, "boldly"), 2);
+        let lines = highlight_matches(lines, "boldly", Some(1));
+
+        let highlighted: Vec<(String, Style)> = lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .filter(|span| span.style.bg.is_some())
+            .map(|span| (span.content.to_string(), span.style))
+            .collect();
+        assert_eq!(highlighted.len(), 3);
+        assert_eq!(highlighted[0].0, "bold");
+        assert!(highlighted[0].1.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(highlighted[1].0, "ly");
+        assert_eq!(highlighted[2].0, "bOLDly");
+        assert_eq!(highlighted[2].1, theme::find_match_current());
+    }
+
+    #[test]
+    fn cites_only_known_ids_in_bracketed_groups() {
+        let known = HashSet::from([11, 12, 13]);
+        let lines = render_markdown(
+            "Pricing is steep [11]. Others disagree [12, 99, 13].\n\n[7] and [a 12]",
+        );
+
+        assert_eq!(cited_comments(&lines, &known), vec![11, 12, 13]);
+        let lines = highlight_citations(lines, &known, Some(1));
+        let cited: Vec<String> = lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .filter(|span| span.style.add_modifier.contains(Modifier::UNDERLINED))
+            .map(|span| span.content.to_string())
+            .collect();
+        assert_eq!(cited, vec!["11", "12", "13"]);
+        let selected = lines[0]
+            .spans
+            .iter()
+            .find(|span| span.content == "12")
+            .expect("selected citation span");
+        assert_eq!(selected.style, theme::article_link_selected());
+    }
 }
@@ -1,5 +1,6 @@
 pub mod article_overlay;
 pub(crate) mod clamped_scroll;
+pub mod color_depth;
 pub mod comment_layout;
 pub mod comment_view;
 pub mod feed_filter;
@@ -10,6 +11,7 @@ pub mod settings;
 pub mod story_list;
 pub mod summary_overlay;
 pub mod theme;
+pub mod user_view;
 
 use crate::app::{App, View};
 use crate::input::InputLayer;
@@ -41,6 +43,7 @@ pub fn render(frame: &mut Frame, app: &App) {
     match app.view {
         View::Stories => story_list::render(frame, app),
         View::Comments => comment_view::render(frame, app),
+        View::User => user_view::render(frame, app),
     }
 
     let layer = app.input_layer();
@@ -48,7 +51,10 @@ pub fn render(frame: &mut Frame, app: &App) {
         layer,
         InputLayer::Help
             | InputLayer::Summary
+            | InputLayer::SummaryChat
+            | InputLayer::ProfilePicker
             | InputLayer::Article
+            | InputLayer::ArticleFind
             | InputLayer::FeedFilter
             | InputLayer::Settings
             | InputLayer::SettingsEditor
@@ -59,16 +65,21 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     match layer {
         InputLayer::Help => help::render(frame, app),
-        InputLayer::Summary => {
+        InputLayer::Summary | InputLayer::SummaryChat | InputLayer::ProfilePicker => {
             summary_overlay::render(frame, &app.summary_overlay, app.spinner_frame());
         }
-        InputLayer::Article => {
+        InputLayer::Article | InputLayer::ArticleFind => {
             article_overlay::render(frame, &app.article_overlay, app.spinner_frame());
         }
         InputLayer::FeedFilter => feed_filter::render(frame, app),
         InputLayer::Settings | InputLayer::SettingsEditor => settings::render(frame, app),
-        InputLayer::FilterText | InputLayer::SearchText | InputLayer::View => {}
+        InputLayer::FilterText
+        | InputLayer::SearchText
+        | InputLayer::FindText
+        | InputLayer::View => {}
     }
+
+    color_depth::downgrade_buffer(frame.buffer_mut());
 }
 
 pub(crate) fn now_unix() -> i64 {
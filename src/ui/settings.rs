@@ -27,8 +27,11 @@ fn render_popup(frame: &mut Frame, popup: &SettingsPopup) {
     };
 
     frame.render_widget(Clear, areas.popup);
-    frame.render_widget(popup_block().style(theme::POPUP), areas.popup);
-    frame.render_widget(settings_paragraph(popup).style(theme::POPUP), areas.content);
+    frame.render_widget(popup_block().style(theme::popup()), areas.popup);
+    frame.render_widget(
+        settings_paragraph(popup).style(theme::popup()),
+        areas.content,
+    );
 }
 
 fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
@@ -40,7 +43,7 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
         .unwrap_or(0);
 
     let mut lines: Vec<Line<'static>> = Vec::new();
-    lines.push(Line::from(Span::styled("Settings", theme::HEADER)));
+    lines.push(Line::from(Span::styled("Settings", theme::header())));
     lines.push(Line::raw(""));
 
     for (i, row) in SettingsPopup::rows().iter().copied().enumerate() {
@@ -63,11 +66,11 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
                     value.to_string()
                 };
                 let style = if is_editing {
-                    theme::SUCCESS
+                    theme::success()
                 } else if is_cursor {
-                    theme::ACCENT
+                    theme::accent()
                 } else {
-                    theme::LABEL
+                    theme::label()
                 };
 
                 if is_editing {
@@ -86,22 +89,22 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
 
                     lines.push(Line::from(vec![
                         Span::styled(format!("{marker}{padded_label}: "), style),
-                        Span::styled(before, theme::SUCCESS),
-                        Span::styled(cursor_char, theme::BLOCK_CURSOR),
-                        Span::styled(after, theme::SUCCESS),
+                        Span::styled(before, theme::success()),
+                        Span::styled(cursor_char, theme::block_cursor()),
+                        Span::styled(after, theme::success()),
                     ]));
                 } else {
                     lines.push(Line::from(vec![
                         Span::styled(format!("{marker}{padded_label}: "), style),
-                        Span::styled(display_value, theme::VALUE),
+                        Span::styled(display_value, theme::value()),
                     ]));
                 }
             }
             SettingsRow::TestConnection => {
                 let style = if is_cursor {
-                    theme::ACCENT
+                    theme::accent()
                 } else {
-                    theme::LABEL
+                    theme::label()
                 };
                 lines.push(Line::from(Span::styled(
                     format!("{marker}[ Test connection ]"),
@@ -112,14 +115,17 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
     }
 
     if let Some(status) = &popup.api_key_status {
-        lines.push(Line::from(Span::styled(format!("  {status}"), theme::HINT)));
+        lines.push(Line::from(Span::styled(
+            format!("  {status}"),
+            theme::hint(),
+        )));
     }
 
     let endpoint = popup.resolved_endpoint_preview();
     let endpoint_style = if endpoint.is_error() {
-        theme::WARN
+        theme::warn()
     } else {
-        theme::HINT
+        theme::hint()
     };
     lines.push(Line::from(Span::styled(
         format!("  {}", endpoint.text()),
@@ -129,17 +135,17 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
     match &popup.connection_test {
         ConnectionTestState::Idle => {}
         ConnectionTestState::Testing => {
-            lines.push(Line::from(Span::styled("  ⏳ testing…", theme::HINT)))
+            lines.push(Line::from(Span::styled("  ⏳ testing…", theme::hint())))
         }
         ConnectionTestState::Success { model, ttft } => {
             lines.push(Line::from(Span::styled(
                 format!("  ✓ ok · {model} · {}", format_ttft(*ttft)),
-                theme::SUCCESS,
+                theme::success(),
             )));
         }
         ConnectionTestState::Error(message) => lines.push(Line::from(Span::styled(
             format!("  ✗ {message}"),
-            theme::ERROR,
+            theme::error(),
         ))),
     }
 
@@ -151,18 +157,18 @@ fn settings_paragraph(popup: &SettingsPopup) -> Paragraph<'static> {
 
     if show_saved {
         lines.push(Line::from(vec![
-            Span::styled("Saved! ", theme::SUCCESS),
-            Span::styled("Esc/q", theme::KEY),
-            Span::styled(":close", theme::HINT),
+            Span::styled("Saved! ", theme::success()),
+            Span::styled("Esc/q", theme::key()),
+            Span::styled(":close", theme::hint()),
         ]));
     } else {
         lines.push(Line::from(vec![
-            Span::styled("j/k", theme::KEY),
-            Span::styled(":nav  ", theme::HINT),
-            Span::styled("Enter", theme::KEY),
-            Span::styled(":activate  ", theme::HINT),
-            Span::styled("Esc/q", theme::KEY),
-            Span::styled(":close", theme::HINT),
+            Span::styled("j/k", theme::key()),
+            Span::styled(":nav  ", theme::hint()),
+            Span::styled("Enter", theme::key()),
+            Span::styled(":activate  ", theme::hint()),
+            Span::styled("Esc/q", theme::key()),
+            Span::styled(":close", theme::hint()),
         ]));
     }
 
@@ -180,7 +186,7 @@ fn format_ttft(ttft: Duration) -> String {
 fn popup_block() -> Block<'static> {
     Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled(",", theme::HEADER))
+        .title(Span::styled(",", theme::header()))
 }
 
 fn settings_areas(area: Rect, popup: &SettingsPopup) -> Option<SettingsAreas> {
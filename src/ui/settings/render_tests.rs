@@ -13,9 +13,8 @@ fn make_popup(model: &str, base_url: &str) -> SettingsPopup {
         model: model.to_string(),
         api_key: String::new(),
         base_url: base_url.to_string(),
-        max_comments: "200".to_string(),
+        max_prompt_tokens: "32000".to_string(),
         include_article: "true".to_string(),
-        max_article_chars: "20000".to_string(),
         system_prompt: String::new(),
         api_key_status: None,
         connection_test: crate::app::ConnectionTestState::Idle,
@@ -80,7 +79,7 @@ fn resolution_failure_is_rendered_inline_with_warn_style() {
     let (buffer, area) = render_test_popup(&popup, 120, 30);
     let (column, row) = find_text(&buffer, area, "missing base URL for provider", area.top());
 
-    assert_eq!(buffer[(column, row)].fg, theme::PEACH);
+    assert_eq!(buffer[(column, row)].fg, theme::peach());
     let (_, action_row) = find_text(&buffer, area, "Pass base_url or set", row);
     find_text(
         &buffer,
@@ -143,14 +142,14 @@ fn api_key_provenance_and_resolved_endpoint_share_the_status_area() {
 }
 
 #[test]
-fn connection_test_is_rendered_as_the_eighth_navigable_row() {
+fn connection_test_is_rendered_as_the_seventh_navigable_row() {
     let mut popup = make_popup("custom/model", "https://gateway.example");
-    popup.cursor = 7;
+    popup.cursor = 6;
 
     let (buffer, area) = render_test_popup(&popup, 120, 30);
     let (column, row) = find_text(&buffer, area, "> [ Test connection ]", area.top());
 
-    assert_eq!(buffer[(column, row)].fg, theme::MAUVE);
+    assert_eq!(buffer[(column, row)].fg, theme::mauve());
 }
 
 #[test]
@@ -159,7 +158,7 @@ fn connection_test_states_use_their_semantic_styles() {
         (
             crate::app::ConnectionTestState::Testing,
             "testing…".to_string(),
-            theme::SUBTEXT0,
+            theme::subtext0(),
         ),
         (
             crate::app::ConnectionTestState::Success {
@@ -167,12 +166,12 @@ fn connection_test_states_use_their_semantic_styles() {
                 ttft: Duration::from_millis(125),
             },
             "ok · fallback/served-model · 125ms".to_string(),
-            theme::GREEN,
+            theme::green(),
         ),
         (
             crate::app::ConnectionTestState::Error("check API key · invalid token".to_string()),
             "check API key · invalid token".to_string(),
-            theme::RED,
+            theme::red(),
         ),
     ];
 
@@ -1,4 +1,5 @@
-use crate::app::App;
+use crate::api::CommentHit;
+use crate::app::{App, FeedRow, OfflineDownload};
 use crate::ui::theme;
 use crate::ui::{
     domain_from_url, domain_icon, format_age, format_error, now_unix, FALLBACK_DOMAIN_ICON,
@@ -14,20 +15,60 @@ pub fn render(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     let spinner = app.spinner_frame();
-    let feed_label = app.current_feed.label();
-    let filter_suffix = if !app.keyword_filter.is_empty() {
-        let visible = app.visible_story_count();
-        format!(" (filtered: {visible})")
+    let feed_label = if app.saved_feed_active {
+        FeedRow::Saved.label()
     } else {
-        String::new()
+        app.current_feed.label()
+    };
+    let feed_label = if app.offline {
+        let stale = app
+            .stories
+            .iter()
+            .filter(|story| story.stale_secs.is_some())
+            .count();
+        if stale > 0 {
+            format!("{feed_label} [offline · {stale} stale]")
+        } else {
+            format!("{feed_label} [offline]")
+        }
+    } else {
+        feed_label.to_string()
+    };
+    // Offline and Saved listings come from disk, whatever the backend.
+    let backend = app
+        .active_backend()
+        .filter(|_| !app.offline && !app.saved_feed_active);
+    let feed_label = match backend {
+        Some((backend, true)) => format!("{feed_label} · {} (failover)", backend.as_str()),
+        Some((backend, false)) => format!("{feed_label} · {}", backend.as_str()),
+        None => feed_label,
+    };
+    let filter_suffix = match app.active_feed_filter() {
+        _ if !app.is_story_list_filtered() => String::new(),
+        Some(named) => format!(" ({}: {})", named.name, app.visible_story_count()),
+        None if app.keyword_filter.is_empty() => {
+            match app.stories.len().saturating_sub(app.visible_story_count()) {
+                0 => String::new(),
+                muted => format!(" ({muted} muted)"),
+            }
+        }
+        None => format!(" (filtered: {})", app.visible_story_count()),
     };
     let title = if app.search_active {
         let n = app.stories.len();
         let q = &app.search_query;
+        let more = if app.has_more_stories { "+" } else { "" };
+        let label = if app.comment_search_active() {
+            "Search comments"
+        } else {
+            "Search"
+        };
         if app.story_loading {
-            format!("Search: {q} (loading {spinner})")
+            format!("{label}: {q} (loading {spinner})")
+        } else if app.is_search_page_in_flight() {
+            format!("{label}: {q} ({n}{more} results, loading more {spinner}){filter_suffix}")
         } else {
-            format!("Search: {q} ({n} results){filter_suffix}")
+            format!("{label}: {q} ({n}{more} results){filter_suffix}")
         }
     } else if app.story_loading && app.stories.is_empty() {
         format!("{feed_label} (loading {spinner})")
@@ -66,19 +107,43 @@ pub fn render(frame: &mut Frame, app: &App) {
     let half_viewport = (app.story_page_size / 2).max(1);
 
     let now = now_unix();
-    let use_filter = !app.keyword_filter.is_empty();
+    let use_filter = app.is_story_list_filtered();
     let visible_count = app.visible_story_count();
 
     let items = if app.story_loading && app.stories.is_empty() {
         vec![ListItem::new(Line::from(format!("Loading {spinner}")))]
+    } else if visible_count == 0 && use_filter && app.keyword_filter.is_empty() {
+        let message = match app.active_feed_filter() {
+            Some(named) => format!(
+                "No stories pass the {} filter from config.toml.",
+                named.name
+            ),
+            None => "Every story here is muted. See [mute] in config.toml.".to_string(),
+        };
+        vec![ListItem::new(Line::from(message))]
     } else if visible_count == 0 && use_filter {
         vec![ListItem::new(Line::from(
             "No stories match filter. Press F to edit or Esc to clear.",
         ))]
+    } else if app.stories.is_empty() && app.saved_feed_active && !app.search_active {
+        vec![ListItem::new(Line::from(
+            "No saved stories. Press b on a story to save it.",
+        ))]
     } else if app.stories.is_empty() {
         vec![ListItem::new(Line::from(
             "No stories loaded. Press r to refresh.",
         ))]
+    } else if !app.comment_hits.is_empty() {
+        (0..visible_count)
+            .map(|idx| {
+                let hit_idx = if use_filter {
+                    app.visible_story_indices[idx]
+                } else {
+                    idx
+                };
+                comment_hit_item(hit_idx + 1, &app.comment_hits[hit_idx], now)
+            })
+            .collect()
     } else {
         let story_data: Vec<_> = (0..visible_count)
             .map(|idx| {
@@ -97,7 +162,9 @@ pub fn render(frame: &mut Frame, app: &App) {
                     story.score,
                     story.comment_count,
                     story.time,
+                    story.stale_secs,
                     app.is_comment_prefetching_for_story(story.id),
+                    app.is_bookmarked(story.id),
                 )
             })
             .collect();
@@ -105,7 +172,19 @@ pub fn render(frame: &mut Frame, app: &App) {
         story_data
             .into_iter()
             .map(
-                |(idx, story_idx, id, title, url, score, comment_count, time, prefetching)| {
+                |(
+                    idx,
+                    story_idx,
+                    id,
+                    title,
+                    url,
+                    score,
+                    comment_count,
+                    time,
+                    stale_secs,
+                    prefetching,
+                    bookmarked,
+                )| {
                     let domain = url
                         .as_deref()
                         .and_then(domain_from_url)
@@ -133,11 +212,14 @@ pub fn render(frame: &mut Frame, app: &App) {
                     }
 
                     let display_num = story_idx + 1;
-                    let mut spans = vec![
-                        Span::styled(format!("{:>2}. ", display_num), base_style),
+                    let mut spans = vec![Span::styled(format!("{:>2}. ", display_num), base_style)];
+                    if bookmarked {
+                        spans.push(Span::styled("★ ", theme::accent()));
+                    }
+                    spans.extend([
                         Span::styled(format!("{icon} "), base_style),
                         Span::styled(title, base_style),
-                    ];
+                    ]);
                     if known_icon.is_none() {
                         spans.push(Span::styled(
                             format!(" ({domain})"),
@@ -154,7 +236,14 @@ pub fn render(frame: &mut Frame, app: &App) {
                     if app.search_active {
                         spans.push(Span::styled(
                             format!(" {}", format_age(time, now)),
-                            theme::HINT,
+                            theme::hint(),
+                        ));
+                    }
+
+                    if let Some(secs) = stale_secs {
+                        spans.push(Span::styled(
+                            format!(" cached {} ago", format_age(0, secs as i64)),
+                            theme::warn(),
                         ));
                     }
 
@@ -174,7 +263,7 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     let list = List::new(items)
         .highlight_symbol("")
-        .highlight_style(theme::SELECTED.add_modifier(Modifier::BOLD));
+        .highlight_style(theme::selected().add_modifier(Modifier::BOLD));
     let mut state = app.story_list_state.clone();
     frame.render_stateful_widget(list, list_area, &mut state);
 
@@ -183,34 +272,48 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(footer_block, footer_area);
 
     let show_copied = app.copied_flash.is_some_and(|t| t.elapsed().as_secs() < 2);
+    let muted = app
+        .muted_flash
+        .as_ref()
+        .filter(|(_, at)| at.elapsed().as_secs() < 2)
+        .map(|(muted, _)| muted);
     let meta = if show_copied {
-        Line::from(Span::styled("Copied!", theme::SUCCESS))
+        Line::from(Span::styled("Copied!", theme::success()))
+    } else if let Some(muted) = muted {
+        Line::from(Span::styled(format!("Muted {muted}"), theme::success()))
     } else if app.filter_input_active {
         let cursor = format!("Filter: {}│", app.keyword_filter);
         Line::from(vec![
-            Span::styled(cursor, theme::KEY),
+            Span::styled(cursor, theme::key()),
             Span::raw("  "),
             Span::styled(
                 if app.keyword_filter.is_empty() {
-                    "regex supported  Enter:apply  Esc:clear"
+                    "points>=50 -is:seen domain:… or a regex  Enter:apply  Esc:clear"
                 } else {
                     "Enter:apply  Esc:clear"
                 },
-                theme::HINT,
+                theme::hint(),
             ),
         ])
     } else if app.search_input_active {
         let cursor = format!("/ {}│", app.search_query);
+        let other = app.search_scope.toggled().label();
         Line::from(vec![
-            Span::styled(cursor, theme::KEY),
+            Span::styled(format!("[{}] ", app.search_scope.label()), theme::accent()),
+            Span::styled(cursor, theme::key()),
             Span::raw("  "),
-            Span::styled("Enter:search  Esc:cancel", theme::HINT),
+            Span::styled(
+                format!("Tab:{other}  Enter:search  Esc:cancel"),
+                theme::hint(),
+            ),
         ])
     } else if let Some(err) = app.last_error.as_deref() {
         Line::from(vec![Span::styled(
             format!("Error: {}", format_error(err)),
-            theme::ERROR,
+            theme::error(),
         )])
+    } else if let Some(download) = app.offline_download {
+        download_progress_line(download)
     } else if let Some(story) = app.selected_story() {
         let age = format_age(story.time, now);
         let score_style = Style::default()
@@ -223,16 +326,16 @@ pub fn render(frame: &mut Frame, app: &App) {
         let mut spans = vec![
             Span::styled(format!("{} pts", story.score), score_style),
             Span::raw(format!(" by {} ", story.by)),
-            Span::styled(age.to_string(), theme::HINT),
+            Span::styled(age.to_string(), theme::hint()),
             Span::raw(" | "),
             Span::styled(format!("{} comments", story.comment_count), comment_style),
         ];
-        if app.is_story_prefetch_in_flight() {
+        if app.is_story_prefetch_in_flight() || app.is_search_page_in_flight() {
             spans.push(Span::raw(" | "));
             spans.push(Span::styled(
                 "loading more…",
                 Style::default()
-                    .fg(theme::SUBTEXT0)
+                    .fg(theme::subtext0())
                     .add_modifier(Modifier::ITALIC),
             ));
         }
@@ -247,7 +350,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         Line::from("")
     } else if app.search_input_active || app.search_active {
         Line::from(
-            "j/k:nav  Enter/Space/l/→:comments  o:source  /:search  f:feeds  Esc:back to feed  ?:help"
+            "j/k:nav  Enter/Space/l/→:comments  o:source  b:save  /:search  f:feeds  Esc:back to feed  ?:help"
                 .to_string(),
         )
     } else {
@@ -264,13 +367,65 @@ pub fn render(frame: &mut Frame, app: &App) {
         };
         let filter_hint = if use_filter { "  F:clear filter" } else { "" };
         Line::from(format!(
-            "j/k:nav  Enter/Space/l/→:comments  o:source  O:comments  /:search  f:feeds  F:filter  r:refresh  ?:help  q:quit{filter_hint}    {count_info}"
+            "j/k:nav  Enter/Space/l/→:comments  o:source  O:comments  b:save  u:user  /:search  f:feeds  F:filter  r:refresh  D:download  ?:help  q:quit{filter_hint}    {count_info}"
         ))
     };
     let paragraph = Paragraph::new(vec![meta, help]);
     frame.render_widget(paragraph, footer_inner);
 }
 
+/// Characters of the comment shown after its author and story title.
+const COMMENT_SNIPPET_CHARS: usize = 200;
+
+/// A comment's HN HTML flattened to one line, cut to a list row's worth.
+pub(crate) fn comment_snippet(text: &str) -> String {
+    crate::text::hn_html_to_plain(text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(COMMENT_SNIPPET_CHARS)
+        .collect()
+}
+
+fn comment_hit_item(number: usize, hit: &CommentHit, now: i64) -> ListItem<'static> {
+    let snippet = comment_snippet(&hit.text);
+    let title = decode_html_entities(&hit.story.title).into_owned();
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{number:>2}. "), theme::meta()),
+        Span::styled(hit.by.clone(), theme::accent()),
+        Span::styled(" on ", theme::hint()),
+        Span::styled(title, theme::label()),
+        Span::styled(format!(" {}", format_age(hit.time, now)), theme::hint()),
+        Span::raw("  "),
+        Span::styled(snippet, theme::value()),
+    ]))
+}
+
+fn download_progress_line(download: OfflineDownload) -> Line<'static> {
+    const BAR_WIDTH: usize = 20;
+    let filled = (download.done * BAR_WIDTH)
+        .checked_div(download.total)
+        .unwrap_or(BAR_WIDTH);
+    let mut spans = vec![
+        Span::styled("Downloading for offline ", theme::label()),
+        Span::styled("█".repeat(filled), theme::success()),
+        Span::styled("░".repeat(BAR_WIDTH - filled), theme::meta()),
+        Span::styled(
+            format!(" {}/{}", download.done, download.total),
+            theme::value(),
+        ),
+    ];
+    if download.failed > 0 {
+        spans.push(Span::styled(
+            format!("  {} incomplete", download.failed),
+            theme::warn(),
+        ));
+    }
+    spans.push(Span::styled("  D:stop", theme::hint()));
+    Line::from(spans)
+}
+
 pub(crate) fn content_areas(area: Rect) -> (Rect, Rect) {
     super::bordered_list_footer_areas(area)
 }
@@ -1,6 +1,6 @@
 use crate::api::types::Story;
-use crate::summarizer::SummaryEvent;
-use crate::ui::{clamped_scroll::ClampedScroll, markdown, overlay, theme};
+use crate::summarizer::{ChatMessage, SummaryEvent};
+use crate::ui::{clamped_scroll::ClampedScroll, format_age, markdown, now_unix, overlay, theme};
 #[cfg(not(target_os = "android"))]
 use anyhow::Context;
 use anyhow::Result;
@@ -8,6 +8,7 @@ use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use ratatui::Frame;
+use std::collections::HashSet;
 use std::time::Instant;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -42,6 +43,44 @@ pub struct SummaryOverlay {
     story_score: i64,
     story_author: String,
     story_time: i64,
+    /// The `[[summarize.profiles]]` entry this summary runs with.
+    profile: Option<String>,
+    profile_picker: Option<ProfilePicker>,
+    /// When the summary on show was generated, if it came from the cache.
+    cached_at: Option<i64>,
+    /// Follow-up questions asked after the summary, oldest first.
+    chat: Vec<ChatTurn>,
+    /// The follow-up being typed; keys go to it, not the bindings.
+    question: Option<String>,
+    /// Comments the LLM was given, so `[id]` citations of them can be
+    /// picked out of its answers.
+    comment_ids: HashSet<u64>,
+    /// Cited comment IDs in reading order, one per citation.
+    citations: Vec<u64>,
+    selected_citation: Option<usize>,
+}
+
+/// `a` over a finished summary: a question about the story and the answer
+/// streamed back for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChatTurn {
+    question: String,
+    answer: String,
+    error: Option<String>,
+}
+
+/// `p` over a summary: the profiles to re-run the story with. The first
+/// row is `[summarize]` itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfilePicker {
+    rows: Vec<Option<String>>,
+    cursor: usize,
+}
+
+impl ProfilePicker {
+    pub fn rows(&self) -> &[Option<String>] {
+        &self.rows
+    }
 }
 
 impl SummaryOverlay {
@@ -63,7 +102,191 @@ impl SummaryOverlay {
         self.story_score = story.score;
         self.story_author = story.by.clone();
         self.story_time = story.time;
+        self.profile = None;
+        self.profile_picker = None;
+        self.cached_at = None;
+        self.chat.clear();
+        self.question = None;
+        self.comment_ids.clear();
+        self.citations.clear();
+        self.selected_citation = None;
+        self.reflow();
+    }
+
+    /// Show a summary generated earlier instead of waiting on the LLM.
+    pub fn show_cached(&mut self, summary: String, model_name: String, created_at: i64) {
+        self.state = SummaryState::Done;
+        self.summary = summary;
+        self.content_started = true;
+        self.waiting_for = None;
+        self.model_name = model_name;
+        self.cached_at = Some(created_at);
+        self.scroll.go_top();
+        self.reflow();
+    }
+
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    #[cfg(test)]
+    pub fn cached_at(&self) -> Option<i64> {
+        self.cached_at
+    }
+
+    /// Begin typing a follow-up. Only a finished summary can be asked about.
+    pub fn start_question(&mut self) {
+        if self.state != SummaryState::Done || self.summary.is_empty() {
+            return;
+        }
+        self.question = Some(String::new());
+    }
+
+    pub fn is_typing_question(&self) -> bool {
+        self.question.is_some()
+    }
+
+    pub fn push_question(&mut self, character: char) {
+        if let Some(question) = self.question.as_mut() {
+            question.push(character);
+        }
+    }
+
+    pub fn pop_question(&mut self) {
+        if let Some(question) = self.question.as_mut() {
+            question.pop();
+        }
+    }
+
+    pub fn cancel_question(&mut self) {
+        self.question = None;
+    }
+
+    /// Stop typing and hand back the question, unless it is blank.
+    pub fn take_question(&mut self) -> Option<String> {
+        let question = self.question.take()?;
+        let question = question.trim();
+        (!question.is_empty()).then(|| question.to_string())
+    }
+
+    /// Add `question` to the transcript and wait for its answer to stream.
+    pub fn ask(&mut self, question: String) {
+        self.chat.push(ChatTurn {
+            question,
+            answer: String::new(),
+            error: None,
+        });
+        self.state = SummaryState::Streaming;
+        self.copied_flash = None;
         self.reflow();
+        self.scroll.go_bottom();
+    }
+
+    /// The conversation after the story context: the summary, then every
+    /// answered follow-up, ending on the question still awaiting one.
+    pub fn transcript(&self) -> Vec<ChatMessage> {
+        let mut messages = vec![ChatMessage::assistant(self.summary.clone())];
+        for turn in self.chat.iter().filter(|turn| turn.error.is_none()) {
+            messages.push(ChatMessage::user(turn.question.clone()));
+            if !turn.answer.is_empty() {
+                messages.push(ChatMessage::assistant(turn.answer.clone()));
+            }
+        }
+        messages
+    }
+
+    #[cfg(test)]
+    pub(crate) fn chat_answers(&self) -> Vec<&str> {
+        self.chat.iter().map(|turn| turn.answer.as_str()).collect()
+    }
+
+    pub fn set_comment_ids(&mut self, comment_ids: HashSet<u64>) {
+        self.comment_ids = comment_ids;
+        self.reflow();
+    }
+
+    pub fn select_next_citation(&mut self) {
+        self.step_citation(true);
+    }
+
+    pub fn select_previous_citation(&mut self) {
+        self.step_citation(false);
+    }
+
+    /// Wraps at either end; the first step picks the first or last one.
+    fn step_citation(&mut self, forward: bool) {
+        let count = self.citations.len();
+        if count == 0 {
+            return;
+        }
+        let target = match (self.selected_citation, forward) {
+            (Some(selected), true) => (selected + 1) % count,
+            (Some(selected), false) => selected.checked_sub(1).unwrap_or(count - 1),
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        self.selected_citation = Some(target);
+        if let Some(rows) = markdown::citation_row_range(
+            self.plain_content_lines(' '),
+            &self.comment_ids,
+            target,
+            self.viewport_width,
+        ) {
+            self.scroll.reveal(*rows.start(), *rows.end());
+        }
+    }
+
+    /// The comment the selected citation points at.
+    pub fn selected_citation(&self) -> Option<u64> {
+        self.citations.get(self.selected_citation?).copied()
+    }
+
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    pub fn profile_picker(&self) -> Option<&ProfilePicker> {
+        self.profile_picker.as_ref()
+    }
+
+    /// List `names` under the default row, with the cursor on the profile
+    /// now in use.
+    pub fn open_profile_picker(&mut self, names: Vec<String>) {
+        let rows: Vec<Option<String>> = std::iter::once(None)
+            .chain(names.into_iter().map(Some))
+            .collect();
+        let cursor = rows
+            .iter()
+            .position(|row| *row == self.profile)
+            .unwrap_or(0);
+        self.profile_picker = Some(ProfilePicker { rows, cursor });
+    }
+
+    pub fn close_profile_picker(&mut self) {
+        self.profile_picker = None;
+    }
+
+    pub fn move_profile_cursor(&mut self, down: bool) {
+        let Some(picker) = self.profile_picker.as_mut() else {
+            return;
+        };
+        let last = picker.rows.len() - 1;
+        picker.cursor = if down {
+            (picker.cursor + 1).min(last)
+        } else {
+            picker.cursor.saturating_sub(1)
+        };
+    }
+
+    /// Close the picker, returning the profile under the cursor (or at
+    /// `index`, when given): `Some(None)` for the default.
+    pub fn take_picked_profile(&mut self, index: Option<usize>) -> Option<Option<String>> {
+        let picker = self.profile_picker.take()?;
+        picker.rows.get(index.unwrap_or(picker.cursor)).cloned()
     }
 
     /// Comment count is only known once the discussion has loaded, which can
@@ -95,6 +318,10 @@ impl SummaryOverlay {
     }
 
     pub fn handle_event(&mut self, event: SummaryEvent) {
+        if !self.chat.is_empty() {
+            self.handle_answer_event(event);
+            return;
+        }
         match event {
             SummaryEvent::Started { model } => self.model_name = model,
             SummaryEvent::Chunk { content, reasoning } => {
@@ -120,7 +347,36 @@ impl SummaryOverlay {
         self.reflow();
     }
 
+    /// Stream into the latest follow-up, following the tail while the
+    /// reader is at the bottom.
+    fn handle_answer_event(&mut self, event: SummaryEvent) {
+        let following = self.scroll.offset() >= self.scroll.max_offset();
+        match event {
+            SummaryEvent::Started { model } => self.model_name = model,
+            SummaryEvent::Chunk { content, .. } => {
+                if let Some(turn) = self.chat.last_mut() {
+                    turn.answer.push_str(&content);
+                }
+            }
+            SummaryEvent::Complete => self.state = SummaryState::Done,
+        }
+        self.reflow();
+        if following {
+            self.scroll.go_bottom();
+        }
+    }
+
+    /// A failed follow-up stays in the transcript; the summary above it
+    /// is still good.
     pub fn fail(&mut self, message: String) {
+        if self.state == SummaryState::Streaming {
+            if let Some(turn) = self.chat.last_mut() {
+                turn.error = Some(message);
+                self.state = SummaryState::Done;
+                self.reflow();
+                return;
+            }
+        }
         self.state = SummaryState::Error;
         self.error = Some(message);
         self.reflow();
@@ -131,21 +387,25 @@ impl SummaryOverlay {
     }
 
     pub fn scroll_down(&mut self, amount: usize) {
+        self.selected_citation = None;
         self.scroll.scroll_down(amount);
         self.pin_reasoning_to_tail();
     }
 
     pub fn scroll_up(&mut self, amount: usize) {
+        self.selected_citation = None;
         self.scroll.scroll_up(amount);
         self.pin_reasoning_to_tail();
     }
 
     pub fn go_top(&mut self) {
+        self.selected_citation = None;
         self.scroll.go_top();
         self.pin_reasoning_to_tail();
     }
 
     pub fn go_bottom(&mut self) {
+        self.selected_citation = None;
         self.scroll.go_bottom();
     }
 
@@ -181,6 +441,14 @@ impl SummaryOverlay {
     }
 
     fn reflow(&mut self) {
+        self.citations =
+            markdown::cited_comments(&self.plain_content_lines(' '), &self.comment_ids);
+        if self
+            .selected_citation
+            .is_some_and(|selected| selected >= self.citations.len())
+        {
+            self.selected_citation = None;
+        }
         let wrapped_line_count = self.content_paragraph(' ').line_count(self.viewport_width);
         self.scroll.set_content_height(wrapped_line_count);
         self.pin_reasoning_to_tail();
@@ -212,10 +480,21 @@ impl SummaryOverlay {
         output.push_str(&overlay::front_matter_date(self.story_time));
         output.push_str("---\n\n");
         output.push_str(&self.summary);
+        for turn in self.chat.iter().filter(|turn| !turn.answer.is_empty()) {
+            output.push_str(&format!("\n\n## {}\n\n{}", turn.question, turn.answer));
+        }
         output
     }
 
     fn content_lines(&self, spinner: char) -> Vec<Line<'static>> {
+        markdown::highlight_citations(
+            self.plain_content_lines(spinner),
+            &self.comment_ids,
+            self.selected_citation,
+        )
+    }
+
+    fn plain_content_lines(&self, spinner: char) -> Vec<Line<'static>> {
         let body = match self.state {
             SummaryState::Loading if self.reasoning.is_empty() => {
                 let label = self
@@ -224,22 +503,26 @@ impl SummaryOverlay {
                     .unwrap_or("Waiting for LLM response");
                 vec![Line::from(Span::styled(
                     format!("{label} {spinner}"),
-                    theme::HINT,
+                    theme::hint(),
                 ))]
             }
             SummaryState::Loading => reasoning_lines(&self.reasoning, spinner),
             SummaryState::Streaming if self.summary.is_empty() => {
                 reasoning_lines(&self.reasoning, spinner)
             }
-            SummaryState::Streaming => {
+            SummaryState::Streaming if self.chat.is_empty() => {
                 let mut lines = markdown::render_markdown(&self.summary);
-                lines.push(Line::from(Span::styled(spinner.to_string(), theme::HINT)));
+                lines.push(Line::from(Span::styled(spinner.to_string(), theme::hint())));
+                lines
+            }
+            SummaryState::Streaming | SummaryState::Done => {
+                let mut lines = markdown::render_markdown(&self.summary);
+                lines.extend(self.chat_lines(spinner));
                 lines
             }
-            SummaryState::Done => markdown::render_markdown(&self.summary),
             SummaryState::Error => vec![Line::from(Span::styled(
                 self.error.as_deref().unwrap_or("Unknown error").to_string(),
-                theme::ERROR,
+                theme::error(),
             ))],
             SummaryState::Idle => return Vec::new(),
         };
@@ -250,7 +533,7 @@ impl SummaryOverlay {
         let mut lines = vec![
             Line::from(Span::styled(
                 format!("⚠ article unavailable ({notice}) — comments only"),
-                theme::WARN,
+                theme::warn(),
             )),
             Line::raw(""),
         ];
@@ -258,6 +541,30 @@ impl SummaryOverlay {
         lines
     }
 
+    /// Each follow-up under the summary: the question, then its answer,
+    /// with the spinner on the one still streaming.
+    fn chat_lines(&self, spinner: char) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (index, turn) in self.chat.iter().enumerate() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(Span::styled(
+                format!("› {}", turn.question),
+                theme::key(),
+            )));
+            lines.push(Line::raw(""));
+            if let Some(error) = &turn.error {
+                lines.push(Line::from(Span::styled(error.clone(), theme::error())));
+            } else {
+                lines.extend(markdown::render_markdown(&turn.answer));
+            }
+            let answering = self.state == SummaryState::Streaming && index + 1 == self.chat.len();
+            if answering {
+                lines.push(Line::from(Span::styled(spinner.to_string(), theme::hint())));
+            }
+        }
+        lines
+    }
+
     fn content_paragraph(&self, spinner: char) -> Paragraph<'static> {
         Paragraph::new(self.content_lines(spinner)).wrap(Wrap { trim: false })
     }
@@ -291,46 +598,126 @@ pub fn render(frame: &mut Frame, overlay: &SummaryOverlay, spinner: char) {
     } else {
         format!(" ({})", overlay.model_name)
     };
+    let profile_tag = overlay
+        .profile
+        .as_deref()
+        .map(|profile| format!(" · {profile}"))
+        .unwrap_or_default();
+    let cached_tag = overlay
+        .cached_at
+        .map(|created_at| format!(" · cached {} ago", format_age(created_at, now_unix())))
+        .unwrap_or_default();
     let title = match overlay.state {
         SummaryState::Loading if overlay.reasoning.is_empty() => format!(
-            " Summarizing {spinner} ({} comments){model_tag} ",
+            " Summarizing{profile_tag} {spinner} ({} comments){model_tag} ",
             overlay.comment_count
         ),
         SummaryState::Loading => format!(" Thinking {spinner}{model_tag} "),
         SummaryState::Streaming if overlay.summary.is_empty() => {
             format!(" Thinking {spinner}{model_tag} ")
         }
-        SummaryState::Streaming => format!(" Summarizing {spinner}{model_tag} "),
-        SummaryState::Done => format!(" Summary{model_tag} "),
+        SummaryState::Streaming if !overlay.chat.is_empty() => {
+            format!(" Answering {spinner}{model_tag} ")
+        }
+        SummaryState::Streaming => format!(" Summarizing{profile_tag} {spinner}{model_tag} "),
+        SummaryState::Done => format!(" Summary{profile_tag}{model_tag}{cached_tag} "),
         SummaryState::Error => " Summary Error ".to_string(),
         SummaryState::Idle => return,
     };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled(title, theme::HEADER_ACCENT));
+        .title(Span::styled(title, theme::header_accent()));
     frame.render_widget(Clear, areas.popup);
-    frame.render_widget(block.style(theme::POPUP), areas.popup);
+    frame.render_widget(block.style(theme::popup()), areas.popup);
     frame.render_widget(
         overlay
             .content_paragraph(spinner)
             .scroll((overlay.render_scroll_offset(), 0))
-            .style(theme::POPUP),
+            .style(theme::popup()),
         areas.content,
     );
     overlay::render_scrollbar(frame, areas.scrollbar, &overlay.scroll);
 
     let hint = if overlay::copied_recently(overlay.copied_flash) {
-        Line::from(Span::styled("Copied!", theme::SUCCESS))
+        Line::from(Span::styled("Copied!", theme::success()))
+    } else if let Some(question) = &overlay.question {
+        Line::from(Span::styled(
+            format!("Ask: {question}▏  Enter: send  Esc: cancel"),
+            theme::hint(),
+        ))
+    } else if let Some(comment_id) = overlay.selected_citation() {
+        Line::from(Span::styled(
+            format!("comment {comment_id}  Enter: jump  Tab/Shift+Tab: citations  Esc: close"),
+            theme::hint(),
+        ))
     } else {
         let text = match overlay.state {
-            SummaryState::Done => "j/k: scroll  c: copy  q/Esc: close",
-            SummaryState::Streaming => "j/k: scroll  c: copy  q/Esc: cancel",
-            SummaryState::Error => "j/k: scroll  q/Esc: close",
+            SummaryState::Done if !overlay.citations.is_empty() => {
+                "j/k: scroll  Tab: citations  a: ask  c: copy  r: regenerate  p: profile  q/Esc: close"
+            }
+            SummaryState::Done => {
+                "j/k: scroll  a: ask  c: copy  r: regenerate  p: profile  q/Esc: close"
+            }
+            SummaryState::Streaming => "j/k: scroll  c: copy  p: profile  q/Esc: cancel",
+            SummaryState::Error => "j/k: scroll  r: retry  p: profile  q/Esc: close",
             _ => "q/Esc: cancel",
         };
-        Line::from(Span::styled(text, theme::HINT))
+        Line::from(Span::styled(text, theme::hint()))
+    };
+    frame.render_widget(Paragraph::new(hint).style(theme::popup()), areas.hint);
+
+    if let Some(picker) = &overlay.profile_picker {
+        render_profile_picker(frame, picker);
+    }
+}
+
+fn render_profile_picker(frame: &mut Frame, picker: &ProfilePicker) {
+    let Some(popup) = profile_picker_rect(frame.area(), picker.rows.len()) else {
+        return;
     };
-    frame.render_widget(Paragraph::new(hint).style(theme::POPUP), areas.hint);
+    let mut lines = vec![
+        Line::from(Span::styled("Re-run with profile", theme::header())),
+        Line::raw(""),
+    ];
+    for (index, row) in picker.rows.iter().enumerate() {
+        let marker = if index == picker.cursor { "> " } else { "  " };
+        let style = if index == picker.cursor {
+            theme::key()
+        } else {
+            theme::label()
+        };
+        let label = row.as_deref().unwrap_or("default");
+        lines.push(Line::from(Span::styled(format!("{marker}{label}"), style)));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled("j/k", theme::key()),
+        Span::styled(":nav  ", theme::hint()),
+        Span::styled("Enter", theme::key()),
+        Span::styled(":run  ", theme::hint()),
+        Span::styled("Esc", theme::key()),
+        Span::styled(":close", theme::hint()),
+    ]));
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled("p", theme::header()));
+    frame.render_widget(
+        Paragraph::new(lines).block(block).style(theme::popup()),
+        popup,
+    );
+}
+
+/// The picker popup, sized to `rows` profiles plus its header and hint.
+pub(crate) fn profile_picker_rect(area: Rect, rows: usize) -> Option<Rect> {
+    if area.width < 10 || area.height < 6 {
+        return None;
+    }
+    let line_count = rows + 4;
+    let desired_width = area.width.min(40);
+    let desired_height = (line_count as u16).saturating_add(2).min(area.height);
+    Some(super::centered(area, desired_width, desired_height))
 }
 
 pub(crate) fn popup_rect(area: Rect) -> Option<Rect> {
@@ -344,10 +731,10 @@ pub(crate) fn content_area(area: Rect) -> Option<Rect> {
 fn reasoning_lines(buffer: &str, spinner: char) -> Vec<Line<'static>> {
     use ratatui::style::{Modifier, Style};
     let style = Style::default()
-        .fg(theme::OVERLAY0)
+        .fg(theme::overlay0())
         .add_modifier(Modifier::DIM | Modifier::ITALIC);
     let mut lines = vec![
-        Line::from(Span::styled(format!("Thinking {spinner}"), theme::HINT)),
+        Line::from(Span::styled(format!("Thinking {spinner}"), theme::hint())),
         Line::raw(""),
     ];
     lines.extend(
@@ -13,6 +13,8 @@ pub(super) fn story() -> Story {
         time: 1_700_000_000,
         comment_count: 2,
         kids: vec![1, 2],
+        kind: None,
+        stale_secs: None,
     }
 }
 
@@ -180,3 +182,20 @@ fn first_summary_content_resets_to_top_then_streaming_growth_holds_position() {
     overlay.scroll_down(usize::MAX);
     assert!(overlay.scroll_offset() > 2);
 }
+
+#[test]
+fn a_failed_follow_up_stays_in_the_transcript_under_the_summary() {
+    let mut overlay = completed_overlay("the summary");
+    overlay.ask("pricing?".to_string());
+    assert_eq!(overlay.state(), SummaryState::Streaming);
+
+    overlay.fail("HTTP 500".to_string());
+
+    assert_eq!(overlay.state(), SummaryState::Done);
+    assert_eq!(overlay.summary(), "the summary");
+    assert_eq!(overlay.chat[0].error.as_deref(), Some("HTTP 500"));
+    assert_eq!(
+        overlay.transcript(),
+        vec![crate::summarizer::ChatMessage::assistant("the summary")]
+    );
+}
@@ -1,4 +1,8 @@
+use crate::config::{ScaleStepConfig, ThemeConfig};
+use crate::ui::color_depth::ColorDepth;
+use anyhow::{anyhow, bail, Context, Result};
 use ratatui::style::{Color, Modifier, Style};
+use std::sync::OnceLock;
 
 const COMMENT_INDENT_BLEND: f64 = 0.35;
 
@@ -10,157 +14,524 @@ const fn hex(rgb: u32) -> Color {
     )
 }
 
-// Catppuccin Frappé palette
-pub(crate) const SURFACE2: Color = hex(0x414559);
-pub(crate) const OVERLAY0: Color = hex(0x737994);
-pub(crate) const SUBTEXT0: Color = hex(0xA5ADCE);
-pub(crate) const SUBTEXT1: Color = hex(0xB5BFE2);
-pub(crate) const TEXT: Color = hex(0xC6D0F5);
-pub(crate) const BLUE: Color = hex(0x8CAAEE);
-pub(crate) const TEAL: Color = hex(0x81C8BE);
-pub(crate) const GREEN: Color = hex(0xA6D189);
-pub(crate) const RED: Color = hex(0xE78284);
-pub(crate) const PEACH: Color = hex(0xEF9F76);
-pub(crate) const MAUVE: Color = hex(0xCA9EE6);
-
-const RAINBOW: [Color; 10] = [
-    hex(0x8CAAEE), // blue
-    hex(0x85C1DC), // sapphire
-    hex(0x99D1DB), // sky
-    hex(0x81C8BE), // teal
-    hex(0xA6D189), // green
-    hex(0xE5C890), // yellow
-    hex(0xEF9F76), // peach
-    hex(0xE78284), // red
-    hex(0xCA9EE6), // mauve
-    hex(0xF4B8E4), // pink
-];
+/// The palette every style below draws from, chosen once at startup by
+/// `[theme]`. Until `install` runs (tests, mostly) it is Frappé.
+static ACTIVE: OnceLock<Theme> = OnceLock::new();
+
+pub(crate) fn install(theme: Theme) {
+    // Startup installs once; a second call keeps the first palette.
+    let _ = ACTIVE.set(theme);
+}
+
+fn active() -> &'static Theme {
+    ACTIVE.get_or_init(Theme::default)
+}
+
+/// One palette's worth of colors, named by Catppuccin role.
+#[derive(Debug, Clone)]
+pub(crate) struct Theme {
+    /// Popup and selection background.
+    surface: Color,
+    overlay0: Color,
+    subtext0: Color,
+    subtext1: Color,
+    text: Color,
+    blue: Color,
+    teal: Color,
+    green: Color,
+    red: Color,
+    peach: Color,
+    mauve: Color,
+    /// What the dimmed backdrop behind a popup fades toward.
+    shade: Color,
+    /// Dimmed cells with no color of their own (the terminal default).
+    backdrop: Color,
+    rainbow: Vec<Color>,
+    score_scale: Vec<ScaleStep>,
+    comment_scale: Vec<ScaleStep>,
+}
+
+/// The rainbow's accents that no style uses directly.
+struct Accents {
+    sapphire: Color,
+    sky: Color,
+    yellow: Color,
+    pink: Color,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavour {
+    Latte,
+    Frappe,
+    Macchiato,
+    Mocha,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::catppuccin(Flavour::Frappe)
+    }
+}
+
+impl Theme {
+    /// A built-in palette by name, then any per-role overrides on top.
+    /// Unnamed, a 16-color terminal gets the ANSI palette: the truecolor
+    /// ones cannot survive being squeezed into sixteen entries.
+    pub(crate) fn from_config(config: &ThemeConfig, depth: ColorDepth) -> Result<Self> {
+        let mut theme = match config.name.as_deref().map(str::to_ascii_lowercase) {
+            None if depth == ColorDepth::Ansi16 => Self::ansi(),
+            None => Self::default(),
+            Some(name) => match name.as_str() {
+                "frappe" | "frappé" => Self::catppuccin(Flavour::Frappe),
+                "latte" | "light" => Self::catppuccin(Flavour::Latte),
+                "macchiato" => Self::catppuccin(Flavour::Macchiato),
+                "mocha" => Self::catppuccin(Flavour::Mocha),
+                "ansi" | "16" => Self::ansi(),
+                _ => bail!(
+                    "unknown theme {name:?} (expected frappe, latte, light, macchiato, mocha or ansi)"
+                ),
+            },
+        };
+        if let Some(rainbow) = &config.rainbow {
+            if rainbow.is_empty() {
+                bail!("rainbow needs at least one color");
+            }
+            theme.rainbow = rainbow
+                .iter()
+                .map(|color| parse_color(color))
+                .collect::<Result<_>>()
+                .context("rainbow")?;
+        }
+        if let Some(steps) = &config.score_scale {
+            theme.score_scale = parse_scale(steps).context("score_scale")?;
+        }
+        if let Some(steps) = &config.comment_scale {
+            theme.comment_scale = parse_scale(steps).context("comment_scale")?;
+        }
+        Ok(theme)
+    }
+
+    fn catppuccin(flavour: Flavour) -> Self {
+        let (theme, accents) = match flavour {
+            Flavour::Frappe => (
+                Self {
+                    surface: hex(0x414559),
+                    overlay0: hex(0x737994),
+                    subtext0: hex(0xA5ADCE),
+                    subtext1: hex(0xB5BFE2),
+                    text: hex(0xC6D0F5),
+                    blue: hex(0x8CAAEE),
+                    teal: hex(0x81C8BE),
+                    green: hex(0xA6D189),
+                    red: hex(0xE78284),
+                    peach: hex(0xEF9F76),
+                    mauve: hex(0xCA9EE6),
+                    shade: Color::Rgb(0, 0, 0),
+                    backdrop: Color::Rgb(30, 30, 30),
+                    rainbow: Vec::new(),
+                    // Hand-tuned before the other flavours existed; they
+                    // derive theirs with `with_heat` instead.
+                    score_scale: scale(&[
+                        (500, hex(0x9F633F)),
+                        (250, hex(0x925B3B)),
+                        (100, hex(0x855337)),
+                        (50, hex(0x5F433A)),
+                        (0, hex(0x3F2E2B)),
+                    ]),
+                    comment_scale: scale(&[
+                        (300, hex(0x5F76A4)),
+                        (200, hex(0x586E9A)),
+                        (100, hex(0x516690)),
+                        (50, hex(0x45526E)),
+                        (0, hex(0x353E5B)),
+                    ]),
+                },
+                Accents {
+                    sapphire: hex(0x85C1DC),
+                    sky: hex(0x99D1DB),
+                    yellow: hex(0xE5C890),
+                    pink: hex(0xF4B8E4),
+                },
+            ),
+            Flavour::Latte => (
+                Self {
+                    surface: hex(0xCCD0DA),
+                    overlay0: hex(0x9CA0B0),
+                    subtext0: hex(0x6C6F85),
+                    subtext1: hex(0x5C5F77),
+                    text: hex(0x4C4F69),
+                    blue: hex(0x1E66F5),
+                    teal: hex(0x179299),
+                    green: hex(0x40A02B),
+                    red: hex(0xD20F39),
+                    peach: hex(0xFE640B),
+                    mauve: hex(0x8839EF),
+                    // A light page dims toward white, not black.
+                    shade: Color::Rgb(255, 255, 255),
+                    backdrop: hex(0xDCE0E8),
+                    rainbow: Vec::new(),
+                    score_scale: Vec::new(),
+                    comment_scale: Vec::new(),
+                }
+                .with_heat(hex(0xEFF1F5), &LIGHT_HEAT),
+                Accents {
+                    sapphire: hex(0x209FB5),
+                    sky: hex(0x04A5E5),
+                    yellow: hex(0xDF8E1D),
+                    pink: hex(0xEA76CB),
+                },
+            ),
+            Flavour::Macchiato => (
+                Self {
+                    surface: hex(0x363A4F),
+                    overlay0: hex(0x6E738D),
+                    subtext0: hex(0xA5ADCB),
+                    subtext1: hex(0xB8C0E0),
+                    text: hex(0xCAD3F5),
+                    blue: hex(0x8AADF4),
+                    teal: hex(0x8BD5CA),
+                    green: hex(0xA6DA95),
+                    red: hex(0xED8796),
+                    peach: hex(0xF5A97F),
+                    mauve: hex(0xC6A0F6),
+                    shade: Color::Rgb(0, 0, 0),
+                    backdrop: Color::Rgb(30, 30, 30),
+                    rainbow: Vec::new(),
+                    score_scale: Vec::new(),
+                    comment_scale: Vec::new(),
+                }
+                .with_heat(hex(0x24273A), &DARK_HEAT),
+                Accents {
+                    sapphire: hex(0x7DC4E4),
+                    sky: hex(0x91D7E3),
+                    yellow: hex(0xEED49F),
+                    pink: hex(0xF5BDE6),
+                },
+            ),
+            Flavour::Mocha => (
+                Self {
+                    surface: hex(0x313244),
+                    overlay0: hex(0x6C7086),
+                    subtext0: hex(0xA6ADC8),
+                    subtext1: hex(0xBAC2DE),
+                    text: hex(0xCDD6F4),
+                    blue: hex(0x89B4FA),
+                    teal: hex(0x94E2D5),
+                    green: hex(0xA6E3A1),
+                    red: hex(0xF38BA8),
+                    peach: hex(0xFAB387),
+                    mauve: hex(0xCBA6F7),
+                    shade: Color::Rgb(0, 0, 0),
+                    backdrop: Color::Rgb(20, 20, 30),
+                    rainbow: Vec::new(),
+                    score_scale: Vec::new(),
+                    comment_scale: Vec::new(),
+                }
+                .with_heat(hex(0x1E1E2E), &DARK_HEAT),
+                Accents {
+                    sapphire: hex(0x74C7EC),
+                    sky: hex(0x89DCEB),
+                    yellow: hex(0xF9E2AF),
+                    pink: hex(0xF5C2E7),
+                },
+            ),
+        };
+        Self {
+            rainbow: vec![
+                theme.blue,
+                accents.sapphire,
+                accents.sky,
+                theme.teal,
+                theme.green,
+                accents.yellow,
+                theme.peach,
+                theme.red,
+                theme.mauve,
+                accents.pink,
+            ],
+            ..theme
+        }
+    }
+
+    /// Heat-map scales blended from the page color toward peach (score)
+    /// and blue (comments), `heat` giving each step's distance.
+    fn with_heat(self, base: Color, heat: &[f64; 5]) -> Self {
+        let heat_scale = |thresholds: [i64; 5], accent: Color| {
+            thresholds
+                .iter()
+                .zip(heat)
+                .map(|(&min, &t)| ScaleStep {
+                    min,
+                    color: blend(base, accent, t),
+                })
+                .collect()
+        };
+        Self {
+            score_scale: heat_scale(SCORE_THRESHOLDS, self.peach),
+            comment_scale: heat_scale(COMMENT_THRESHOLDS, self.blue),
+            ..self
+        }
+    }
+
+    /// The sixteen ANSI colors, for terminals without truecolor. Text keeps
+    /// the terminal's own foreground so it reads on light and dark alike.
+    fn ansi() -> Self {
+        Self {
+            surface: Color::DarkGray,
+            overlay0: Color::DarkGray,
+            subtext0: Color::Gray,
+            subtext1: Color::Reset,
+            text: Color::Reset,
+            blue: Color::Blue,
+            teal: Color::Cyan,
+            green: Color::Green,
+            red: Color::Red,
+            peach: Color::Yellow,
+            mauve: Color::Magenta,
+            shade: Color::DarkGray,
+            backdrop: Color::Reset,
+            rainbow: vec![
+                Color::Blue,
+                Color::LightBlue,
+                Color::Cyan,
+                Color::LightCyan,
+                Color::Green,
+                Color::Yellow,
+                Color::LightYellow,
+                Color::Red,
+                Color::Magenta,
+                Color::LightMagenta,
+            ],
+            score_scale: scale(&[
+                (500, Color::LightYellow),
+                (250, Color::Yellow),
+                (100, Color::Yellow),
+                (50, Color::DarkGray),
+                (0, Color::DarkGray),
+            ]),
+            comment_scale: scale(&[
+                (300, Color::LightCyan),
+                (200, Color::Cyan),
+                (100, Color::Cyan),
+                (50, Color::DarkGray),
+                (0, Color::DarkGray),
+            ]),
+        }
+    }
+}
+
+fn scale(steps: &[(i64, Color)]) -> Vec<ScaleStep> {
+    steps
+        .iter()
+        .map(|&(min, color)| ScaleStep { min, color })
+        .collect()
+}
+
+/// `#rrggbb`, an ANSI name (`lightblue`), or a 256-color index.
+fn parse_color(text: &str) -> Result<Color> {
+    text.parse::<Color>()
+        .map_err(|_| anyhow!("unknown color {text:?}"))
+}
+
+fn parse_scale(steps: &[ScaleStepConfig]) -> Result<Vec<ScaleStep>> {
+    let steps = steps
+        .iter()
+        .map(|step| {
+            Ok(ScaleStep {
+                min: step.min,
+                color: parse_color(&step.color)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !steps.windows(2).all(|pair| pair[0].min > pair[1].min) {
+        bail!("steps must be listed from the highest min down");
+    }
+    if steps.last().map(|step| step.min) != Some(0) {
+        bail!("the last step must have min = 0");
+    }
+    Ok(steps)
+}
 
 // Layout constants
 pub(crate) const COMMENT_MAX_LINES: Option<usize> = None;
 pub(crate) const COMMENT_DEFAULT_VISIBLE_LEVELS: usize = 2;
 
 // Score/comment heat-map scales (descending threshold, last must be 0)
+#[derive(Debug, Clone, Copy)]
 struct ScaleStep {
     min: i64,
     color: Color,
 }
 
-const SCORE_SCALE: [ScaleStep; 5] = [
-    ScaleStep {
-        min: 500,
-        color: hex(0x9F633F),
-    },
-    ScaleStep {
-        min: 250,
-        color: hex(0x925B3B),
-    },
-    ScaleStep {
-        min: 100,
-        color: hex(0x855337),
-    },
-    ScaleStep {
-        min: 50,
-        color: hex(0x5F433A),
-    },
-    ScaleStep {
-        min: 0,
-        color: hex(0x3F2E2B),
-    },
-];
-
-const COMMENT_SCALE: [ScaleStep; 5] = [
-    ScaleStep {
-        min: 300,
-        color: hex(0x5F76A4),
-    },
-    ScaleStep {
-        min: 200,
-        color: hex(0x586E9A),
-    },
-    ScaleStep {
-        min: 100,
-        color: hex(0x516690),
-    },
-    ScaleStep {
-        min: 50,
-        color: hex(0x45526E),
-    },
-    ScaleStep {
-        min: 0,
-        color: hex(0x353E5B),
-    },
-];
+const SCORE_THRESHOLDS: [i64; 5] = [500, 250, 100, 50, 0];
+const COMMENT_THRESHOLDS: [i64; 5] = [300, 200, 100, 50, 0];
+/// How far each derived heat step sits from the page color toward its accent.
+const DARK_HEAT: [f64; 5] = [0.55, 0.5, 0.45, 0.25, 0.08];
+const LIGHT_HEAT: [f64; 5] = [0.95, 0.85, 0.75, 0.55, 0.4];
+
+// ── Palette roles ────────────────────────────────────────────────────
+
+pub(crate) fn overlay0() -> Color {
+    active().overlay0
+}
+
+pub(crate) fn subtext0() -> Color {
+    active().subtext0
+}
+
+pub(crate) fn text() -> Color {
+    active().text
+}
+
+pub(crate) fn blue() -> Color {
+    active().blue
+}
+
+pub(crate) fn green() -> Color {
+    active().green
+}
+
+#[cfg(test)]
+pub(crate) fn red() -> Color {
+    active().red
+}
+
+#[cfg(test)]
+pub(crate) fn peach() -> Color {
+    active().peach
+}
+
+#[cfg(test)]
+pub(crate) fn mauve() -> Color {
+    active().mauve
+}
 
 // ── Semantic styles (shared across all views) ────────────────────────
 
 /// Popup background
-pub(crate) const POPUP: Style = Style::new().bg(SURFACE2);
+pub(crate) fn popup() -> Style {
+    Style::new().bg(active().surface)
+}
 /// Bold primary text (popup titles, keys)
-pub(crate) const HEADER: Style = Style::new().fg(TEXT).add_modifier(Modifier::BOLD);
+pub(crate) fn header() -> Style {
+    Style::new().fg(active().text).add_modifier(Modifier::BOLD)
+}
 /// Accent header (summary overlay title)
-pub(crate) const HEADER_ACCENT: Style = Style::new().fg(MAUVE).add_modifier(Modifier::BOLD);
+pub(crate) fn header_accent() -> Style {
+    Style::new().fg(active().mauve).add_modifier(Modifier::BOLD)
+}
 /// Hint / secondary help text
-pub(crate) const HINT: Style = Style::new().fg(SUBTEXT0);
+pub(crate) fn hint() -> Style {
+    Style::new().fg(active().subtext0)
+}
 /// Keyboard shortcut labels
-pub(crate) const KEY: Style = Style::new().fg(TEXT).add_modifier(Modifier::BOLD);
+pub(crate) fn key() -> Style {
+    Style::new().fg(active().text).add_modifier(Modifier::BOLD)
+}
 /// Low-emphasis label
-pub(crate) const LABEL: Style = Style::new().fg(SUBTEXT1);
+pub(crate) fn label() -> Style {
+    Style::new().fg(active().subtext1)
+}
 /// Normal-emphasis value
-pub(crate) const VALUE: Style = Style::new().fg(TEXT);
+pub(crate) fn value() -> Style {
+    Style::new().fg(active().text)
+}
 /// Purple accent (active section, cursor in popup)
-pub(crate) const ACCENT: Style = Style::new().fg(MAUVE).add_modifier(Modifier::BOLD);
+pub(crate) fn accent() -> Style {
+    Style::new().fg(active().mauve).add_modifier(Modifier::BOLD)
+}
 /// Green success flash ("Copied!", "Saved!")
-pub(crate) const SUCCESS: Style = Style::new().fg(GREEN).add_modifier(Modifier::BOLD);
+pub(crate) fn success() -> Style {
+    Style::new().fg(active().green).add_modifier(Modifier::BOLD)
+}
 /// Error text
-pub(crate) const ERROR: Style = Style::new().fg(RED);
+pub(crate) fn error() -> Style {
+    Style::new().fg(active().red)
+}
 /// Degraded-but-usable notice (e.g. a summary without its article)
-pub(crate) const WARN: Style = Style::new().fg(PEACH);
+pub(crate) fn warn() -> Style {
+    Style::new().fg(active().peach)
+}
 /// Subtle metadata (overlay0)
-pub(crate) const META: Style = Style::new().fg(OVERLAY0);
+pub(crate) fn meta() -> Style {
+    Style::new().fg(active().overlay0)
+}
 /// Selected-item highlight bg
-pub(crate) const SELECTED: Style = Style::new().bg(SURFACE2);
+pub(crate) fn selected() -> Style {
+    Style::new().bg(active().surface)
+}
 /// Block quote text
-pub(crate) const QUOTE: Style = Style::new().fg(SUBTEXT0).add_modifier(Modifier::ITALIC);
+pub(crate) fn quote() -> Style {
+    Style::new()
+        .fg(active().subtext0)
+        .add_modifier(Modifier::ITALIC)
+}
 /// Block quote bar
-pub(crate) const QUOTE_BAR: Style = Style::new().fg(OVERLAY0);
+pub(crate) fn quote_bar() -> Style {
+    Style::new().fg(active().overlay0)
+}
 /// Inline / fenced code
-pub(crate) const CODE: Style = Style::new().fg(TEAL);
+pub(crate) fn code() -> Style {
+    Style::new().fg(active().teal)
+}
 /// List bullet / number
-pub(crate) const LIST_MARKER: Style = Style::new().fg(BLUE);
+pub(crate) fn list_marker() -> Style {
+    Style::new().fg(active().blue)
+}
 /// Focused Article Link
-pub(crate) const ARTICLE_LINK_SELECTED: Style = Style::new()
-    .fg(SURFACE2)
-    .bg(BLUE)
-    .add_modifier(Modifier::BOLD)
-    .add_modifier(Modifier::UNDERLINED);
+pub(crate) fn article_link_selected() -> Style {
+    Style::new()
+        .fg(active().surface)
+        .bg(active().blue)
+        .add_modifier(Modifier::BOLD)
+        .add_modifier(Modifier::UNDERLINED)
+}
+/// A comment cited in a summary
+pub(crate) fn citation() -> Style {
+    Style::new()
+        .fg(active().blue)
+        .add_modifier(Modifier::UNDERLINED)
+}
 /// Block cursor in editing mode
-pub(crate) const BLOCK_CURSOR: Style = Style::new().fg(SURFACE2).bg(GREEN);
+pub(crate) fn block_cursor() -> Style {
+    Style::new().fg(active().surface).bg(active().green)
+}
+/// Text matching a find query
+pub(crate) fn find_match() -> Style {
+    Style::new().fg(active().surface).bg(active().peach)
+}
+/// The find match last jumped to
+pub(crate) fn find_match_current() -> Style {
+    Style::new()
+        .fg(active().surface)
+        .bg(active().mauve)
+        .add_modifier(Modifier::BOLD)
+}
 
 pub(crate) fn section_heading(active: bool) -> Style {
     if active {
-        ACCENT
+        accent()
     } else {
-        Style::new().fg(SUBTEXT0).add_modifier(Modifier::BOLD)
+        Style::new().fg(subtext0()).add_modifier(Modifier::BOLD)
     }
 }
 
 // ── Scale helpers ────────────────────────────────────────────────────
 
 pub(crate) fn score_color(score: i64) -> Color {
-    scale_color(score, &SCORE_SCALE)
+    scale_color(score, &active().score_scale)
 }
 
 pub(crate) fn comment_color(comments: i64) -> Color {
-    scale_color(comments, &COMMENT_SCALE)
+    scale_color(comments, &active().comment_scale)
 }
 
 pub(crate) fn score_level(score: i64) -> f64 {
-    scale_level(score, &SCORE_SCALE)
+    scale_level(score, &active().score_scale)
 }
 
 pub(crate) fn comment_level(comments: i64) -> f64 {
-    scale_level(comments, &COMMENT_SCALE)
+    scale_level(comments, &active().comment_scale)
 }
 
 fn scale_color(value: i64, steps: &[ScaleStep]) -> Color {
@@ -188,24 +559,26 @@ fn scale_level(value: i64, steps: &[ScaleStep]) -> f64 {
 
 pub(crate) fn comment_indent_color(depth: usize) -> Color {
     let accent = rainbow_depth(depth);
-    blend(OVERLAY0, accent, COMMENT_INDENT_BLEND)
+    blend(overlay0(), accent, COMMENT_INDENT_BLEND)
 }
 
 pub(crate) fn rainbow(level: f64) -> Color {
+    let rainbow = &active().rainbow;
     let level = level.clamp(0.0, 1.0);
-    let max_idx = RAINBOW.len() - 1;
+    let max_idx = rainbow.len() - 1;
     let pos = level * (max_idx as f64);
     let idx = pos.floor() as usize;
     if idx >= max_idx {
-        return RAINBOW[max_idx];
+        return rainbow[max_idx];
     }
     let t = pos - (idx as f64);
-    blend(RAINBOW[idx], RAINBOW[idx + 1], t)
+    blend(rainbow[idx], rainbow[idx + 1], t)
 }
 
 pub(crate) fn rainbow_depth(depth: usize) -> Color {
-    let idx = (depth.saturating_mul(3)) % RAINBOW.len();
-    RAINBOW[idx]
+    let rainbow = &active().rainbow;
+    let idx = (depth.saturating_mul(3)) % rainbow.len();
+    rainbow[idx]
 }
 
 /// Story foreground based on row position, importance, and distance from selection.
@@ -219,7 +592,7 @@ pub(crate) fn story_gradient_fg(
     let rainbow_color = rainbow(hue_pos);
 
     let saturation = 0.2 + (importance * 0.75);
-    let importance_adjusted = blend(SUBTEXT0, rainbow_color, saturation);
+    let importance_adjusted = blend(subtext0(), rainbow_color, saturation);
 
     if distance == 0 {
         return importance_adjusted;
@@ -228,28 +601,28 @@ pub(crate) fn story_gradient_fg(
     let max_dist = half_viewport.max(1) as f64;
     let fade = (distance as f64 / max_dist).min(1.0);
     let dim_factor = fade * (0.5 - importance * 0.2);
-    blend(importance_adjusted, SUBTEXT0, dim_factor)
+    blend(importance_adjusted, subtext0(), dim_factor)
 }
 
-/// Darken a color by blending toward black.
+/// Fade a color toward the theme's shade, for the backdrop behind a popup.
 pub(crate) fn dim_color(color: Color, factor: f64) -> Color {
+    let theme = active();
     match color {
-        Color::Rgb(r, g, b) => blend(Color::Rgb(r, g, b), Color::Rgb(0, 0, 0), factor),
-        _ => Color::Rgb(30, 30, 30),
+        Color::Rgb(..) => blend(color, theme.shade, factor),
+        Color::Reset => theme.backdrop,
+        _ => theme.shade,
     }
 }
 
+/// Mix two colors; named (16-color) ones cannot mix, so the nearer end wins.
 pub(crate) fn blend(a: Color, b: Color, t: f64) -> Color {
-    let (ar, ag, ab) = rgb_components(a);
-    let (br, bg, bb) = rgb_components(b);
     let t = t.clamp(0.0, 1.0);
-    Color::Rgb(lerp_u8(ar, br, t), lerp_u8(ag, bg, t), lerp_u8(ab, bb, t))
-}
-
-fn rgb_components(color: Color) -> (u8, u8, u8) {
-    match color {
-        Color::Rgb(r, g, b) => (r, g, b),
-        _ => panic!("expected Color::Rgb"),
+    match (a, b) {
+        (Color::Rgb(ar, ag, ab), Color::Rgb(br, bg, bb)) => {
+            Color::Rgb(lerp_u8(ar, br, t), lerp_u8(ag, bg, t), lerp_u8(ab, bb, t))
+        }
+        _ if t < 0.5 => a,
+        _ => b,
     }
 }
 
@@ -258,3 +631,99 @@ fn lerp_u8(a: u8, b: u8, t: f64) -> u8 {
     let b = b as f64;
     ((a + ((b - a) * t)).round()).clamp(0.0, 255.0) as u8
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(name: &str) -> ThemeConfig {
+        ThemeConfig {
+            name: Some(name.to_string()),
+            ..ThemeConfig::default()
+        }
+    }
+
+    #[test]
+    fn the_light_theme_puts_dark_text_on_a_light_page() {
+        let theme =
+            Theme::from_config(&config("light"), ColorDepth::Truecolor).expect("built-in theme");
+
+        let Color::Rgb(r, g, b) = theme.text else {
+            panic!("latte text is truecolor");
+        };
+        assert!(u32::from(r) + u32::from(g) + u32::from(b) < 3 * 128);
+        assert_eq!(theme.rainbow.len(), 10);
+    }
+
+    #[test]
+    fn overrides_replace_the_scales_and_rainbow_of_the_chosen_theme() {
+        let theme = Theme::from_config(
+            &ThemeConfig {
+                rainbow: Some(vec!["#ff0000".to_string(), "blue".to_string()]),
+                score_scale: Some(vec![
+                    ScaleStepConfig {
+                        min: 100,
+                        color: "#00ff00".to_string(),
+                    },
+                    ScaleStepConfig {
+                        min: 0,
+                        color: "darkgray".to_string(),
+                    },
+                ]),
+                ..config("mocha")
+            },
+            ColorDepth::Truecolor,
+        )
+        .expect("valid overrides");
+
+        assert_eq!(theme.rainbow, vec![Color::Rgb(255, 0, 0), Color::Blue]);
+        assert_eq!(scale_color(150, &theme.score_scale), Color::Rgb(0, 255, 0));
+        assert_eq!(scale_color(3, &theme.score_scale), Color::DarkGray);
+        assert_eq!(theme.comment_scale.len(), 5);
+    }
+
+    #[test]
+    fn bad_theme_entries_are_reported() {
+        let unknown = Theme::from_config(&config("solarized"), ColorDepth::Truecolor)
+            .expect_err("unknown theme");
+        let unordered = Theme::from_config(
+            &ThemeConfig {
+                comment_scale: Some(vec![
+                    ScaleStepConfig {
+                        min: 0,
+                        color: "red".to_string(),
+                    },
+                    ScaleStepConfig {
+                        min: 10,
+                        color: "blue".to_string(),
+                    },
+                ]),
+                ..ThemeConfig::default()
+            },
+            ColorDepth::Truecolor,
+        )
+        .expect_err("unordered scale");
+
+        assert!(unknown.to_string().contains("unknown theme \"solarized\""));
+        assert_eq!(
+            format!("{unordered:#}"),
+            "comment_scale: steps must be listed from the highest min down"
+        );
+    }
+
+    #[test]
+    fn a_sixteen_color_terminal_defaults_to_the_ansi_palette() {
+        let unnamed =
+            Theme::from_config(&ThemeConfig::default(), ColorDepth::Ansi16).expect("default theme");
+        let named = Theme::from_config(&config("mocha"), ColorDepth::Ansi16).expect("mocha");
+
+        assert_eq!(unnamed.text, Color::Reset);
+        assert!(matches!(named.text, Color::Rgb(..)));
+    }
+
+    #[test]
+    fn sixteen_color_blends_pick_the_nearer_end() {
+        assert_eq!(blend(Color::Blue, Color::Red, 0.2), Color::Blue);
+        assert_eq!(blend(Color::Blue, Color::Red, 0.8), Color::Red);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::api::UserProfile;
+use crate::app::{App, UserRow};
+use crate::ui::story_list::comment_snippet;
+use crate::ui::theme;
+use crate::ui::{format_age, format_error, now_unix};
+use html_escape::decode_html_entities;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Karma line, up to three lines of "about", and the rule under them.
+const HEADER_HEIGHT: u16 = 5;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let spinner = app.spinner_frame();
+    let id = app.user_id.as_deref().unwrap_or("");
+    let title = if app.user_loading {
+        format!("User: {id} (loading {spinner})")
+    } else {
+        format!("User: {id}")
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    frame.render_widget(block, area);
+    let (header_area, list_area, footer_area) = areas(area);
+
+    let now = now_unix();
+    if let Some(profile) = &app.user_profile {
+        let header_block = Block::default().borders(Borders::BOTTOM);
+        let header_inner = header_block.inner(header_area);
+        frame.render_widget(header_block, header_area);
+        frame.render_widget(
+            Paragraph::new(header_lines(profile)).wrap(Wrap { trim: true }),
+            header_inner,
+        );
+    }
+
+    let items = if app.user_profile.is_none() {
+        let message = if app.user_loading {
+            format!("Loading {spinner}")
+        } else {
+            "No profile loaded. Press r to retry.".to_string()
+        };
+        vec![ListItem::new(Line::from(message))]
+    } else if app.user_row_count() == 0 {
+        vec![ListItem::new(Line::from(
+            "No recent submissions or comments.",
+        ))]
+    } else {
+        (0..app.user_row_count())
+            .filter_map(|index| app.user_row(index))
+            .map(|row| row_item(row, now))
+            .collect()
+    };
+    let list = List::new(items)
+        .highlight_symbol("")
+        .highlight_style(theme::selected().add_modifier(Modifier::BOLD));
+    let mut state = app.user_list_state.clone();
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    let footer_block = Block::default().borders(Borders::TOP);
+    let footer_inner = footer_block.inner(footer_area);
+    frame.render_widget(footer_block, footer_area);
+
+    let meta = if let Some(err) = app.last_error.as_deref() {
+        Line::from(Span::styled(
+            format!("Error: {}", format_error(err)),
+            theme::error(),
+        ))
+    } else if let Some(row) = app.user_row(app.user_list_state.selected().unwrap_or(0)) {
+        let story = row.story();
+        Line::from(vec![
+            Span::styled(format!("{} pts", story.score), theme::value()),
+            Span::raw(format!(" by {} ", story.by)),
+            Span::styled(format_age(story.time, now), theme::hint()),
+            Span::raw(" | "),
+            Span::styled(format!("{} comments", story.comment_count), theme::value()),
+        ])
+    } else {
+        Line::from("")
+    };
+    let help = Line::from(
+        "j/k:nav  Enter/Space/l/→:thread  o:source  O:comments  b:save  v:article  r:refresh  ?:help  q/Esc:back",
+    );
+    frame.render_widget(Paragraph::new(vec![meta, help]), footer_inner);
+}
+
+fn header_lines(profile: &UserProfile) -> Vec<Line<'static>> {
+    let joined = chrono::DateTime::from_timestamp(profile.created, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("karma ", theme::label()),
+        Span::styled(profile.karma.to_string(), theme::accent()),
+        Span::styled("  joined ", theme::label()),
+        Span::styled(joined, theme::value()),
+        Span::styled(
+            format!(
+                "  {} submissions · {} comments",
+                profile.submissions.len(),
+                profile.comments.len()
+            ),
+            theme::hint(),
+        ),
+    ])];
+    if let Some(about) = &profile.about {
+        let plain = crate::text::hn_html_to_plain(about);
+        lines.extend(
+            plain
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| Line::styled(line.to_string(), theme::value())),
+        );
+    }
+    lines
+}
+
+fn row_item(row: UserRow<'_>, now: i64) -> ListItem<'static> {
+    let line = match row {
+        UserRow::Submission(story) => Line::from(vec![
+            Span::styled("story    ", theme::meta()),
+            Span::styled(
+                decode_html_entities(&story.title).into_owned(),
+                theme::label(),
+            ),
+            Span::styled(
+                format!("  {}·{}", story.score, story.comment_count),
+                theme::value(),
+            ),
+            Span::styled(format!(" {}", format_age(story.time, now)), theme::hint()),
+        ]),
+        UserRow::Comment(hit) => Line::from(vec![
+            Span::styled("comment  ", theme::meta()),
+            Span::styled("on ", theme::hint()),
+            Span::styled(
+                decode_html_entities(&hit.story.title).into_owned(),
+                theme::label(),
+            ),
+            Span::styled(format!(" {}", format_age(hit.time, now)), theme::hint()),
+            Span::raw("  "),
+            Span::styled(comment_snippet(&hit.text), theme::value()),
+        ]),
+    };
+    ListItem::new(line)
+}
+
+fn areas(area: Rect) -> (Rect, Rect, Rect) {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let [header_area, rest] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(HEADER_HEIGHT), Constraint::Min(1)])
+        .areas(inner);
+    let (list_area, footer_area) = super::list_footer_areas(rest);
+    (header_area, list_area, footer_area)
+}
+
+pub(crate) fn content_areas(area: Rect) -> (Rect, Rect) {
+    let (_, list_area, footer_area) = areas(area);
+    (list_area, footer_area)
+}